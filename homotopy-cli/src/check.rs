@@ -0,0 +1,182 @@
+use std::{fmt, path::PathBuf, process::ExitCode};
+
+use homotopy_core::{
+    typecheck::{typecheck, Mode, TypeError},
+    Diagram, Generator, SliceIndex,
+};
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::{
+    io::{load_hom, print_report, Format},
+    proof::Signature,
+    text::print_diagram,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Check {
+    /// The .hom file to check.
+    #[structopt(parse(from_os_str))]
+    input: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckReport {
+    input: PathBuf,
+    format: Format,
+    ok: bool,
+    diagrams: Vec<DiagramReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagramReport {
+    /// The generator name, or `workspace` for the workspace diagram.
+    name: String,
+    generator: Option<Generator>,
+    dimension: usize,
+    malformed: Vec<String>,
+    type_error: Option<TypeErrorReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct TypeErrorReport {
+    message: String,
+    /// The offending point, as a slice path into the diagram.
+    point: Option<Vec<SliceIndex>>,
+    generator: Option<Generator>,
+    /// The source and target of the neighbourhood of the point, in the text syntax if possible.
+    source: Option<String>,
+    target: Option<String>,
+    /// The points at which a dualisable generator is not a manifold, with their links.
+    non_manifold: Vec<String>,
+}
+
+impl TypeErrorReport {
+    fn new(error: &TypeError, sig: &Signature) -> Self {
+        let print = |diagram: &Diagram| {
+            print_diagram(diagram, sig).unwrap_or_else(|_| {
+                format!("({}-diagram not expressible as text)", diagram.dimension())
+            })
+        };
+        let location = error.location();
+        Self {
+            message: error.to_string(),
+            point: location.map(|location| location.point.clone()),
+            generator: location.map(|location| location.generator),
+            source: location.map(|location| print(&location.source)),
+            target: location.map(|location| print(&location.target)),
+            non_manifold: match error {
+                TypeError::Dualisable(_, points) => {
+                    points.iter().map(ToString::to_string).collect()
+                }
+                _ => vec![],
+            },
+        }
+    }
+}
+
+impl DiagramReport {
+    fn new(name: String, generator: Option<Generator>, diagram: &Diagram, sig: &Signature) -> Self {
+        let malformed: Vec<_> = diagram
+            .check(true)
+            .err()
+            .into_iter()
+            .flatten()
+            .map(|e| e.to_string())
+            .collect();
+
+        // Typechecking assumes a well-formed diagram.
+        let type_error = if malformed.is_empty() {
+            typecheck(diagram, sig, Mode::default(), true)
+                .err()
+                .map(|e| TypeErrorReport::new(&e, sig))
+        } else {
+            None
+        };
+
+        Self {
+            name,
+            generator,
+            dimension: diagram.dimension(),
+            malformed,
+            type_error,
+        }
+    }
+
+    fn is_ok(&self) -> bool {
+        self.malformed.is_empty() && self.type_error.is_none()
+    }
+}
+
+impl Check {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let (proof, format) = load_hom(&self.input)?;
+
+        let mut diagrams: Vec<_> = proof
+            .signature
+            .iter()
+            .map(|info| {
+                DiagramReport::new(
+                    info.name.clone(),
+                    Some(info.generator),
+                    &info.diagram,
+                    &proof.signature,
+                )
+            })
+            .collect();
+        if let Some(ws) = &proof.workspace {
+            diagrams.push(DiagramReport::new(
+                "workspace".to_owned(),
+                None,
+                &ws.diagram,
+                &proof.signature,
+            ));
+        }
+
+        let report = CheckReport {
+            input: self.input.clone(),
+            format,
+            ok: diagrams.iter().all(DiagramReport::is_ok),
+            diagrams,
+        };
+        print_report(&report, json)?;
+
+        Ok(if report.ok {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        })
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({} format)", self.input.display(), self.format)?;
+        for d in &self.diagrams {
+            let status = if d.is_ok() { "ok" } else { "FAILED" };
+            match d.generator {
+                Some(g) => writeln!(f, "  {:?} {}: {status}", g, d.name)?,
+                None => writeln!(f, "  {}: {status}", d.name)?,
+            }
+            for e in &d.malformed {
+                writeln!(f, "    malformed: {e}")?;
+            }
+            if let Some(e) = &d.type_error {
+                writeln!(f, "    type error: {}", e.message)?;
+                if let (Some(source), Some(target)) = (&e.source, &e.target) {
+                    writeln!(f, "      neighbourhood source: {source}")?;
+                    writeln!(f, "      neighbourhood target: {target}")?;
+                }
+                for point in &e.non_manifold {
+                    writeln!(f, "      non-manifold point {point}")?;
+                }
+            }
+        }
+        let failed = self.diagrams.iter().filter(|d| !d.is_ok()).count();
+        writeln!(
+            f,
+            "{} diagrams checked, {failed} failed",
+            self.diagrams.len()
+        )
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fmt, path::PathBuf, process::ExitCode};
+
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::io::{export_hom, load_hom, print_report, Format};
+
+#[derive(Debug, StructOpt)]
+pub struct Convert {
+    /// The .hom file to convert, in either the legacy or the current format, a .txt file in the
+    /// text syntax, or a .json file.
+    #[structopt(parse(from_os_str))]
+    input: PathBuf,
+
+    /// Where to write the converted file, in the text syntax if it ends in .txt and as JSON if it
+    /// ends in .json.
+    #[structopt(parse(from_os_str))]
+    output: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct ConvertReport {
+    input: PathBuf,
+    output: PathBuf,
+    format: Format,
+    generators: usize,
+}
+
+impl Convert {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let (proof, format) = load_hom(&self.input)?;
+        export_hom(&self.output, &proof)?;
+
+        let report = ConvertReport {
+            input: self.input.clone(),
+            output: self.output.clone(),
+            format,
+            generators: proof.signature.iter().count(),
+        };
+        print_report(&report, json)?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+impl fmt::Display for ConvertReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Converted {} ({} format, {} generators) to {}",
+            self.input.display(),
+            self.format,
+            self.generators,
+            self.output.display()
+        )
+    }
+}
@@ -0,0 +1,240 @@
+use std::{fmt, fs::write, path::PathBuf, process::ExitCode, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+use homotopy_core::{Diagram, DiagramN, SliceIndex};
+use homotopy_graphics::{
+    manim::{self, ManimOptions},
+    stl::{self, StlOptions},
+    svg::{self, SvgOptions},
+    tikz::{self, TikzOptions},
+};
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::io::{load_hom, print_report};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ImageFormat {
+    Tikz,
+    Svg,
+    Manim,
+    Stl,
+}
+
+impl ImageFormat {
+    const VARIANTS: &'static [&'static str] = &["tikz", "svg", "manim", "stl"];
+}
+
+impl FromStr for ImageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tikz" => Ok(Self::Tikz),
+            "svg" => Ok(Self::Svg),
+            "manim" => Ok(Self::Manim),
+            "stl" => Ok(Self::Stl),
+            _ => Err(anyhow!("unknown image format: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tikz => f.write_str("tikz"),
+            Self::Svg => f.write_str("svg"),
+            Self::Manim => f.write_str("manim"),
+            Self::Stl => f.write_str("stl"),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Export {
+    /// The .hom file containing the diagram to render.
+    #[structopt(parse(from_os_str))]
+    input: PathBuf,
+
+    /// The image format to produce.
+    #[structopt(short, long, possible_values = ImageFormat::VARIANTS)]
+    format: ImageFormat,
+
+    /// Render the diagram of the generator with this name instead of the workspace.
+    #[structopt(short, long)]
+    generator: Option<String>,
+
+    /// The slice to render, as a comma-separated list of slice indices such as `S,R1,S0`.
+    /// Defaults to the slice shown in the workspace, or the whole generator diagram.
+    #[structopt(short, long, use_delimiter = true)]
+    path: Option<Vec<SliceIndex>>,
+
+    /// The number of dimensions to project onto. Defaults to the view of the workspace, or to as
+    /// many dimensions as the format supports.
+    #[structopt(short, long)]
+    view: Option<u8>,
+
+    /// Where to write the image. Defaults to standard output.
+    #[structopt(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+
+    /// Render TikZ diagrams from left to right.
+    #[structopt(long)]
+    left_to_right: bool,
+
+    /// Do not use masking to render braidings in TikZ.
+    #[structopt(long)]
+    hide_braidings: bool,
+
+    /// Generate Manim code for the OpenGL renderer.
+    #[structopt(long)]
+    opengl: bool,
+
+    /// The number of samples used to render STL geometry.
+    #[structopt(long, default_value = "10")]
+    geometry_samples: u8,
+
+    /// The subdivision depth used to render STL geometry.
+    #[structopt(long, default_value = "2")]
+    subdivision_depth: u8,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportReport {
+    input: PathBuf,
+    output: PathBuf,
+    format: ImageFormat,
+    generator: Option<String>,
+    path: Vec<SliceIndex>,
+    view: u8,
+}
+
+impl Export {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let (proof, _) = load_hom(&self.input)?;
+
+        let (diagram, path, view) = match &self.generator {
+            Some(name) => {
+                let info = proof
+                    .signature
+                    .iter()
+                    .find(|info| &info.name == name)
+                    .ok_or_else(|| anyhow!("No generator named {name:?}."))?;
+                (
+                    info.diagram.clone(),
+                    self.path.clone().unwrap_or_default(),
+                    None,
+                )
+            }
+            None => {
+                let ws = proof
+                    .workspace
+                    .as_ref()
+                    .context("The workspace is empty.")?;
+                match &self.path {
+                    Some(path) => (ws.diagram.clone(), path.clone(), None),
+                    None => (
+                        ws.diagram.clone(),
+                        ws.path.iter().copied().collect(),
+                        Some(ws.view.dimension()),
+                    ),
+                }
+            }
+        };
+
+        let diagram = path.iter().try_fold(diagram, |diagram, &index| {
+            DiagramN::try_from(diagram)
+                .ok()
+                .and_then(|d| d.slice(index))
+                .ok_or_else(|| anyhow!("Slice path {path:?} does not exist in the diagram."))
+        })?;
+
+        let view = self.view.or(view).unwrap_or_else(|| {
+            let max_view = if self.format == ImageFormat::Stl {
+                3
+            } else {
+                2
+            };
+            diagram.dimension().min(max_view) as u8
+        });
+
+        let data = self
+            .render(&diagram, view, &proof.signature)
+            .with_context(|| {
+                format!("Could not render {view}-dimensional {} image.", self.format)
+            })?;
+
+        let Some(output) = &self.output else {
+            print!("{data}");
+            return Ok(ExitCode::SUCCESS);
+        };
+        write(output, data).context("Could not write image.")?;
+
+        let report = ExportReport {
+            input: self.input.clone(),
+            output: output.clone(),
+            format: self.format,
+            generator: self.generator.clone(),
+            path,
+            view,
+        };
+        print_report(&report, json)?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+
+    fn render(
+        &self,
+        diagram: &Diagram,
+        view: u8,
+        signature: &crate::proof::Signature,
+    ) -> anyhow::Result<String> {
+        let data = match self.format {
+            ImageFormat::Tikz => {
+                let options = TikzOptions {
+                    left_to_right: self.left_to_right,
+                    show_braidings: !self.hide_braidings,
+                };
+                tikz::render(diagram, view, signature, options)?
+            }
+            ImageFormat::Svg => svg::render(diagram, view, signature, SvgOptions::default())?,
+            ImageFormat::Manim => {
+                let options = ManimOptions {
+                    use_opengl: self.opengl,
+                };
+                let stylesheet = manim::stylesheet(signature);
+                manim::render(diagram, view, signature, &stylesheet, options)?
+            }
+            ImageFormat::Stl => {
+                if view != 3 {
+                    bail!("STL export requires a 3-dimensional view.");
+                }
+                let options = StlOptions {
+                    geometry_samples: self.geometry_samples,
+                    subdivision_depth: self.subdivision_depth,
+                };
+                stl::render(diagram, signature, options)?
+            }
+        };
+        Ok(data)
+    }
+}
+
+impl fmt::Display for ExportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match &self.generator {
+            Some(name) => format!("generator {name}"),
+            None => "workspace".to_owned(),
+        };
+        writeln!(
+            f,
+            "Rendered {what} of {} at path {:?} as {}d {} to {}",
+            self.input.display(),
+            self.path,
+            self.view,
+            self.format,
+            self.output.display()
+        )
+    }
+}
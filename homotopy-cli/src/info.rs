@@ -0,0 +1,122 @@
+use std::{collections::BTreeMap, fmt, path::PathBuf, process::ExitCode};
+
+use homotopy_core::{signature::Invertibility, Generator, SliceIndex};
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::{
+    io::{load_hom, print_report, Format},
+    proof::Metadata,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Info {
+    /// The .hom file to summarise.
+    #[structopt(parse(from_os_str))]
+    input: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct InfoReport {
+    input: PathBuf,
+    format: Format,
+    metadata: Metadata,
+    /// Number of generators of each dimension.
+    counts: BTreeMap<usize, usize>,
+    generators: Vec<GeneratorSummary>,
+    workspace: Option<WorkspaceSummary>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeneratorSummary {
+    generator: Generator,
+    name: String,
+    invertibility: Invertibility,
+    oriented: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct WorkspaceSummary {
+    dimension: usize,
+    size: Option<usize>,
+    view: u8,
+    path: Vec<SliceIndex>,
+}
+
+impl Info {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let (proof, format) = load_hom(&self.input)?;
+
+        let generators: Vec<_> = proof
+            .signature
+            .iter()
+            .map(|info| GeneratorSummary {
+                generator: info.generator,
+                name: info.name.clone(),
+                invertibility: info.invertibility,
+                oriented: info.oriented,
+            })
+            .collect();
+
+        let mut counts = BTreeMap::new();
+        for g in &generators {
+            *counts.entry(g.generator.dimension).or_default() += 1;
+        }
+
+        let report = InfoReport {
+            input: self.input.clone(),
+            format,
+            metadata: proof.metadata.clone(),
+            counts,
+            generators,
+            workspace: proof.workspace.as_ref().map(|ws| WorkspaceSummary {
+                dimension: ws.diagram.dimension(),
+                size: ws.diagram.size(),
+                view: ws.view.dimension(),
+                path: ws.path.iter().copied().collect(),
+            }),
+        };
+        print_report(&report, json)?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+impl fmt::Display for InfoReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({} format)", self.input.display(), self.format)?;
+        if let Some(title) = &self.metadata.title {
+            writeln!(f, "title: {title}")?;
+        }
+        if let Some(author) = &self.metadata.author {
+            writeln!(f, "author: {author}")?;
+        }
+
+        writeln!(f, "{} generators", self.generators.len())?;
+        for (dimension, count) in &self.counts {
+            writeln!(f, "  dimension {dimension}: {count}")?;
+        }
+        for g in &self.generators {
+            writeln!(
+                f,
+                "  {:?} {} ({}{})",
+                g.generator,
+                g.name,
+                g.invertibility,
+                if g.oriented { ", oriented" } else { "" }
+            )?;
+        }
+
+        match &self.workspace {
+            None => writeln!(f, "workspace: empty"),
+            Some(ws) => writeln!(
+                f,
+                "workspace: dimension {}, size {}, viewing {}d at path {:?}",
+                ws.dimension,
+                ws.size.unwrap_or_default(),
+                ws.view,
+                ws.path
+            ),
+        }
+    }
+}
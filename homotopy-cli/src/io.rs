@@ -0,0 +1,161 @@
+use std::{
+    ffi::OsStr,
+    fmt::Display,
+    fs::{read, read_to_string, write},
+    path::Path,
+};
+
+use anyhow::{anyhow, Context};
+use serde::Serialize;
+
+use crate::{
+    json, migration,
+    proof::{ProofState, Workspace},
+    serialize, text, Action, Proof,
+};
+
+/// The name under which the workspace is bound in the text syntax.
+const WORKSPACE: &str = "workspace";
+
+/// The on-disk format a .hom file was read from.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Current,
+    Legacy,
+    Text,
+    Json,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Current => f.write_str("current"),
+            Self::Legacy => f.write_str("legacy"),
+            Self::Text => f.write_str("text"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// Files with this extension are read and written in the text syntax.
+fn is_text(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new("txt"))
+}
+
+/// Files with this extension are read and written in the JSON format.
+fn is_json(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new("json"))
+}
+
+/// Load a .hom file in either the current or the legacy format, a .txt file in the text syntax,
+/// or a .json file in the JSON format, without checking its diagrams.
+pub fn load_hom(path: &Path) -> anyhow::Result<(Proof, Format)> {
+    if is_json(path) {
+        let ((signature, workspace), metadata) =
+            json::import(&read_to_string(path)?).context("Failed to import JSON file.")?;
+        let mut proof: Proof = Default::default();
+        proof.signature = signature;
+        proof.workspace = workspace;
+        proof.metadata = metadata;
+        return Ok((proof, Format::Json));
+    }
+
+    if is_text(path) {
+        let document = text::parse(&read_to_string(path)?).context("Failed to parse text file.")?;
+        let mut proof: Proof = Default::default();
+        proof.workspace = document.diagram(WORKSPACE).cloned().map(Workspace::new);
+        proof.signature = document.signature;
+        return Ok((proof, Format::Text));
+    }
+
+    let data = read(path)?;
+    let mut proof: Proof = Default::default();
+    let format = if let Some((state, _)) = serialize::deserialize_session(&data) {
+        proof.signature = state.signature;
+        proof.workspace = state.workspace;
+        proof.metadata = state.metadata;
+        proof.boundary = state.boundary;
+        proof.stash = state.stash;
+        Format::Current
+    } else {
+        let ((signature, workspace), metadata) = migration::deserialize(&data)
+            .context("Failed to deserialize or migrate from legacy format.")?;
+        proof.signature = signature;
+        proof.workspace = workspace;
+        proof.metadata = metadata;
+        Format::Legacy
+    };
+    Ok((proof, format))
+}
+
+/// Load a .hom file and deep-check all of its diagrams.
+pub fn import_hom(path: &Path) -> anyhow::Result<Proof> {
+    let (proof, _) = load_hom(path)?;
+
+    for g in proof.signature.iter() {
+        g.diagram
+            .check(true)
+            .map_err(|e| anyhow!("Signature diagram deep check failed: {:?}", e))?;
+    }
+    if let Some(w) = proof.workspace.as_ref() {
+        w.diagram
+            .check(true)
+            .map_err(|e| anyhow!("Workspace diagram deep check failed: {:?}", e))?;
+    }
+
+    Ok(proof)
+}
+
+pub fn export_hom(path: &Path, proof: &ProofState) -> anyhow::Result<()> {
+    if is_json(path) {
+        let data = json::export(&proof.signature, proof.workspace.as_ref(), &proof.metadata);
+        return write(path, data).context("Could not export JSON file.");
+    }
+    if is_text(path) {
+        let document = text::Document {
+            signature: proof.signature.clone(),
+            diagrams: proof
+                .workspace
+                .iter()
+                .map(|ws| (WORKSPACE.to_owned(), ws.diagram.clone()))
+                .collect(),
+        };
+        let data = text::print(&document).context("Could not print proof as text.")?;
+        return write(path, data).context("Could not export text file.");
+    }
+
+    let data = serialize::serialize_session(proof, None);
+    write(path, data).context("Could not export .hom file.")
+}
+
+/// Import an exported action log.
+///
+/// If the log is marked as unsafe, its last action is the one that crashed and is returned
+/// separately.
+pub fn import_actions(path: &Path) -> anyhow::Result<(Vec<Action>, Option<Action>)> {
+    let data = read(path)?;
+    let (safe, mut actions): (bool, Vec<_>) = serde_json::from_slice(&data)?;
+    if safe {
+        Ok((actions, None))
+    } else {
+        let last_action = actions.pop();
+        Ok((actions, last_action))
+    }
+}
+
+/// Export an action log whose last action is the one that crashes, so that it can be replayed.
+pub fn export_actions(path: &Path, actions: &[Action]) -> anyhow::Result<()> {
+    let data = serde_json::to_vec(&(false, actions))?;
+    write(path, data).context("Could not export action file.")
+}
+
+/// Print a command report to standard output, either as JSON or in human-readable form.
+pub fn print_report(report: &(impl Serialize + Display), json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+    } else {
+        print!("{report}");
+    }
+    Ok(())
+}
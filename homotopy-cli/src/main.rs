@@ -1,13 +1,19 @@
-use std::{
-    fs::{read, write},
-    path::PathBuf,
-};
+use std::process::ExitCode;
 
-use anyhow::{anyhow, Context};
 pub use history::Proof;
-pub use homotopy_model::{history, migration, proof, proof::Action, serialize};
+pub use homotopy_model::{history, json, migration, proof, proof::Action, serialize, text};
 use structopt::StructOpt;
 
+mod check;
+mod convert;
+mod export;
+mod info;
+mod io;
+mod minimize;
+mod morphism;
+mod regress;
+mod replay;
+
 // Struct for CLI options
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -15,95 +21,53 @@ use structopt::StructOpt;
     about = "Handy tool to debug proofs! Made by yours truly."
 )]
 struct Opt {
-    #[structopt(short, long, parse(from_os_str))]
-    input_hom: Option<PathBuf>,
+    /// Print machine-readable JSON instead of human-readable text.
+    #[structopt(long, global = true)]
+    json: bool,
 
-    #[structopt(short = "a", long, parse(from_os_str))]
-    input_actions: Option<PathBuf>,
+    #[structopt(subcommand)]
+    command: Command,
+}
 
-    #[structopt(short, long, parse(from_os_str))]
-    output_hom: Option<PathBuf>,
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Deep-check and typecheck every diagram in a .hom file.
+    Check(check::Check),
 
-    #[structopt(short, long)]
-    no_replay_crash: bool,
-}
+    /// Summarise the signature and workspace of a .hom file.
+    Info(info::Info),
 
-fn import_actions(path: &PathBuf) -> anyhow::Result<(Vec<Action>, Option<Action>)> {
-    let data = read(path)?;
-    let (safe, actions): (bool, Vec<_>) = serde_json::from_slice(&data)?;
-    if safe {
-        Ok((actions, None))
-    } else {
-        let len = actions.len();
-        let last_action = Some(actions[len - 1].clone());
-        Ok((actions[..len - 1].to_vec(), last_action))
-    }
-}
+    /// Replay an exported action log, optionally on top of a .hom file.
+    Replay(replay::Replay),
 
-fn import_hom(path: &PathBuf) -> anyhow::Result<Proof> {
-    let data = read(path)?;
-    let ((signature, workspace), metadata) = match serialize::deserialize(&data) {
-        Some(res) => res,
-        None => migration::deserialize(&data)
-            .context("Failed to deserialize or migrate from legacy format.")?,
-    };
+    /// Shrink an action log ending in a crash or an error, and the .hom file it starts from, to a
+    /// minimal reproducer.
+    Minimize(minimize::Minimize),
 
-    for g in signature.iter() {
-        g.diagram
-            .check(true)
-            .map_err(|e| anyhow!("Signature diagram deep check failed: {:?}", e))?;
-    }
-    if let Some(w) = workspace.as_ref() {
-        w.diagram
-            .check(true)
-            .map_err(|e| anyhow!("Workspace diagram deep check failed: {:?}", e))?;
-    }
+    /// Convert a .hom file to the current format, or to or from the text syntax or JSON.
+    Convert(convert::Convert),
 
-    let mut proof: Proof = Default::default();
-    proof.signature = signature;
-    proof.workspace = workspace;
-    proof.metadata = metadata;
-    Ok(proof)
-}
+    /// Render the workspace or a generator of a .hom file to an image.
+    Export(export::Export),
 
-fn export_hom(path: &PathBuf, proof: &Proof) -> anyhow::Result<()> {
-    let data = serialize::serialize(
-        proof.signature.clone(),
-        proof.workspace.clone(),
-        proof.metadata.clone(),
-    );
-    write(path, data).context("Could not export .hom file.")
+    /// Check, typecheck and round-trip every .hom file in a corpus, reporting timings.
+    Regress(regress::Regress),
+
+    /// Transport a .hom file into the signature of another, matching generators by name.
+    ApplyMorphism(morphism::ApplyMorphism),
 }
 
-fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<ExitCode> {
     // Give me options.
     let opt = Opt::from_args();
-    let mut proof = match opt.input_hom {
-        Some(path) => import_hom(&path).context("Could not import .hom file.")?,
-        None => Default::default(),
-    };
-
-    let (actions, last_action) = match opt.input_actions {
-        Some(path) => import_actions(&path).context("Could not import action file.")?,
-        None => Default::default(),
-    };
-
-    for a in &actions {
-        println!("Performing action: {a:?}");
-        proof.update(a)?;
-    }
-
-    if !opt.no_replay_crash {
-        if let Some(a) = last_action {
-            println!("Performing final action: {a:?}");
-            // When debugging, set a breakpoint here!
-            proof.update(&a)?;
-        }
-    }
-
-    if let Some(path) = opt.output_hom {
-        export_hom(&path, &proof)?;
+    match opt.command {
+        Command::Check(cmd) => cmd.run(opt.json),
+        Command::Info(cmd) => cmd.run(opt.json),
+        Command::Replay(cmd) => cmd.run(opt.json),
+        Command::Minimize(cmd) => cmd.run(opt.json),
+        Command::Convert(cmd) => cmd.run(opt.json),
+        Command::Export(cmd) => cmd.run(opt.json),
+        Command::Regress(cmd) => cmd.run(opt.json),
+        Command::ApplyMorphism(cmd) => cmd.run(opt.json),
     }
-
-    Ok(())
 }
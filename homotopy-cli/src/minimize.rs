@@ -0,0 +1,385 @@
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::ExitCode,
+    sync::Mutex,
+};
+
+use anyhow::{anyhow, Context};
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::{
+    io::{export_actions, export_hom, import_actions, import_hom, print_report},
+    proof::{ProofState, SignatureEdit, SignatureItem},
+    serialize, Action,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Minimize {
+    /// The .hom file the log starts from. Defaults to an empty proof.
+    #[structopt(short, long, parse(from_os_str))]
+    input_hom: Option<PathBuf>,
+
+    /// The exported action log which ends in a crash or an error.
+    #[structopt(short = "a", long, parse(from_os_str))]
+    input_actions: PathBuf,
+
+    /// Where to write the simplified .hom file.
+    #[structopt(short, long, parse(from_os_str))]
+    output_hom: PathBuf,
+
+    /// Where to write the minimal action log.
+    #[structopt(short = "A", long, parse(from_os_str))]
+    output_actions: PathBuf,
+}
+
+/// The way in which replaying a log fails.
+///
+/// Panics are told apart by where they happen, since their messages often mention indices which
+/// change as the log shrinks. Errors are told apart by their message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Failure {
+    Panic { location: String, message: String },
+    Error { message: String },
+}
+
+impl Failure {
+    fn same_as(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Panic { location: a, .. }, Self::Panic { location: b, .. }) => a == b,
+            (Self::Error { message: a }, Self::Error { message: b }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic { location, message } => write!(f, "panic at {location}: {message}"),
+            Self::Error { message } => write!(f, "error: {message}"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct MinimizeReport {
+    failure: Failure,
+    actions: (usize, usize),
+    generators: (usize, usize),
+    replays: usize,
+    output_hom: PathBuf,
+    output_actions: PathBuf,
+}
+
+/// The location of the last panic, recorded by the hook installed while minimising. Panics in
+/// worker threads are resumed on the main thread, so this is shared between threads.
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Replays logs on a starting proof, remembering the failure to reproduce.
+struct Reproducer {
+    failure: Failure,
+    replays: usize,
+}
+
+impl Reproducer {
+    /// Replays `actions` on `proof` until the first action that fails.
+    ///
+    /// Returns the failure together with the number of actions performed, including the one that
+    /// failed.
+    fn replay(proof: &ProofState, actions: &[Action]) -> Option<(Failure, usize)> {
+        let mut proof = proof.clone();
+        for (i, action) in actions.iter().enumerate() {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| proof.update(action)));
+            let failure = match result {
+                Ok(Ok(_)) => continue,
+                Ok(Err(error)) => Failure::Error {
+                    message: error.to_string(),
+                },
+                Err(payload) => Failure::Panic {
+                    location: LAST_PANIC.lock().unwrap().take().unwrap_or_default(),
+                    message: payload
+                        .downcast_ref::<&str>()
+                        .map(ToString::to_string)
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default(),
+                },
+            };
+            return Some((failure, i + 1));
+        }
+        None
+    }
+
+    /// Determines if `actions` still fail the same way on `proof`, and if so returns them up to
+    /// the failing action.
+    fn reproduces(&mut self, proof: &ProofState, actions: &[Action]) -> Option<Vec<Action>> {
+        self.replays += 1;
+        let (failure, len) = Self::replay(proof, actions)?;
+        failure
+            .same_as(&self.failure)
+            .then(|| actions[..len].to_vec())
+    }
+
+    /// Shrinks a log by delta debugging, keeping the part of it up to the failing action.
+    fn minimize_actions(&mut self, proof: &ProofState, actions: Vec<Action>) -> Vec<Action> {
+        ddmin(actions, |candidate| self.reproduces(proof, candidate))
+    }
+
+    /// Simplifies the starting proof by clearing its workspace, boundary, stash and metadata and
+    /// by removing items from its signature, as long as the log still fails the same way.
+    ///
+    /// Candidates go through the .hom format, so that the written reproducer behaves the same.
+    fn minimize_proof(&mut self, mut proof: ProofState, actions: &[Action]) -> ProofState {
+        let simplifications: [fn(&mut ProofState); 4] = [
+            |proof| proof.workspace = None,
+            |proof| proof.boundary = None,
+            |proof| proof.stash = Default::default(),
+            |proof| proof.metadata = Default::default(),
+        ];
+        for simplify in simplifications {
+            let mut candidate = proof.clone();
+            simplify(&mut candidate);
+            self.try_proof(&mut proof, candidate, actions);
+        }
+
+        // Removing a generator also removes everything that depends on it, so try the generators
+        // that were added last first. Nodes are looked up afresh each time, since they need not
+        // survive the trip through the .hom format.
+        let generators: Vec<_> = proof.signature.iter().map(|info| info.generator).collect();
+        for generator in generators.into_iter().rev() {
+            let node = proof.signature.as_tree().iter().find_map(|(node, item)| {
+                matches!(item.inner(), SignatureItem::Item(info) if info.generator == generator)
+                    .then_some(node)
+            });
+            let Some(node) = node else { continue };
+            let mut candidate = proof.clone();
+            let edit = Action::EditSignature(SignatureEdit::Remove(node));
+            if matches!(candidate.update(&edit), Ok(true)) {
+                self.try_proof(&mut proof, candidate, actions);
+            }
+        }
+
+        proof
+    }
+
+    /// Replaces `proof` by `candidate` if the log still fails the same way on it.
+    fn try_proof(&mut self, proof: &mut ProofState, candidate: ProofState, actions: &[Action]) {
+        let data = serialize::serialize_session(&candidate, None);
+        if let Some((candidate, _)) = serialize::deserialize_session(&data) {
+            if self.reproduces(&candidate, actions).is_some() {
+                *proof = candidate;
+            }
+        }
+    }
+}
+
+/// Shrinks a list by delta debugging: repeatedly tries to keep only a chunk of it, or to drop a
+/// chunk of it, and refines the chunks when neither is interesting.
+///
+/// `test` returns the part of a candidate which is interesting, if any. The result is 1-minimal:
+/// dropping any single item from it makes it uninteresting.
+fn ddmin<T: Clone>(mut items: Vec<T>, mut test: impl FnMut(&[T]) -> Option<Vec<T>>) -> Vec<T> {
+    let mut chunks = 2;
+    while items.len() >= 2 {
+        let size = items.len().div_ceil(chunks);
+        let ranges: Vec<_> = (0..items.len())
+            .step_by(size)
+            .map(|start| start..(start + size).min(items.len()))
+            .collect();
+
+        let reduced = ranges.iter().find_map(|range| {
+            test(&items[range.clone()])
+                .map(|reduced| (reduced, 2))
+                .or_else(|| {
+                    let complement: Vec<_> = items[..range.start]
+                        .iter()
+                        .chain(&items[range.end..])
+                        .cloned()
+                        .collect();
+                    test(&complement).map(|reduced| (reduced, (chunks - 1).max(2)))
+                })
+        });
+
+        match reduced {
+            Some((reduced, next)) => {
+                items = reduced;
+                chunks = next;
+            }
+            None if chunks >= items.len() => break,
+            None => chunks = (chunks * 2).min(items.len()),
+        }
+    }
+    items
+}
+
+impl Minimize {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let proof: ProofState = match &self.input_hom {
+            Some(path) => {
+                let proof = import_hom(path).context("Could not import .hom file.")?;
+                ProofState::clone(&proof)
+            }
+            None => Default::default(),
+        };
+        let generators = proof.signature.iter().count();
+
+        let (mut actions, last_action) =
+            import_actions(&self.input_actions).context("Could not import action file.")?;
+        actions.extend(last_action);
+
+        // Keep the output clean of the many panics provoked along the way.
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|info| {
+            *LAST_PANIC.lock().unwrap() = info.location().map(ToString::to_string);
+        }));
+        let result = minimize(proof, &actions);
+        panic::set_hook(hook);
+        let (proof, minimized, reproducer) = result?;
+
+        export_hom(&self.output_hom, &proof)?;
+        export_actions(&self.output_actions, &minimized)?;
+
+        let report = MinimizeReport {
+            failure: reproducer.failure,
+            actions: (actions.len(), minimized.len()),
+            generators: (generators, proof.signature.iter().count()),
+            replays: reproducer.replays,
+            output_hom: self.output_hom.clone(),
+            output_actions: self.output_actions.clone(),
+        };
+        print_report(&report, json)?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn minimize(
+    mut proof: ProofState,
+    actions: &[Action],
+) -> anyhow::Result<(ProofState, Vec<Action>, Reproducer)> {
+    let (failure, len) = Reproducer::replay(&proof, actions)
+        .ok_or_else(|| anyhow!("The action log does not fail when replayed."))?;
+    eprintln!("Reproducing {failure}");
+
+    let mut reproducer = Reproducer {
+        failure,
+        replays: 1,
+    };
+    let mut minimized = actions[..len].to_vec();
+
+    // Shrinking the proof can make more of the log redundant and vice versa, so alternate until
+    // neither changes.
+    loop {
+        let before = (minimized.len(), proof.signature.iter().count());
+        minimized = reproducer.minimize_actions(&proof, minimized);
+        proof = reproducer.minimize_proof(proof, &minimized);
+        let after = (minimized.len(), proof.signature.iter().count());
+        eprintln!("Reduced to {} actions and {} generators", after.0, after.1);
+        if after == before {
+            break;
+        }
+    }
+
+    Ok((proof, minimized, reproducer))
+}
+
+impl fmt::Display for MinimizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Minimized {}", self.failure)?;
+        writeln!(
+            f,
+            "Actions: {} -> {}, generators: {} -> {}, in {} replays",
+            self.actions.0, self.actions.1, self.generators.0, self.generators.1, self.replays
+        )?;
+        writeln!(f, "Wrote {}", self.output_hom.display())?;
+        writeln!(f, "Wrote {}", self.output_actions.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use homotopy_core::signature::Invertibility;
+
+    use super::*;
+    use crate::proof::ProofError;
+
+    #[test]
+    fn ddmin_is_one_minimal() {
+        let interesting = |items: &[usize]| items.contains(&3) && items.contains(&7);
+        let minimized = ddmin((0..20).collect(), |items| {
+            interesting(items).then(|| items.to_vec())
+        });
+
+        assert_eq!(minimized, [3, 7]);
+        for i in 0..minimized.len() {
+            let mut smaller = minimized.clone();
+            smaller.remove(i);
+            assert!(!interesting(&smaller));
+        }
+    }
+
+    #[test]
+    fn ddmin_keeps_the_interesting_prefix() {
+        // Like replaying a log, only the part up to the first failure is kept.
+        let minimized = ddmin((0..16).collect(), |items| {
+            let end = items.iter().position(|&item| item == 11)?;
+            items[..end].contains(&5).then(|| items[..=end].to_vec())
+        });
+
+        assert_eq!(minimized, [5, 11]);
+    }
+
+    #[test]
+    fn panics_are_compared_by_location() {
+        let panic = |location: &str, message: &str| Failure::Panic {
+            location: location.to_owned(),
+            message: message.to_owned(),
+        };
+        let error = |message: &str| Failure::Error {
+            message: message.to_owned(),
+        };
+
+        assert!(panic("a.rs:1:2", "index 3").same_as(&panic("a.rs:1:2", "index 1")));
+        assert!(!panic("a.rs:1:2", "index 3").same_as(&panic("a.rs:5:2", "index 3")));
+        assert!(error("oops").same_as(&error("oops")));
+        assert!(!error("oops").same_as(&error("whoops")));
+        assert!(!error("a.rs:1:2").same_as(&panic("a.rs:1:2", "a.rs:1:2")));
+    }
+
+    #[test]
+    fn unrelated_generators_are_removed() {
+        let mut proof = ProofState::default();
+        let x = proof.signature.create_generator_zero("x");
+        let y = proof.signature.create_generator_zero("y");
+        let f = proof
+            .signature
+            .create_generator(x.into(), x.into(), "f", Invertibility::Directed)
+            .unwrap()
+            .max_generator();
+
+        // Inverting a directed generator fails, so only f and its boundary are needed.
+        let actions = [
+            Action::SelectGenerator(y.generator),
+            Action::SelectGenerator(f.generator),
+            Action::Invert,
+            Action::ClearWorkspace,
+        ];
+        let (proof, minimized, reproducer) = minimize(proof, &actions).unwrap();
+
+        let generators: Vec<_> = proof.signature.iter().map(|info| info.generator).collect();
+        assert_eq!(generators, [x.generator, f.generator]);
+        assert_eq!(
+            minimized,
+            [Action::SelectGenerator(f.generator), Action::Invert]
+        );
+        assert_eq!(
+            reproducer.failure,
+            Failure::Error {
+                message: ProofError::NotInvertible.to_string()
+            }
+        );
+    }
+}
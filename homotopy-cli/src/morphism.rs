@@ -0,0 +1,83 @@
+use std::{fmt, path::PathBuf, process::ExitCode};
+
+use anyhow::anyhow;
+use homotopy_core::{morphism::MorphismError, signature::Signature as _};
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::io::{export_hom, load_hom, print_report};
+
+#[derive(Debug, StructOpt)]
+pub struct ApplyMorphism {
+    /// The .hom file to transport.
+    #[structopt(parse(from_os_str))]
+    input: PathBuf,
+
+    /// The .hom file whose signature to transport into. Each generator of the input is sent to
+    /// the generator of the same name and dimension.
+    #[structopt(parse(from_os_str))]
+    target: PathBuf,
+
+    /// Where to write the transported proof.
+    #[structopt(parse(from_os_str))]
+    output: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct MorphismReport {
+    input: PathBuf,
+    target: PathBuf,
+    output: PathBuf,
+    generators: usize,
+}
+
+impl ApplyMorphism {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let (mut proof, _) = load_hom(&self.input)?;
+        let (target, _) = load_hom(&self.target)?;
+
+        let morphism = proof
+            .signature
+            .morphism_by_name(&target.signature)
+            .map_err(|error| match error {
+                MorphismError::Missing(generator) => anyhow!(
+                    "No unique generator of dimension {} named \"{}\" in the target.",
+                    generator.dimension,
+                    proof
+                        .signature
+                        .generator_info(generator)
+                        .map_or("", |info| info.name.as_str())
+                ),
+                error => {
+                    anyhow!("Failed to match the generators of the input with the target: {error}")
+                }
+            })?;
+        **proof = proof
+            .transport(&morphism, target.signature.clone())
+            .map_err(|error| anyhow!("Failed to transport the proof: {error}"))?;
+        export_hom(&self.output, &proof)?;
+
+        let report = MorphismReport {
+            input: self.input.clone(),
+            target: self.target.clone(),
+            output: self.output.clone(),
+            generators: proof.signature.iter().count(),
+        };
+        print_report(&report, json)?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+impl fmt::Display for MorphismReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Transported {} into the signature of {} ({} generators) and wrote it to {}",
+            self.input.display(),
+            self.target.display(),
+            self.generators,
+            self.output.display()
+        )
+    }
+}
@@ -0,0 +1,75 @@
+use std::{fmt, path::PathBuf, process::ExitCode};
+
+use anyhow::Context;
+use homotopy_model::regression::{check_dir, check_file, FileReport};
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::io::print_report;
+
+#[derive(Debug, StructOpt)]
+pub struct Regress {
+    /// The .hom files to check, or directories whose .hom files should all be checked.
+    #[structopt(parse(from_os_str), required = true)]
+    inputs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct RegressReport {
+    ok: bool,
+    files: Vec<FileReport>,
+}
+
+impl Regress {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let mut files = Vec::new();
+        for input in &self.inputs {
+            if input.is_dir() {
+                files.extend(
+                    check_dir(input)
+                        .with_context(|| format!("Could not read {}.", input.display()))?,
+                );
+            } else {
+                files.push(check_file(input));
+            }
+        }
+
+        let report = RegressReport {
+            ok: files.iter().all(FileReport::is_ok),
+            files,
+        };
+        print_report(&report, json)?;
+
+        Ok(if report.ok {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        })
+    }
+}
+
+impl fmt::Display for RegressReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in &self.files {
+            let status = if file.is_ok() { "ok" } else { "FAILED" };
+            let t = &file.timings;
+            writeln!(
+                f,
+                "{}: {status} ({} diagrams{}, {:.0?} total: deserialize {:.0?}, check {:.0?}, typecheck {:.0?}, round trip {:.0?})",
+                file.path.display(),
+                file.diagrams,
+                if file.legacy { ", legacy format" } else { "" },
+                t.total(),
+                t.deserialize,
+                t.check,
+                t.typecheck,
+                t.round_trip,
+            )?;
+            for failure in &file.failures {
+                writeln!(f, "    {failure}")?;
+            }
+        }
+        let failed = self.files.iter().filter(|file| !file.is_ok()).count();
+        writeln!(f, "{} files checked, {failed} failed", self.files.len())
+    }
+}
@@ -0,0 +1,94 @@
+use std::{fmt, path::PathBuf, process::ExitCode};
+
+use anyhow::{anyhow, Context};
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::{
+    io::{export_hom, import_actions, import_hom, print_report},
+    Proof,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Replay {
+    /// The .hom file to start from. Defaults to an empty proof.
+    #[structopt(short, long, parse(from_os_str))]
+    input_hom: Option<PathBuf>,
+
+    /// The exported action log to replay.
+    #[structopt(short = "a", long, parse(from_os_str))]
+    input_actions: PathBuf,
+
+    /// Where to write the resulting .hom file.
+    #[structopt(short, long, parse(from_os_str))]
+    output_hom: Option<PathBuf>,
+
+    /// Do not replay the final action of a log that ended in a crash.
+    #[structopt(short, long)]
+    no_replay_crash: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ReplayReport {
+    actions: usize,
+    replayed_crash: bool,
+    output: Option<PathBuf>,
+}
+
+impl Replay {
+    pub fn run(&self, json: bool) -> anyhow::Result<ExitCode> {
+        let mut proof: Proof = match &self.input_hom {
+            Some(path) => import_hom(path).context("Could not import .hom file.")?,
+            None => Default::default(),
+        };
+
+        let (actions, last_action) =
+            import_actions(&self.input_actions).context("Could not import action file.")?;
+
+        for (i, a) in actions.iter().enumerate() {
+            if !json {
+                println!("Performing action: {a:?}");
+            }
+            proof
+                .update(a)
+                .map_err(|e| anyhow!("Action {i} failed: {a:?}: {e}"))?;
+        }
+
+        let mut replayed_crash = false;
+        if !self.no_replay_crash {
+            if let Some(a) = last_action {
+                if !json {
+                    println!("Performing final action: {a:?}");
+                }
+                // When debugging, set a breakpoint here!
+                proof
+                    .update(&a)
+                    .map_err(|e| anyhow!("Final action failed: {a:?}: {e}"))?;
+                replayed_crash = true;
+            }
+        }
+
+        if let Some(path) = &self.output_hom {
+            export_hom(path, &proof)?;
+        }
+
+        let report = ReplayReport {
+            actions: actions.len() + usize::from(replayed_crash),
+            replayed_crash,
+            output: self.output_hom.clone(),
+        };
+        print_report(&report, json)?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Replayed {} actions", self.actions)?;
+        if let Some(output) = &self.output {
+            writeln!(f, "Wrote {}", output.display())?;
+        }
+        Ok(())
+    }
+}
@@ -193,6 +193,26 @@ impl<T> Tree<T> {
             .map(move |n| (n, &self.nodes[n]))
     }
 
+    /// Maps a function over the data of every node, keeping the node indices of this tree.
+    #[inline]
+    pub fn map_ref<F, U>(&self, mut f: F) -> Tree<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Tree {
+            root: self.root,
+            nodes: self
+                .nodes
+                .values()
+                .map(|nd| NodeData {
+                    data: f(&nd.data),
+                    parent: nd.parent,
+                    children: nd.children.clone(),
+                })
+                .collect(),
+        }
+    }
+
     #[inline]
     pub fn map<F, U>(self, mut f: F) -> Tree<U>
     where
@@ -284,7 +304,9 @@ impl<T> Tree<T> {
         }
     }
 
-    pub fn clean_up(&mut self) {
+    /// Frees the memory associated with all nodes disconnected from the root, renumbering the
+    /// remaining nodes in breadth-first order. Returns the new index of every remaining node.
+    pub fn clean_up(&mut self) -> FastHashMap<Node, Node> {
         // Allocate temporary storage
         let mut nodes = IdxVec::new();
 
@@ -295,6 +317,9 @@ impl<T> Tree<T> {
         // `take` the data out later
         let mut nodes: IdxVec<_, _> = nodes.into_values().map(|n| n.map(Some)).collect();
 
+        // Keep track of where each node ends up in the cleaned tree
+        let mut indices = FastHashMap::default();
+
         // Initialise a queue for a breadth-first walk (starting at the root)
         let mut to_visit = VecDeque::new();
         to_visit.push_back(self.root);
@@ -314,6 +339,7 @@ impl<T> Tree<T> {
                 // valid in the cleaned tree in an earlier iteration)
                 parent: node_data.parent,
             });
+            indices.insert(node, idx);
 
             // If the node we just added has a parent, make sure we add it to that
             // node's list of children.
@@ -332,6 +358,10 @@ impl<T> Tree<T> {
                 nodes[child].parent = Some(idx);
             }
         }
+
+        // The root is always visited first
+        self.root = indices[&self.root];
+        indices
     }
 }
 
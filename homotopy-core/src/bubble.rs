@@ -1,68 +1,128 @@
-use itertools::Itertools;
 use thiserror::Error;
 
-use crate::{rewrite::Cone, Cospan, Diagram, DiagramN, Orientation, Rewrite, Rewrite0, RewriteN};
+use crate::{
+    diagram::AttachmentError, rewrite::Cone, signature::Signature, Boundary, Cospan, Diagram,
+    DiagramN, Orientation, Rewrite, Rewrite0, RewriteN,
+};
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Error)]
+#[derive(Debug, Error)]
 pub enum BubbleError {
-    #[error("Expected an atomic diagram")]
-    NotAtomic,
+    #[error("the diagram cannot be bubbled because not all generators are defined as invertible")]
+    NotInvertible,
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
 }
 
 impl DiagramN {
-    pub fn bubble(&self) -> Result<Self, BubbleError> {
-        use Orientation::Zero;
+    /// The bubble of this diagram, which goes from the identity on its source to the diagram
+    /// followed by its inverse.
+    ///
+    /// The cospans of the diagram are bubbled from the bottom up, each inside the bubbles of the
+    /// cospans below it. Fails unless the generators of the diagram are invertible in `signature`,
+    /// as the bubble would not be well-typed otherwise.
+    pub fn bubble(&self, signature: &impl Signature) -> Result<Self, BubbleError> {
+        if !Diagram::from(self.clone())
+            .invertibility(signature)
+            .is_invertible()
+        {
+            return Err(BubbleError::NotInvertible);
+        }
 
-        // Check that the diagram is atomic.
-        let cospan = self
-            .cospans()
-            .iter()
-            .exactly_one()
-            .cloned()
-            .map_err(|_err| BubbleError::NotAtomic)?;
+        let slices: Vec<_> = self.regular_slices().collect();
+        let bubble = self.cospans().iter().enumerate().try_fold(
+            self.source().identity().identity(),
+            |bubble, (height, cospan)| {
+                let mut embedding = vec![0; self.dimension()];
+                embedding[0] = height;
+                bubble.attach(
+                    &atomic_bubble(&slices[height], cospan.clone()),
+                    Boundary::Target,
+                    &embedding,
+                )
+            },
+        )?;
+        Ok(bubble)
+    }
 
-        let f0 = cospan.forward.orientation_transform(Zero);
-        let b0 = cospan.backward.orientation_transform(Zero);
+    /// Introduces an invertible pair: the result goes from this diagram to the one in which
+    /// `diagram` followed by its inverse is inserted at `embedding`.
+    pub fn bubble_at(
+        &self,
+        diagram: &Self,
+        embedding: &[usize],
+        signature: &impl Signature,
+    ) -> Result<Self, BubbleError> {
+        let bubble = self.clone().identity().attach(
+            &diagram.bubble(signature)?,
+            Boundary::Target,
+            embedding,
+        )?;
+        Ok(bubble)
+    }
 
-        let inverse = cospan.inverse();
+    /// Cancels an invertible pair: the result goes from this diagram to the one in which the
+    /// occurrence of `diagram` followed by its inverse at `embedding` is removed.
+    pub fn cancel_at(
+        &self,
+        diagram: &Self,
+        embedding: &[usize],
+        signature: &impl Signature,
+    ) -> Result<Self, BubbleError> {
+        let cancel = self.clone().identity().attach(
+            &diagram.bubble(signature)?.inverse(),
+            Boundary::Target,
+            embedding,
+        )?;
+        Ok(cancel)
+    }
+}
 
-        let singular0 = self.source().rewrite_forward(&cospan.forward).unwrap();
-        let singular1 = self.source().rewrite_forward(&inverse.backward).unwrap();
+/// The bubble of a single cospan out of the given diagram.
+fn atomic_bubble(source: &Diagram, cospan: Cospan) -> DiagramN {
+    use Orientation::Zero;
 
-        let contract = RewriteN::new(
-            self.dimension(),
-            vec![Cone::new(
-                0,
-                vec![cospan, inverse],
-                Cospan {
-                    forward: f0.clone(),
-                    backward: f0.clone(),
-                },
-                vec![f0.clone(), b0, f0.clone()],
-                vec![singular0.directed_identity(), singular1.directed_identity()],
-            )],
-        );
+    let f0 = cospan.forward.orientation_transform(Zero);
+    let b0 = cospan.backward.orientation_transform(Zero);
 
-        let expand = RewriteN::new(
-            self.dimension(),
-            vec![Cone::new_unit(
-                0,
-                Cospan {
-                    forward: f0.clone(),
-                    backward: f0.clone(),
-                },
-                f0,
-            )],
-        );
+    let inverse = cospan.inverse();
 
-        Ok(Self::new(
-            self.source().identity().into(),
-            vec![Cospan {
-                forward: expand.into(),
-                backward: contract.into(),
-            }],
-        ))
-    }
+    let singular0 = source.clone().rewrite_forward(&cospan.forward).unwrap();
+    let singular1 = source.clone().rewrite_forward(&inverse.backward).unwrap();
+
+    let contract = RewriteN::new(
+        source.dimension() + 1,
+        vec![Cone::new(
+            0,
+            vec![cospan, inverse],
+            Cospan {
+                forward: f0.clone(),
+                backward: f0.clone(),
+            },
+            vec![f0.clone(), b0, f0.clone()],
+            vec![singular0.directed_identity(), singular1.directed_identity()],
+        )],
+    );
+
+    let expand = RewriteN::new(
+        source.dimension() + 1,
+        vec![Cone::new_unit(
+            0,
+            Cospan {
+                forward: f0.clone(),
+                backward: f0.clone(),
+            },
+            f0,
+        )],
+    );
+
+    DiagramN::new(
+        source.clone().identity().into(),
+        vec![Cospan {
+            forward: expand.into(),
+            backward: contract.into(),
+        }],
+    )
 }
 
 impl Diagram {
@@ -15,6 +15,7 @@ use petgraph::{
 
 use crate::{
     common::{Label, LabelIdentifications},
+    parallel::{self, MaybeSend, MaybeSync},
     scaffold::{Scaffold, ScaffoldNode, StableScaffold},
     Diagram, Height, Rewrite0,
 };
@@ -196,9 +197,9 @@ where
 
 impl<V, E, Ix> Collapsible<V, E, Ix> for Scaffold<V, E, Ix>
 where
-    V: Clone + Ord + Cartesian<Height>,
-    E: Clone,
-    Ix: IndexType,
+    V: Clone + Ord + Cartesian<Height> + MaybeSend + MaybeSync,
+    E: Clone + MaybeSync,
+    Ix: IndexType + MaybeSend + MaybeSync,
 {
     fn collapse(&self) -> (StableScaffold<Set<V>, E, Ix>, UnionFind<NodeIndex<Ix>>) {
         let mut stable = StableScaffold::from(self.map(
@@ -224,8 +225,9 @@ pub(crate) fn collapse_stable<V, E, Ix>(
     graph: &mut StableScaffold<V, E, Ix>,
 ) -> UnionFind<NodeIndex<Ix>>
 where
-    V: Cartesian<Height> + AddAssign,
-    Ix: IndexType,
+    V: Cartesian<Height> + AddAssign + MaybeSync,
+    E: MaybeSync,
+    Ix: IndexType + MaybeSend + MaybeSync,
 {
     // invariant: #nodes of graph = #equivalence classes of union_find
     let mut union_find = UnionFind::new(graph.node_count());
@@ -277,7 +279,6 @@ where
         .edge_references()
         .collect();
         seen_edges.extend(edges.iter().map(EdgeRef::id));
-        let mut quotient: Vec<_> = Default::default();
         let label_set = |u: NodeIndex<Ix>, v: NodeIndex<Ix>| -> FastHashSet<Option<&Label>> {
             graph
                 .edges_connecting(u, v)
@@ -289,7 +290,7 @@ where
                 .collect()
         };
         // find collapsible edges wrt nodes
-        for e in edges.into_iter().filter(|e| {
+        let quotient: Vec<_> = parallel::filter(edges, |e| {
             // e is an identity rewrite
             <&Rewrite0>::try_from(&e.weight().rewrite).unwrap().boundaries().map_or(true, |(s, t)| s.generator == t.generator) &&
             // check triangles within nodes which might refute collapsibility of e
@@ -301,10 +302,11 @@ where
                     .neighbors_directed(e.target(), Outgoing)
                     .filter(|n| graph.find_edge(e.source(), *n).is_some())
                     .all(|n| label_set(e.target(), n) == label_set(e.source(), n))
-        }) {
-            // e is collapsible
-            quotient.push((e.source(), e.target()));
-        }
+        })
+        .into_iter()
+        // e is collapsible
+        .map(|e| (e.source(), e.target()))
+        .collect();
 
         for (s, t) in quotient {
             unify(
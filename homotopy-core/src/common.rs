@@ -1,21 +1,22 @@
 use std::{
-    cell::RefCell,
     cmp::Ordering,
     fmt,
     iter::Product,
     ops::{Index, IndexMut, Mul},
+    str::FromStr,
 };
 
-use hashconsing::{HConsed, HConsign, HashConsign};
+use hashconsing::{HConsed, HashConsign};
 use homotopy_common::{hash::FastHashMap, idx::Idx};
 use im::OrdSet;
 use itertools::Either;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-thread_local! {
-    static LABEL_FACTORY: RefCell<HConsign<LabelInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
+use crate::parallel::factories;
+
+factories! {
+    static LABEL_FACTORY: LabelInternal;
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -285,6 +286,42 @@ impl fmt::Display for SliceIndex {
     }
 }
 
+#[derive(Debug, Error)]
+#[error("invalid slice index: {0}")]
+pub struct ParseSliceIndexError(String);
+
+/// Parses a slice index in the format produced by its [`Display`](fmt::Display) implementation.
+///
+/// ```
+/// # use homotopy_core::common::Boundary::*;
+/// # use homotopy_core::common::Height::*;
+/// # use homotopy_core::common::SliceIndex::*;
+/// assert_eq!("S".parse().ok(), Some(Boundary(Source)));
+/// assert_eq!("T".parse().ok(), Some(Boundary(Target)));
+/// assert_eq!("R2".parse().ok(), Some(Interior(Regular(2))));
+/// assert_eq!("S0".parse().ok(), Some(Interior(Singular(0))));
+/// assert!("X1".parse::<homotopy_core::SliceIndex>().is_err());
+/// ```
+impl FromStr for SliceIndex {
+    type Err = ParseSliceIndexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseSliceIndexError(s.to_owned());
+        match s {
+            "S" => return Ok(Boundary::Source.into()),
+            "T" => return Ok(Boundary::Target.into()),
+            _ => {}
+        }
+        let (kind, height) = s.split_at(s.find(|c: char| c.is_ascii_digit()).ok_or_else(err)?);
+        let height = height.parse().map_err(|_| err())?;
+        match kind {
+            "R" => Ok(Height::Regular(height).into()),
+            "S" => Ok(Height::Singular(height).into()),
+            _ => Err(err()),
+        }
+    }
+}
+
 impl From<Height> for SliceIndex {
     fn from(height: Height) -> Self {
         Self::Interior(height)
@@ -26,6 +26,7 @@ use crate::{
     common::{Boundary, BoundaryPath, DimensionError, Height, Orientation, SingularHeight},
     diagram::{Diagram, Diagram0, DiagramN},
     expansion::expand_propagate,
+    parallel::{MaybeSend, MaybeSync},
     rewrite::{Cone, Cospan, Rewrite, Rewrite0, RewriteN},
     scaffold::{
         Explodable, ExplosionOutput, ExternalRewrite, InternalRewrite, Scaffold, ScaffoldEdge,
@@ -121,6 +122,74 @@ impl DiagramN {
             Ok(vec![cospan])
         })
     }
+
+    /// The distinct outcomes of [`DiagramN::contract`] under every choice of bias, starting with
+    /// `bias`, each together with the bias which produces it and its updated interior path.
+    ///
+    /// Multicontractions can not be biased, so they have at most one outcome. At most `bound`
+    /// outcomes are returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contractions(
+        &self,
+        boundary_path: BoundaryPath,
+        interior_path: &[Height],
+        height: SingularHeight,
+        direction: Direction,
+        step: usize,
+        bias: Option<Bias>,
+        signature: &impl Signature,
+        bound: usize,
+    ) -> Vec<(Option<Bias>, Self, Vec<Height>)> {
+        let mut biases = vec![bias];
+        if step == 1 {
+            biases.extend(
+                [
+                    None,
+                    Some(Bias::Lower),
+                    Some(Bias::Same),
+                    Some(Bias::Higher),
+                ]
+                .into_iter()
+                .filter(|other| *other != bias),
+            );
+        }
+
+        let mut outcomes: Vec<(Option<Bias>, Self, Vec<Height>)> = vec![];
+        for bias in biases {
+            if outcomes.len() == bound {
+                break;
+            }
+
+            let mut path = interior_path.to_vec();
+            let Ok(contracted) = self.contract(
+                boundary_path,
+                &mut path,
+                height,
+                direction,
+                step,
+                bias,
+                signature,
+            ) else {
+                continue;
+            };
+            if outcomes
+                .iter()
+                .all(|(_, outcome, _)| *outcome != contracted)
+            {
+                outcomes.push((bias, contracted, path));
+            }
+        }
+        outcomes
+    }
+}
+
+/// The rewrite which contracts a singular height of a diagram with the one above it.
+pub(crate) fn contraction(
+    diagram: &DiagramN,
+    height: SingularHeight,
+) -> Result<RewriteN, ContractionError> {
+    contract_base(diagram, height, Direction::Forward, 1, None, false)
+        .map(|ContractExpand { contract, .. }| contract)
 }
 
 fn contract_base(
@@ -424,7 +493,9 @@ impl Cartesian<Height> for ContractNode {
 
 type ContractGraph<Ix> = Scaffold<ContractNode, (), Ix>;
 
-fn colimit<Ix: IndexType>(graph: &ContractGraph<Ix>) -> Result<Cocone<Ix>, ContractionError> {
+fn colimit<Ix: IndexType + MaybeSend + MaybeSync>(
+    graph: &ContractGraph<Ix>,
+) -> Result<Cocone<Ix>, ContractionError> {
     let dimension = graph
         .node_weights()
         .next()
@@ -447,7 +518,9 @@ fn colimit<Ix: IndexType>(graph: &ContractGraph<Ix>) -> Result<Cocone<Ix>, Contr
     }
 }
 
-fn colimit_base<Ix: IndexType>(graph: &ContractGraph<Ix>) -> Result<Cocone<Ix>, ContractionError> {
+fn colimit_base<Ix: IndexType + MaybeSend + MaybeSync>(
+    graph: &ContractGraph<Ix>,
+) -> Result<Cocone<Ix>, ContractionError> {
     let (mut stable, mut union_find) = graph.collapse();
 
     // unify all nodes of maximal dimension
@@ -579,7 +652,7 @@ fn colimit_base<Ix: IndexType>(graph: &ContractGraph<Ix>) -> Result<Cocone<Ix>,
     Ok(cocone)
 }
 
-fn colimit_recursive<Ix: IndexType>(
+fn colimit_recursive<Ix: IndexType + MaybeSend + MaybeSync>(
     graph: &ContractGraph<Ix>,
 ) -> Result<Cocone<Ix>, ContractionError> {
     // Input: graph of n-diagrams and n-rewrites
@@ -1,13 +1,11 @@
 use std::{
-    cell::RefCell,
     convert::{From, Into, TryFrom},
     fmt,
     hash::Hash,
 };
 
-use hashconsing::{HConsed, HConsign, HashConsign};
+use hashconsing::{HConsed, HashConsign};
 use homotopy_common::hash::{FastHashMap, FastHashSet};
-use once_cell::unsync::OnceCell;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -17,14 +15,14 @@ use crate::{
         Boundary, BoundaryPath, DimensionError, Direction, Generator, Height, Label, RegularHeight,
         SliceIndex, WithDirection,
     },
+    parallel::{factories, OnceCell},
     rewrite::{Cospan, Rewrite, Rewrite0, RewriteN},
     signature::{GeneratorInfo, Invertibility, Signature},
     Orientation,
 };
 
-thread_local! {
-    static DIAGRAM_FACTORY: RefCell<HConsign<DiagramInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
+factories! {
+    static DIAGRAM_FACTORY: DiagramInternal;
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -130,6 +128,62 @@ impl Diagram {
         }
     }
 
+    /// Rewrites every occurrence of the source of a [rule] in this diagram by the target of the
+    /// rule, returning a diagram whose source is this diagram and whose target is the result of
+    /// the rewrite.
+    ///
+    /// Occurrences are chosen greedily from the bottom up, skipping those which overlap an
+    /// occurrence that was already chosen.
+    pub fn rewrite_all(&self, rule: &DiagramN) -> Result<DiagramN, AttachmentError> {
+        if rule.dimension() != self.dimension() + 1 {
+            return Err(DimensionError.into());
+        }
+
+        let source = rule.source();
+        let extents = source.extents();
+
+        let mut occurrences: Vec<Vec<usize>> = vec![];
+        for embedding in self.embeddings(&source) {
+            let overlaps = occurrences
+                .iter()
+                .any(|occurrence| overlaps(&extents, occurrence, &embedding));
+            if !overlaps {
+                occurrences.push(embedding);
+            }
+        }
+
+        if occurrences.is_empty() {
+            return Err(AttachmentError::NoOccurrences);
+        }
+
+        // Rewriting from the top down leaves the occurrences further down in place.
+        occurrences
+            .iter()
+            .rev()
+            .try_fold(self.clone().identity(), |diagram, embedding| {
+                diagram.attach(rule, Boundary::Target, embedding)
+            })
+    }
+
+    /// The number of heights occupied by the diagram in every direction, taking the largest
+    /// slice in each direction. Slices of size zero still occupy a height.
+    fn extents(&self) -> Vec<usize> {
+        match self {
+            Self::Diagram0(_) => vec![],
+            Self::DiagramN(d) => {
+                let mut extents = vec![d.size().max(1)];
+                for slice in d.slices() {
+                    let slice = slice.extents();
+                    extents.resize(slice.len() + 1, 1);
+                    for (extent, slice) in extents[1..].iter_mut().zip(slice) {
+                        *extent = (*extent).max(slice);
+                    }
+                }
+                extents
+            }
+        }
+    }
+
     pub(crate) fn rewrite_forward(self, rewrite: &Rewrite) -> Result<Self, RewritingError> {
         match (self, rewrite) {
             (Self::Diagram0(d), Rewrite::Rewrite0(r)) => d.rewrite_forward(r).map(Into::into),
@@ -225,6 +279,22 @@ impl Diagram {
     }
 }
 
+/// Whether two occurrences of a diagram with the given extents overlap.
+///
+/// Occurrences which start at different heights in a direction are only compared in that
+/// direction, since their later coordinates are positions in different slices.
+fn overlaps(extents: &[usize], a: &[usize], b: &[usize]) -> bool {
+    for ((extent, a), b) in extents.iter().zip(a).zip(b) {
+        if a + extent <= *b || b + extent <= *a {
+            return false;
+        }
+        if a != b {
+            return true;
+        }
+    }
+    true
+}
+
 pub(crate) fn globularity(s: &Diagram, t: &Diagram) -> bool {
     use Diagram::{Diagram0, DiagramN};
     match (s, t) {
@@ -277,7 +347,7 @@ impl Diagram0 {
                 self.suspended(),
                 Some(Label::new(
                     BoundaryPath(Boundary::Source, self.generator.dimension),
-                    Default::default(),
+                    std::iter::once(vec![]).collect(),
                 )),
             )
             .into();
@@ -286,7 +356,7 @@ impl Diagram0 {
                 self.suspended(),
                 Some(Label::new(
                     BoundaryPath(Boundary::Target, self.generator.dimension),
-                    Default::default(),
+                    std::iter::once(vec![]).collect(),
                 )),
             )
             .into();
@@ -979,6 +1049,9 @@ pub enum AttachmentError {
 
     #[error("failed to attach incompatible diagrams")]
     IncompatibleAttachment,
+
+    #[error("no occurrences of the source of the rule")]
+    NoOccurrences,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -386,6 +386,68 @@ pub fn bead_series(n: usize) -> (SignatureBuilder, DiagramN) {
     (sig, res)
 }
 
+// A wire f with three beads a, b and c on it, to be stacked with `beads`.
+#[must_use]
+pub fn bead_generators() -> (SignatureBuilder, DiagramN, [DiagramN; 3]) {
+    let mut sig = SignatureBuilder::default();
+
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let a = sig.add(f.clone(), f.clone()).unwrap();
+    let b = sig.add(f.clone(), f.clone()).unwrap();
+    let c = sig.add(f.clone(), f.clone()).unwrap();
+    (sig, f, [a, b, c])
+}
+
+// Beads stacked from bottom to top on parallel wires, each attached to the wire with the given
+// index. For example `beads(&ff, &[(&a, 0), (&b, 1)])` is
+//
+// | b
+// a |
+// | |
+#[must_use]
+pub fn beads(wires: &DiagramN, beads: &[(&DiagramN, usize)]) -> DiagramN {
+    beads
+        .iter()
+        .fold(wires.clone().identity(), |diagram, &(bead, wire)| {
+            diagram.attach(bead, Target, &[wire]).unwrap()
+        })
+}
+
+// Two applications of a binary operation to three wires.
+//
+//    |
+//    m
+//   / \
+//  m   |
+// / \  |
+#[must_use]
+pub fn multiply_thrice(wire: &DiagramN, multiplication: &DiagramN) -> DiagramN {
+    wire.attach(wire, Target, &[])
+        .unwrap()
+        .attach(wire, Target, &[])
+        .unwrap()
+        .identity()
+        .attach(multiplication, Target, &[0])
+        .unwrap()
+        .attach(multiplication, Target, &[0])
+        .unwrap()
+}
+
+// Scalars stacked from bottom to top. For example `stack_scalars(&[&a, &b])` is
+//
+//  b
+//  a
+#[must_use]
+pub fn stack_scalars(scalars: &[&DiagramN]) -> DiagramN {
+    scalars
+        .iter()
+        .skip(1)
+        .fold(scalars[0].clone(), |diagram, scalar| {
+            diagram.attach(scalar, Target, &[0]).unwrap()
+        })
+}
+
 // take an endomorphism on a zero cell `n` times
 #[must_use]
 pub fn iterated_endomorphism(n: usize) -> (SignatureBuilder, DiagramN) {
@@ -76,6 +76,108 @@ impl DiagramN {
             Ok(vec![cospan])
         })
     }
+
+    /// Expands at a point in an n-dimensional projection of the slice at the given path, moving
+    /// along one of its axes.
+    ///
+    /// The coordinates of the point before the axis descend further into the slice, and the
+    /// coordinates at the axis and the one after it form the point of the 2-dimensional
+    /// expansion. Any remaining coordinates are not needed to locate the expansion.
+    pub fn expand_along(
+        &self,
+        boundary_path: BoundaryPath,
+        interior_path: &mut [Height],
+        point: &[Height],
+        axis: usize,
+        direction: Direction,
+        signature: &impl Signature,
+    ) -> Result<Self, ExpansionError> {
+        if axis + 2 > point.len() {
+            return Err(ExpansionError::WrongLocation);
+        }
+
+        let mut path = interior_path.to_vec();
+        path.extend_from_slice(&point[..axis]);
+        let expanded = self.expand(
+            boundary_path,
+            &mut path,
+            [point[axis], point[axis + 1]],
+            direction,
+            signature,
+        )?;
+        interior_path.copy_from_slice(&path[..interior_path.len()]);
+        Ok(expanded)
+    }
+
+    /// The distinct outcomes of [`DiagramN::expand_along`] for every way of propagating the
+    /// expansion through the singular heights on the path to the point.
+    ///
+    /// At most `bound` factorizations are tried on either side of each of these heights and at
+    /// most `bound` outcomes are returned, each together with its updated interior path. The
+    /// outcome of [`DiagramN::expand_along`] comes first whenever it succeeds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn expansions(
+        &self,
+        boundary_path: BoundaryPath,
+        interior_path: &[Height],
+        point: &[Height],
+        axis: usize,
+        direction: Direction,
+        signature: &impl Signature,
+        bound: usize,
+    ) -> Result<Vec<(Self, Vec<Height>)>, ExpansionError> {
+        if axis + 2 > point.len() {
+            return Err(ExpansionError::WrongLocation);
+        }
+
+        let slice: Self = self
+            .boundary(boundary_path)
+            .ok_or(ExpansionError::OutOfBounds)?
+            .try_into()?;
+        let mut path = interior_path.to_vec();
+        path.extend_from_slice(&point[..axis]);
+        let candidates = expansions_in_path(
+            &slice,
+            &path,
+            [point[axis], point[axis + 1]],
+            direction,
+            bound,
+        )?;
+
+        let mut error = None;
+        let mut outcomes: Vec<(Self, Vec<Height>)> = vec![];
+        for (expand, mut path) in candidates {
+            if outcomes.len() == bound {
+                break;
+            }
+
+            let cospan = Cospan {
+                forward: Rewrite::identity(slice.dimension()),
+                backward: expand.into(),
+            };
+            if let Err(e) = typecheck_cospan(slice.clone().into(), cospan.clone(), signature) {
+                error.get_or_insert(e.into());
+                continue;
+            }
+            let cospan = match boundary_path.boundary() {
+                Boundary::Source => cospan.flip(),
+                Boundary::Target => cospan,
+            };
+
+            let expanded = attach(self, boundary_path, |_| {
+                Ok::<_, ExpansionError>(vec![cospan])
+            })?;
+            if outcomes.iter().all(|(outcome, _)| *outcome != expanded) {
+                path.truncate(interior_path.len());
+                outcomes.push((expanded, path));
+            }
+        }
+
+        match error {
+            Some(error) if outcomes.is_empty() => Err(error),
+            _ => Ok(outcomes),
+        }
+    }
 }
 
 pub fn expand_in_path(
@@ -103,6 +205,51 @@ pub fn expand_in_path(
     }
 }
 
+/// The expansions of [`expand_in_path`] for every way of propagating the expansion through the
+/// heights of the path, trying at most `bound` factorizations on either side of each of them.
+fn expansions_in_path(
+    diagram: &DiagramN,
+    path: &[Height],
+    point: [Height; 2],
+    direction: Direction,
+    bound: usize,
+) -> Result<Vec<(RewriteN, Vec<Height>)>, ExpansionError> {
+    let Some((step, rest)) = path.split_first() else {
+        return Ok(vec![(
+            expand_in_path(diagram, &mut [], point, direction)?,
+            vec![],
+        )]);
+    };
+
+    let slice: DiagramN = diagram
+        .slice(*step)
+        .ok_or(ExpansionError::OutOfBounds)?
+        .try_into()?;
+
+    let mut error = None;
+    let mut expansions = vec![];
+    for (recursive, rest) in expansions_in_path(&slice, rest, point, direction, bound)? {
+        for result in expand_propagate_all(diagram, *step, &recursive.into(), true, true, bound) {
+            match result {
+                Ok((expansion, step)) if expansions.len() < bound => {
+                    let mut path = vec![step];
+                    path.extend_from_slice(&rest);
+                    expansions.push((expansion, path));
+                }
+                Ok(_) => return Ok(expansions),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+    }
+
+    match error {
+        Some(error) if expansions.is_empty() => Err(error),
+        _ => Ok(expansions),
+    }
+}
+
 fn expand_base_regular(
     diagram: &DiagramN,
     h0: RegularHeight,
@@ -442,6 +589,87 @@ pub(crate) fn expand_propagate(
 
     let forward = factorize(&target_cospan.forward, &expansion).next();
     let backward = factorize(&target_cospan.backward, &expansion).next();
+    propagate(
+        diagram,
+        height,
+        expansion,
+        forward,
+        backward,
+        normalize,
+        anticontraction,
+    )
+}
+
+/// Propagate an expansion on a slice to the whole diagram in every way that factorizes it
+/// through the singular height, trying at most `bound` factorizations on either side.
+fn expand_propagate_all(
+    diagram: &DiagramN,
+    height: Height,
+    expansion: &Rewrite,
+    normalize: bool,
+    anticontraction: bool,
+    bound: usize,
+) -> Vec<Result<(RewriteN, Height), ExpansionError>> {
+    let target_cospan = match height {
+        Height::Regular(_) => return vec![Err(ExpansionError::RegularSlice)],
+        Height::Singular(i) => match diagram.cospans().get(i) {
+            Some(cospan) => cospan,
+            None => return vec![Err(ExpansionError::OutOfBounds)],
+        },
+    };
+
+    let factorizations = |rewrite: &Rewrite| -> Vec<Option<Rewrite>> {
+        let mut factorizations: Vec<_> = factorize(rewrite, expansion)
+            .take(bound)
+            .map(Some)
+            .collect();
+        if factorizations.is_empty() {
+            factorizations.push(None);
+        }
+        factorizations
+    };
+    let backwards = factorizations(&target_cospan.backward);
+
+    factorizations(&target_cospan.forward)
+        .into_iter()
+        .flat_map(|forward| {
+            backwards.iter().map(move |backward| {
+                let mut height = height;
+                propagate(
+                    diagram,
+                    &mut height,
+                    expansion.clone(),
+                    forward.clone(),
+                    backward.clone(),
+                    normalize,
+                    anticontraction,
+                )
+                .map(|expansion| (expansion, height))
+            })
+        })
+        .collect()
+}
+
+/// Propagate an expansion on a slice to the whole diagram, given its factorizations through the
+/// forward and backward rewrites of the singular height.
+fn propagate(
+    diagram: &DiagramN,
+    height: &mut Height,
+    expansion: Rewrite,
+    forward: Option<Rewrite>,
+    backward: Option<Rewrite>,
+    normalize: bool,
+    anticontraction: bool,
+) -> Result<RewriteN, ExpansionError> {
+    let i = match *height {
+        Height::Regular(_) => return Err(ExpansionError::RegularSlice),
+        Height::Singular(i) => i,
+    };
+
+    let target_cospan = diagram
+        .cospans()
+        .get(i)
+        .ok_or(ExpansionError::OutOfBounds)?;
 
     #[allow(clippy::single_match_else)]
     let cone = || {
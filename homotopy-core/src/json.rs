@@ -0,0 +1,418 @@
+//! A JSON representation of diagrams, rewrites and cones for exchange with other tools.
+//!
+//! Diagrams share most of their structure, so rather than nesting, every diagram, rewrite and
+//! cone is stored once in a table and referred to by its index in that table:
+//!
+//! ```json
+//! {
+//!   "diagrams": [
+//!     { "type": "Diagram0", "generator": { "id": 0, "dimension": 0 }, "orientation": "Positive" },
+//!     { "type": "DiagramN", "dimension": 1, "source": 0, "cospans": [{ "forward": 0, "backward": 1 }] }
+//!   ],
+//!   "rewrites": [
+//!     {
+//!       "type": "Rewrite0",
+//!       "source": { "generator": { "id": 0, "dimension": 0 }, "orientation": "Positive" },
+//!       "target": { "generator": { "id": 1, "dimension": 1 }, "orientation": "Positive" },
+//!       "label": { "boundary": ["Source", 0], "coordinates": [[]] }
+//!     },
+//!     { "type": "RewriteN", "dimension": 1, "cones": [0] }
+//!   ],
+//!   "cones": [
+//!     {
+//!       "index": 0,
+//!       "source": [],
+//!       "target": { "forward": 0, "backward": 1 },
+//!       "regular_slices": [0],
+//!       "singular_slices": []
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! - A `Diagram0` is a generator with an orientation, which is one of `"Positive"`, `"Zero"` and
+//!   `"Negative"`.
+//! - A `DiagramN` of dimension `n` has a source diagram of dimension `n - 1`, and a list of
+//!   cospans whose rewrites have dimension `n - 1`.
+//! - A `Rewrite0` either has both a source and a target, or neither if it is an identity. The
+//!   target generator must have at least the dimension of the source generator. Its
+//!   optional label consists of a boundary path and a set of coordinates, which are lists of
+//!   heights such as `{ "Regular": 0 }` or `{ "Singular": 1 }`.
+//! - A `RewriteN` of dimension `n` is a list of cones, sorted by `index`. The cospans and slices of
+//!   its cones all have dimension `n - 1`.
+//!
+//! Importing checks that all indices and dimensions are consistent, but not that the diagrams are
+//! well-formed; use [`Diagram::check`] for that.
+
+use std::num::NonZeroUsize;
+
+use homotopy_common::hash::FastHashMap;
+use im::OrdSet;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    common::{BoundaryPath, Label},
+    rewrite::Cone,
+    Cospan, Diagram, Diagram0, DiagramN, Generator, Height, Orientation, Rewrite, Rewrite0,
+    RewriteN,
+};
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("there is no {0} with index {1}")]
+    Index(&'static str, usize),
+
+    #[error("{0} {1} does not have dimension {2}")]
+    Dimension(&'static str, usize, usize),
+
+    #[error("rewrite {0} is not a valid 0-rewrite")]
+    Rewrite0(usize),
+
+    #[error("cone {0} must have one more regular slice than it has singular slices and sources")]
+    Cone(usize),
+}
+
+/// Tables of diagrams, rewrites and cones, as described in the [module documentation](self).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Tables {
+    diagrams: Vec<DiagramJson>,
+    rewrites: Vec<RewriteJson>,
+    cones: Vec<ConeJson>,
+
+    #[serde(skip)]
+    diagram_indices: FastHashMap<Diagram, usize>,
+    #[serde(skip)]
+    rewrite_indices: FastHashMap<Rewrite, usize>,
+    #[serde(skip)]
+    cone_indices: FastHashMap<Cone, usize>,
+
+    #[serde(skip)]
+    unpacked_diagrams: FastHashMap<usize, Diagram>,
+    #[serde(skip)]
+    unpacked_rewrites: FastHashMap<usize, Rewrite>,
+    #[serde(skip)]
+    unpacked_cones: FastHashMap<usize, Cone>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum DiagramJson {
+    Diagram0 {
+        generator: Generator,
+        orientation: Orientation,
+    },
+    DiagramN {
+        dimension: NonZeroUsize,
+        source: usize,
+        cospans: Vec<CospanJson>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum RewriteJson {
+    Rewrite0 {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<Diagram0Json>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target: Option<Diagram0Json>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<LabelJson>,
+    },
+    RewriteN {
+        dimension: NonZeroUsize,
+        cones: Vec<usize>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Diagram0Json {
+    generator: Generator,
+    orientation: Orientation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LabelJson {
+    boundary: BoundaryPath,
+    coordinates: Vec<Vec<Height>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct CospanJson {
+    forward: usize,
+    backward: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConeJson {
+    index: usize,
+    source: Vec<CospanJson>,
+    target: CospanJson,
+    regular_slices: Vec<usize>,
+    singular_slices: Vec<usize>,
+}
+
+impl From<Diagram0> for Diagram0Json {
+    fn from(d: Diagram0) -> Self {
+        Self {
+            generator: d.generator,
+            orientation: d.orientation,
+        }
+    }
+}
+
+impl From<Diagram0Json> for Diagram0 {
+    fn from(d: Diagram0Json) -> Self {
+        Self::new(d.generator, d.orientation)
+    }
+}
+
+impl Tables {
+    /// Adds a diagram to the tables, returning its index.
+    pub fn pack_diagram(&mut self, diagram: &Diagram) -> usize {
+        if let Some(index) = self.diagram_indices.get(diagram) {
+            return *index;
+        }
+
+        let json = match diagram {
+            Diagram::Diagram0(d) => DiagramJson::Diagram0 {
+                generator: d.generator,
+                orientation: d.orientation,
+            },
+            Diagram::DiagramN(d) => DiagramJson::DiagramN {
+                dimension: NonZeroUsize::new(d.dimension()).unwrap(),
+                source: self.pack_diagram(&d.source()),
+                cospans: d
+                    .cospans()
+                    .iter()
+                    .map(|cospan| self.pack_cospan(cospan))
+                    .collect(),
+            },
+        };
+
+        let index = self.diagrams.len();
+        self.diagrams.push(json);
+        self.diagram_indices.insert(diagram.clone(), index);
+        index
+    }
+
+    fn pack_cospan(&mut self, cospan: &Cospan) -> CospanJson {
+        CospanJson {
+            forward: self.pack_rewrite(&cospan.forward),
+            backward: self.pack_rewrite(&cospan.backward),
+        }
+    }
+
+    /// Adds a rewrite to the tables, returning its index.
+    pub fn pack_rewrite(&mut self, rewrite: &Rewrite) -> usize {
+        if let Some(index) = self.rewrite_indices.get(rewrite) {
+            return *index;
+        }
+
+        let json = match rewrite {
+            Rewrite::Rewrite0(r) => RewriteJson::Rewrite0 {
+                source: r.source().map(Into::into),
+                target: r.target().map(Into::into),
+                label: r.label().map(|label| LabelJson {
+                    boundary: label.boundary_path(),
+                    coordinates: label.coords().into_iter().collect(),
+                }),
+            },
+            Rewrite::RewriteN(r) => RewriteJson::RewriteN {
+                dimension: NonZeroUsize::new(r.dimension()).unwrap(),
+                cones: r.cones().iter().map(|cone| self.pack_cone(cone)).collect(),
+            },
+        };
+
+        let index = self.rewrites.len();
+        self.rewrites.push(json);
+        self.rewrite_indices.insert(rewrite.clone(), index);
+        index
+    }
+
+    fn pack_cone(&mut self, cone: &Cone) -> usize {
+        if let Some(index) = self.cone_indices.get(cone) {
+            return *index;
+        }
+
+        let json = ConeJson {
+            index: cone.index,
+            source: cone
+                .source()
+                .iter()
+                .map(|cospan| self.pack_cospan(cospan))
+                .collect(),
+            target: self.pack_cospan(cone.target()),
+            regular_slices: cone
+                .regular_slices()
+                .iter()
+                .map(|slice| self.pack_rewrite(slice))
+                .collect(),
+            singular_slices: cone
+                .singular_slices()
+                .iter()
+                .map(|slice| self.pack_rewrite(slice))
+                .collect(),
+        };
+
+        let index = self.cones.len();
+        self.cones.push(json);
+        self.cone_indices.insert(cone.clone(), index);
+        index
+    }
+
+    fn diagram_dimension(&self, index: usize) -> Result<usize, ImportError> {
+        match self.diagrams.get(index) {
+            None => Err(ImportError::Index("diagram", index)),
+            Some(DiagramJson::Diagram0 { .. }) => Ok(0),
+            Some(DiagramJson::DiagramN { dimension, .. }) => Ok(dimension.get()),
+        }
+    }
+
+    fn rewrite_dimension(&self, index: usize) -> Result<usize, ImportError> {
+        match self.rewrites.get(index) {
+            None => Err(ImportError::Index("rewrite", index)),
+            Some(RewriteJson::Rewrite0 { .. }) => Ok(0),
+            Some(RewriteJson::RewriteN { dimension, .. }) => Ok(dimension.get()),
+        }
+    }
+
+    /// Reconstructs the diagram with the given index.
+    pub fn unpack_diagram(&mut self, index: usize) -> Result<Diagram, ImportError> {
+        if let Some(diagram) = self.unpacked_diagrams.get(&index) {
+            return Ok(diagram.clone());
+        }
+
+        let diagram: Diagram = match self.diagrams.get(index).cloned() {
+            None => return Err(ImportError::Index("diagram", index)),
+            Some(DiagramJson::Diagram0 {
+                generator,
+                orientation,
+            }) => Diagram0::new(generator, orientation).into(),
+            Some(DiagramJson::DiagramN {
+                dimension,
+                source,
+                cospans,
+            }) => {
+                let dimension = dimension.get() - 1;
+                if self.diagram_dimension(source)? != dimension {
+                    return Err(ImportError::Dimension("diagram", source, dimension));
+                }
+                let source = self.unpack_diagram(source)?;
+                let cospans = cospans
+                    .into_iter()
+                    .map(|cospan| self.unpack_cospan(cospan, dimension))
+                    .collect::<Result<_, _>>()?;
+                DiagramN::new_unsafe(source, cospans).into()
+            }
+        };
+
+        self.unpacked_diagrams.insert(index, diagram.clone());
+        Ok(diagram)
+    }
+
+    fn unpack_cospan(
+        &mut self,
+        cospan: CospanJson,
+        dimension: usize,
+    ) -> Result<Cospan, ImportError> {
+        Ok(Cospan {
+            forward: self.unpack_rewrite_with_dimension(cospan.forward, dimension)?,
+            backward: self.unpack_rewrite_with_dimension(cospan.backward, dimension)?,
+        })
+    }
+
+    fn unpack_rewrite_with_dimension(
+        &mut self,
+        index: usize,
+        dimension: usize,
+    ) -> Result<Rewrite, ImportError> {
+        if self.rewrite_dimension(index)? != dimension {
+            return Err(ImportError::Dimension("rewrite", index, dimension));
+        }
+        self.unpack_rewrite(index)
+    }
+
+    /// Reconstructs the rewrite with the given index.
+    pub fn unpack_rewrite(&mut self, index: usize) -> Result<Rewrite, ImportError> {
+        if let Some(rewrite) = self.unpacked_rewrites.get(&index) {
+            return Ok(rewrite.clone());
+        }
+
+        let rewrite: Rewrite = match self.rewrites.get(index).cloned() {
+            None => return Err(ImportError::Index("rewrite", index)),
+            Some(RewriteJson::Rewrite0 {
+                source,
+                target,
+                label,
+            }) => match (source, target) {
+                (None, None) => Rewrite0::identity().into(),
+                (Some(source), Some(target))
+                    if source.generator.dimension <= target.generator.dimension =>
+                {
+                    let label = label.map(|label| {
+                        Label::new(
+                            label.boundary,
+                            label.coordinates.into_iter().collect::<OrdSet<_>>(),
+                        )
+                    });
+                    Rewrite0::new(Diagram0::from(source), Diagram0::from(target), label).into()
+                }
+                _ => return Err(ImportError::Rewrite0(index)),
+            },
+            Some(RewriteJson::RewriteN { dimension, cones }) => {
+                let cones = cones
+                    .into_iter()
+                    .map(|cone| self.unpack_cone(cone, dimension.get() - 1))
+                    .collect::<Result<_, _>>()?;
+                RewriteN::new_unsafe(dimension.get(), cones).into()
+            }
+        };
+
+        self.unpacked_rewrites.insert(index, rewrite.clone());
+        Ok(rewrite)
+    }
+
+    fn unpack_cone(&mut self, index: usize, dimension: usize) -> Result<Cone, ImportError> {
+        let json = self
+            .cones
+            .get(index)
+            .ok_or(ImportError::Index("cone", index))?;
+        if self.rewrite_dimension(json.target.forward)? != dimension {
+            return Err(ImportError::Dimension("cone", index, dimension));
+        }
+
+        if let Some(cone) = self.unpacked_cones.get(&index) {
+            return Ok(cone.clone());
+        }
+
+        let json = self.cones[index].clone();
+        if json.regular_slices.len() != json.singular_slices.len() + 1
+            || json.source.len() != json.singular_slices.len()
+        {
+            return Err(ImportError::Cone(index));
+        }
+
+        let source = json
+            .source
+            .into_iter()
+            .map(|cospan| self.unpack_cospan(cospan, dimension))
+            .collect::<Result<_, _>>()?;
+        let target = self.unpack_cospan(json.target, dimension)?;
+        let regular_slices = json
+            .regular_slices
+            .into_iter()
+            .map(|slice| self.unpack_rewrite_with_dimension(slice, dimension))
+            .collect::<Result<_, _>>()?;
+        let singular_slices = json
+            .singular_slices
+            .into_iter()
+            .map(|slice| self.unpack_rewrite_with_dimension(slice, dimension))
+            .collect::<Result<_, _>>()?;
+        let cone = Cone::new(json.index, source, target, regular_slices, singular_slices);
+
+        self.unpacked_cones.insert(index, cone.clone());
+        Ok(cone)
+    }
+}
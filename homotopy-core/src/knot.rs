@@ -0,0 +1,558 @@
+//! Classical invariants of links presented by 3-dimensional diagrams.
+//!
+//! The strands of a link are the generators of dimension two, and its crossings are the braidings
+//! between them. In the 2-dimensional projection of the diagram, a crossing is a point at which
+//! two strands meet at different depths, and the strand in front, as recorded by [`Depths`],
+//! passes over the other one.
+//!
+//! Strands are oriented upwards where their generator appears with positive orientation and
+//! downwards where it appears with negative orientation, which is consistent across the cups and
+//! caps between a generator and its inverse.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    ops::{Add, Mul},
+};
+
+use homotopy_common::{hash::FastHashMap, idx::IdxVec};
+use itertools::Itertools;
+use petgraph::{graph::NodeIndex, unionfind::UnionFind, visit::EdgeRef};
+use thiserror::Error;
+
+use crate::{
+    common::{DimensionError, SingularHeight},
+    projection::Depths,
+    scaffold::{Explodable, Scaffold, ScaffoldNode},
+    Boundary, Diagram, DiagramN, Direction, Orientation, Rewrite, SliceIndex,
+};
+
+type Coordinate = [SliceIndex; 2];
+
+/// The most crossings of a link whose Kauffman bracket is computed, as the state sum takes time
+/// exponential in the number of crossings.
+pub const MAX_CROSSINGS: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum KnotError {
+    #[error("links are presented by diagrams of dimension 3, not {0}")]
+    Dimension(usize),
+    #[error("diagram does not contain any strands")]
+    Empty,
+    #[error("strands reach the boundary of the diagram")]
+    Open,
+    #[error("strands do not cross transversally at {}", .0.iter().join(" "))]
+    NonGeneric(Coordinate),
+    #[error("strands crossing at {} are not oriented", .0.iter().join(" "))]
+    Unoriented(Coordinate),
+    #[error("link has {0} crossings, more than the {} supported", MAX_CROSSINGS)]
+    Crossings(usize),
+    #[error(transparent)]
+    Projection(#[from] DimensionError),
+}
+
+/// A Laurent polynomial with integer coefficients.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Laurent {
+    coefficients: BTreeMap<i32, i64>,
+}
+
+impl Laurent {
+    #[must_use]
+    pub fn monomial(exponent: i32, coefficient: i64) -> Self {
+        let mut coefficients = BTreeMap::new();
+        if coefficient != 0 {
+            coefficients.insert(exponent, coefficient);
+        }
+        Self { coefficients }
+    }
+
+    #[must_use]
+    pub fn coefficient(&self, exponent: i32) -> i64 {
+        self.coefficients
+            .get(&exponent)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The non-zero terms of the polynomial, as pairs of exponent and coefficient.
+    pub fn terms(&self) -> impl Iterator<Item = (i32, i64)> + '_ {
+        self.coefficients.iter().map(|(&e, &c)| (e, c))
+    }
+
+    fn pow(&self, n: usize) -> Self {
+        (0..n).fold(Self::monomial(0, 1), |acc, _| &acc * self)
+    }
+
+    fn map_exponents(&self, f: impl Fn(i32) -> i32) -> Self {
+        Self {
+            coefficients: self.terms().map(|(e, c)| (f(e), c)).collect(),
+        }
+    }
+
+    /// Displays the polynomial in the given variable, with exponents counted in multiples of
+    /// `1 / denominator`.
+    #[must_use]
+    pub fn display<'a>(&'a self, variable: &'a str, denominator: i32) -> impl fmt::Display + 'a {
+        LaurentDisplay {
+            polynomial: self,
+            variable,
+            denominator,
+        }
+    }
+}
+
+impl Add for &Laurent {
+    type Output = Laurent;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut coefficients = self.coefficients.clone();
+        for (e, c) in rhs.terms() {
+            let coefficient = coefficients.entry(e).or_default();
+            *coefficient += c;
+            if *coefficient == 0 {
+                coefficients.remove(&e);
+            }
+        }
+        Laurent { coefficients }
+    }
+}
+
+impl Mul for &Laurent {
+    type Output = Laurent;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.terms()
+            .cartesian_product(rhs.terms().collect_vec())
+            .fold(Laurent::default(), |acc, ((e, c), (f, d))| {
+                &acc + &Laurent::monomial(e + f, c * d)
+            })
+    }
+}
+
+struct LaurentDisplay<'a> {
+    polynomial: &'a Laurent,
+    variable: &'a str,
+    denominator: i32,
+}
+
+impl<'a> fmt::Display for LaurentDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.polynomial.coefficients.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, (e, c)) in self.polynomial.terms().enumerate() {
+            match (i, c < 0) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            if c.abs() != 1 || e == 0 {
+                write!(f, "{}", c.abs())?;
+            }
+            match (e, e % self.denominator) {
+                (0, _) => {}
+                (_, 0) if e == self.denominator => write!(f, "{}", self.variable)?,
+                (_, 0) => write!(f, "{}^{}", self.variable, e / self.denominator)?,
+                _ => write!(f, "{}^({}/{})", self.variable, e, self.denominator)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Classical invariants of a link.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LinkInvariants {
+    pub components: usize,
+    /// The sum of the signs of all crossings.
+    pub writhe: isize,
+    /// The linking numbers of each pair of components, with zeros on the diagonal.
+    pub linking_numbers: Vec<Vec<isize>>,
+    /// The Kauffman bracket in the variable A, normalised to be 1 on the unknot without crossings.
+    pub kauffman_bracket: Laurent,
+    /// The Jones polynomial, with exponents counted in multiples of 1/2.
+    pub jones_polynomial: Laurent,
+}
+
+impl fmt::Display for LinkInvariants {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} components, writhe {}", self.components, self.writhe)?;
+        for (i, j) in (0..self.components).tuple_combinations() {
+            write!(
+                f,
+                ", linking number of {i} and {j} {}",
+                self.linking_numbers[i][j]
+            )?;
+        }
+        write!(
+            f,
+            ", Kauffman bracket {}, Jones polynomial {}",
+            self.kauffman_bracket.display("A", 1),
+            self.jones_polynomial.display("t", 2)
+        )
+    }
+}
+
+/// A link diagram, in which corner `k` of crossing `i` is numbered `4 * i + k`, with the corners
+/// ordered bottom left, bottom right, top left, top right.
+struct LinkDiagram {
+    components: usize,
+    crossings: Vec<Crossing>,
+    /// The pairs of corners joined by an arc.
+    arcs: Vec<(usize, usize)>,
+    /// The number of closed loops without crossings.
+    free_loops: usize,
+}
+
+struct Crossing {
+    /// The components of the strands in front and behind.
+    components: [usize; 2],
+    sign: isize,
+    /// Whether the strand in front goes from the bottom left to the top right.
+    rising: bool,
+}
+
+impl LinkDiagram {
+    fn invariants(&self) -> Result<LinkInvariants, KnotError> {
+        if self.crossings.len() > MAX_CROSSINGS {
+            return Err(KnotError::Crossings(self.crossings.len()));
+        }
+
+        let writhe = self.crossings.iter().map(|crossing| crossing.sign).sum();
+        let mut linking_numbers = vec![vec![0; self.components]; self.components];
+        for crossing in &self.crossings {
+            let [i, j] = crossing.components;
+            if i != j {
+                linking_numbers[i][j] += crossing.sign;
+                linking_numbers[j][i] += crossing.sign;
+            }
+        }
+        for linking_number in linking_numbers.iter_mut().flatten() {
+            *linking_number /= 2;
+        }
+
+        let loop_value = &Laurent::monomial(2, -1) + &Laurent::monomial(-2, -1);
+        let mut kauffman_bracket = Laurent::default();
+        for state in 0..1_usize << self.crossings.len() {
+            let mut loops = UnionFind::new(4 * self.crossings.len());
+            for &(a, b) in &self.arcs {
+                loops.union(a, b);
+            }
+            let mut exponent = 0;
+            for (i, crossing) in self.crossings.iter().enumerate() {
+                // The A-smoothing joins the regions swept out by turning the front strand
+                // anticlockwise.
+                let a_smoothing = state >> i & 1 == 0;
+                exponent += if a_smoothing { 1 } else { -1 };
+                let [bl, br, tl, tr] = [0, 1, 2, 3].map(|k| 4 * i + k);
+                if a_smoothing == crossing.rising {
+                    loops.union(bl, tl);
+                    loops.union(br, tr);
+                } else {
+                    loops.union(bl, br);
+                    loops.union(tl, tr);
+                }
+            }
+            let loops = loops.into_labeling().into_iter().unique().count() + self.free_loops;
+            kauffman_bracket =
+                &kauffman_bracket + &(&Laurent::monomial(exponent, 1) * &loop_value.pow(loops - 1));
+        }
+
+        // Normalise by (-A^3)^(-writhe) and substitute A = t^(-1/4).
+        let exponent = i32::try_from(writhe)
+            .ok()
+            .and_then(|writhe| writhe.checked_mul(-3))
+            .ok_or(KnotError::Crossings(self.crossings.len()))?;
+        let normalisation = Laurent::monomial(exponent, if writhe % 2 == 0 { 1 } else { -1 });
+        let jones_polynomial = (&normalisation * &kauffman_bracket).map_exponents(|e| -e / 2);
+
+        Ok(LinkInvariants {
+            components: self.components,
+            writhe,
+            linking_numbers,
+            kauffman_bracket,
+            jones_polynomial,
+        })
+    }
+}
+
+/// Computes the invariants of the link presented by a 3-dimensional diagram.
+///
+/// The Kauffman bracket is computed as a state sum, which takes time exponential in the number
+/// of crossings, so links with more than [`MAX_CROSSINGS`] crossings are rejected.
+pub fn link_invariants(diagram: &DiagramN) -> Result<LinkInvariants, KnotError> {
+    if diagram.dimension() != 3 {
+        return Err(KnotError::Dimension(diagram.dimension()));
+    }
+    let diagram: Diagram = diagram.clone().into();
+    let depths = Depths::<2>::new(&diagram)?;
+
+    // Construct the exploded graph, keeping the vertical rewrites along which strands continue.
+    let mut graph: Scaffold<Coordinate, Direction> = Scaffold::default();
+    graph.add_node(ScaffoldNode::new([Boundary::Source.into(); 2], diagram));
+    for i in 0..2 {
+        graph = graph.explode_graph(
+            |_, key, si| {
+                let mut key = *key;
+                key[i] = si;
+                Some(key)
+            },
+            |_, _, r| (i == 0).then(|| r.direction()),
+            |_, key, r| (i > 0 && r.is_atomic()).then_some(*key),
+        )?;
+    }
+
+    // The points of the projection at which a strand passes, by their node and depth.
+    let strands: IdxVec<NodeIndex, Vec<(SingularHeight, Orientation)>> = graph
+        .node_weights()
+        .map(|node| strands(&node.diagram))
+        .collect();
+    let points: Vec<(NodeIndex, SingularHeight, Orientation)> = graph
+        .node_indices()
+        .flat_map(|n| strands[n].iter().map(move |&(h, o)| (n, h, o)))
+        .collect();
+    if points.is_empty() {
+        return Err(KnotError::Empty);
+    }
+    if points
+        .iter()
+        .any(|&(n, _, _)| matches!(graph[n].key[0], SliceIndex::Boundary(_)))
+    {
+        return Err(KnotError::Open);
+    }
+    let index: FastHashMap<(NodeIndex, SingularHeight), usize> = points
+        .iter()
+        .enumerate()
+        .map(|(p, &(n, h, _))| ((n, h), p))
+        .collect();
+
+    // Follow the strands along the vertical rewrites.
+    let mut below = vec![vec![]; points.len()];
+    let mut above = vec![vec![]; points.len()];
+    let mut adjacent = vec![];
+    for e in graph.edge_references() {
+        let Rewrite::RewriteN(r) = &e.weight().rewrite else {
+            continue;
+        };
+        for &(h, _) in &strands[e.source()] {
+            if let Some(&q) = index.get(&(e.target(), r.singular_image(h))) {
+                let p = index[&(e.source(), h)];
+                match e.weight().key {
+                    Direction::Forward => below[q].push(p),
+                    Direction::Backward => above[q].push(p),
+                }
+                adjacent.push((p, q));
+            }
+        }
+    }
+
+    // Find the crossings, with the strand points at their corners.
+    let mut crossings = vec![];
+    let mut at_crossing = vec![false; points.len()];
+    for (n, group) in &(0..points.len()).group_by(|&p| points[p].0) {
+        let group = group.collect_vec();
+        if group.len() < 2 {
+            continue;
+        }
+        let coord = graph[n].key;
+        let &[p, q] = &group[..] else {
+            return Err(KnotError::NonGeneric(coord));
+        };
+        let (front, back) = if depths.node_depth(coord) == Some(points[p].1) {
+            (p, q)
+        } else {
+            (q, p)
+        };
+
+        // The horizontal position of the strand next to the crossing on either side.
+        let position = |neighbours: &[usize]| match neighbours {
+            &[r] => Ok(graph[points[r].0].key[1]),
+            _ => Err(KnotError::NonGeneric(coord)),
+        };
+        let rising = position(&below[front])? < position(&below[back])?;
+        if rising == (position(&above[front])? < position(&above[back])?) {
+            return Err(KnotError::NonGeneric(coord));
+        }
+
+        let sign = match (points[front].2, points[back].2) {
+            (Orientation::Zero, _) | (_, Orientation::Zero) => {
+                return Err(KnotError::Unoriented(coord));
+            }
+            (a, b) if (a == b) == rising => 1,
+            _ => -1,
+        };
+        let corners = if rising {
+            [
+                below[front][0],
+                below[back][0],
+                above[back][0],
+                above[front][0],
+            ]
+        } else {
+            [
+                below[back][0],
+                below[front][0],
+                above[front][0],
+                above[back][0],
+            ]
+        };
+        at_crossing[p] = true;
+        at_crossing[q] = true;
+        crossings.push((coord, [front, back], sign, rising, corners));
+    }
+
+    let mut components = UnionFind::new(points.len());
+    let mut arcs = UnionFind::new(points.len());
+    for &(p, q) in &adjacent {
+        components.union(p, q);
+        if !at_crossing[p] && !at_crossing[q] {
+            arcs.union(p, q);
+        }
+    }
+    let components = components.into_labeling();
+    let labels: Vec<usize> = components.iter().copied().unique().collect();
+    let component = |p: usize| labels.iter().position(|&l| l == components[p]).unwrap();
+
+    // The arcs between crossings join up their corners, and the remaining arcs are closed loops.
+    let arcs = arcs.into_labeling();
+    let mut ends: FastHashMap<usize, Vec<usize>> = FastHashMap::default();
+    for (i, (_, _, _, _, corners)) in crossings.iter().enumerate() {
+        for (k, &p) in corners.iter().enumerate() {
+            ends.entry(arcs[p]).or_default().push(4 * i + k);
+        }
+    }
+    let free_loops = (0..points.len())
+        .filter(|&p| !at_crossing[p])
+        .map(|p| arcs[p])
+        .unique()
+        .filter(|arc| !ends.contains_key(arc))
+        .count();
+    let arcs = ends
+        .into_values()
+        .map(|ends| match ends[..] {
+            [a, b] => Ok((a, b)),
+            _ => Err(KnotError::NonGeneric(crossings[ends[0] / 4].0)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let link = LinkDiagram {
+        components: labels.len(),
+        crossings: crossings
+            .into_iter()
+            .map(|(_, strands, sign, rising, _)| Crossing {
+                components: strands.map(component),
+                sign,
+                rising,
+            })
+            .collect(),
+        arcs,
+        free_loops,
+    };
+    link.invariants()
+}
+
+/// The depths and orientations of the strands in a slice of the projection.
+fn strands(diagram: &Diagram) -> Vec<(SingularHeight, Orientation)> {
+    let Ok(diagram) = <&DiagramN>::try_from(diagram) else {
+        return vec![];
+    };
+    diagram
+        .singular_slices()
+        .enumerate()
+        .filter_map(|(h, slice)| {
+            let g = slice.max_generator();
+            (g.generator.dimension == 2).then_some((h, g.orientation))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The closure of the braid on two strands with `n` positive crossings.
+    fn twist_closure(n: usize) -> LinkDiagram {
+        // The strands swap places at every crossing, so the closure has two components exactly
+        // when the number of crossings is even.
+        let components = 2 - n % 2;
+        let crossings = (0..n)
+            .map(|i| Crossing {
+                components: [i % components, (i + 1) % components],
+                sign: 1,
+                rising: true,
+            })
+            .collect();
+        let arcs = (0..n)
+            .flat_map(|i| {
+                let j = (i + 1) % n;
+                [(4 * i + 2, 4 * j), (4 * i + 3, 4 * j + 1)]
+            })
+            .collect();
+        LinkDiagram {
+            components,
+            crossings,
+            arcs,
+            free_loops: 0,
+        }
+    }
+
+    #[test]
+    fn unknot() {
+        let unknot = LinkDiagram {
+            components: 1,
+            crossings: vec![],
+            arcs: vec![],
+            free_loops: 1,
+        }
+        .invariants()
+        .unwrap();
+        assert_eq!(unknot.writhe, 0);
+        assert_eq!(unknot.kauffman_bracket, Laurent::monomial(0, 1));
+        assert_eq!(unknot.jones_polynomial, Laurent::monomial(0, 1));
+    }
+
+    #[test]
+    fn kink() {
+        let kink = twist_closure(1).invariants().unwrap();
+        assert_eq!(kink.writhe, 1);
+        assert_eq!(kink.kauffman_bracket.display("A", 1).to_string(), "-A^3");
+        assert_eq!(kink.jones_polynomial, Laurent::monomial(0, 1));
+    }
+
+    #[test]
+    fn hopf_link() {
+        let hopf = twist_closure(2).invariants().unwrap();
+        assert_eq!(hopf.components, 2);
+        assert_eq!(hopf.linking_numbers, vec![vec![0, 1], vec![1, 0]]);
+        assert_eq!(
+            hopf.jones_polynomial.display("t", 2).to_string(),
+            "-t^(1/2) - t^(5/2)"
+        );
+    }
+
+    #[test]
+    fn trefoil() {
+        let trefoil = twist_closure(3).invariants().unwrap();
+        assert_eq!(trefoil.components, 1);
+        assert_eq!(trefoil.writhe, 3);
+        assert_eq!(
+            trefoil.kauffman_bracket.display("A", 1).to_string(),
+            "A^-7 - A^-3 - A^5"
+        );
+        assert_eq!(
+            trefoil.jones_polynomial.display("t", 2).to_string(),
+            "t + t^3 - t^4"
+        );
+    }
+
+    #[test]
+    fn too_many_crossings() {
+        assert!(twist_closure(MAX_CROSSINGS).invariants().is_ok());
+        assert!(matches!(
+            twist_closure(MAX_CROSSINGS + 1).invariants(),
+            Err(KnotError::Crossings(n)) if n == MAX_CROSSINGS + 1
+        ));
+    }
+}
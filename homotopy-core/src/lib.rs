@@ -15,16 +15,25 @@ pub mod diagram;
 pub mod examples;
 pub mod expansion;
 pub mod factorization;
+pub mod json;
+pub mod knot;
 pub mod layout;
 pub mod manifold;
 pub mod mesh;
 pub mod migration;
 pub mod monotone;
+pub mod morphism;
+pub mod normalization;
+pub mod parallel;
+pub mod pattern;
+pub mod product;
 pub mod projection;
 pub mod rewrite;
 pub mod scaffold;
 pub mod serialize;
 pub mod signature;
+pub mod substitution;
+pub mod topology;
 pub mod typecheck;
 
 pub fn collect_garbage() {
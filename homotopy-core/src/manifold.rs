@@ -1,18 +1,18 @@
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, fmt};
 
-use homotopy_common::idx::IdxVec;
+use homotopy_common::{hash::FastHashMap, idx::IdxVec};
 use im::HashSet;
 use itertools::Itertools;
-use petgraph::{graph::NodeIndex, visit::EdgeRef};
+use petgraph::{graph::NodeIndex, unionfind::UnionFind, visit::EdgeRef};
 
 use crate::{common::Height, diagram::Diagram, scaffold::Scaffold};
 
 type SimplexVec = Vec<NodeIndex>; // An n-simplex is a list of n + 1 vertices.
-type Simplex = BTreeSet<NodeIndex>;
+pub(crate) type Simplex = BTreeSet<NodeIndex>;
 
 #[derive(Debug)]
-struct Complex {
-    facets: HashSet<Simplex>,
+pub(crate) struct Complex {
+    pub(crate) facets: HashSet<Simplex>,
 }
 
 impl Complex {
@@ -71,9 +71,165 @@ impl Complex {
         }
         result
     }
+
+    /// The number of faces of each dimension, starting with vertices.
+    fn face_counts(&self) -> Vec<usize> {
+        let mut faces: HashSet<Simplex> = HashSet::new();
+        for facet in &self.facets {
+            for face in facet.iter().copied().powerset().skip(1) {
+                faces.insert(face.into_iter().collect());
+            }
+        }
+        let mut counts = vec![0; faces.iter().map(BTreeSet::len).max().unwrap_or_default()];
+        for face in faces {
+            counts[face.len() - 1] += 1;
+        }
+        counts
+    }
+
+    fn is_connected(&self) -> bool {
+        let vertices: Vec<NodeIndex> = self.vertices().into_iter().collect();
+        let mut union_find = UnionFind::new(vertices.len());
+        for facet in &self.facets {
+            for (u, v) in facet.iter().tuple_windows() {
+                union_find.union(
+                    vertices.binary_search(u).unwrap(),
+                    vertices.binary_search(v).unwrap(),
+                );
+            }
+        }
+        (1..vertices.len()).all(|i| union_find.equiv(0, i))
+    }
+
+    /// Whether some face of codimension one in a facet of dimension `dim` has no other coface.
+    fn has_boundary(&self, dim: usize) -> bool {
+        dim > 0
+            && self
+                .facets
+                .iter()
+                .filter(|facet| facet.len() == dim + 1)
+                .flat_map(simplex_boundary)
+                .any(|ridge| self.count_cofaces(&ridge) == 1)
+    }
+
+    /// Whether the facets of dimension `dim` can be oriented such that every face of codimension
+    /// one shared by exactly two of them inherits opposite orientations.
+    pub(crate) fn is_orientable(&self, dim: usize) -> bool {
+        let facets: Vec<Vec<NodeIndex>> = self
+            .facets
+            .iter()
+            .filter(|facet| facet.len() == dim + 1)
+            .map(|facet| facet.iter().copied().collect())
+            .collect();
+
+        // The facets containing each ridge, with the sign of the ridge in their boundary.
+        let mut ridges: FastHashMap<Vec<NodeIndex>, Vec<(usize, bool)>> = FastHashMap::default();
+        for (i, facet) in facets.iter().enumerate() {
+            for j in 0..facet.len() {
+                let mut ridge = facet.clone();
+                ridge.remove(j);
+                ridges.entry(ridge).or_default().push((i, j % 2 == 0));
+            }
+        }
+
+        let mut orientations: Vec<Option<bool>> = vec![None; facets.len()];
+        for start in 0..facets.len() {
+            if orientations[start].is_some() {
+                continue;
+            }
+            orientations[start] = Some(true);
+            let mut queue = vec![start];
+            while let Some(i) = queue.pop() {
+                let orientation = orientations[i].unwrap();
+                for j in 0..facets[i].len() {
+                    let mut ridge = facets[i].clone();
+                    ridge.remove(j);
+                    let [(a, sign_a), (b, sign_b)] = ridges[&ridge][..] else {
+                        continue;
+                    };
+                    let (sign, other, other_sign) = if a == i {
+                        (sign_a, b, sign_b)
+                    } else {
+                        (sign_b, a, sign_a)
+                    };
+                    // The induced orientations of the ridge must be opposite.
+                    let required = (orientation == sign) != other_sign;
+                    match orientations[other] {
+                        None => {
+                            orientations[other] = Some(required);
+                            queue.push(other);
+                        }
+                        Some(existing) if existing != required => return false,
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+/// A description of the link of a point, which is a simplicial complex that should be a sphere
+/// for the diagram to be a manifold.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Link {
+    /// The dimension of the sphere the link is expected to be.
+    pub dimension: usize,
+    /// The number of faces of each dimension, starting with vertices.
+    pub face_counts: Vec<usize>,
+    pub disconnected: bool,
+    pub non_orientable: bool,
+    pub boundary: bool,
+}
+
+impl Link {
+    fn new(complex: &Complex, dimension: usize) -> Self {
+        Self {
+            dimension,
+            face_counts: complex.face_counts(),
+            disconnected: !complex.is_connected(),
+            non_orientable: !complex.is_orientable(dimension),
+            boundary: complex.has_boundary(dimension),
+        }
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "link is not a {}-sphere (face counts {})",
+            self.dimension,
+            self.face_counts.iter().join(", ")
+        )?;
+        for (property, description) in [
+            (self.disconnected, "disconnected"),
+            (self.non_orientable, "non-orientable"),
+            (self.boundary, "has boundary"),
+        ] {
+            if property {
+                write!(f, ", {description}")?;
+            }
+        }
+        Ok(())
+    }
 }
 
-fn stratum(coord: &[Height]) -> usize {
+/// A point of the maximal stratum of a diagram at which it is not a manifold.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NonManifoldPoint {
+    /// The coordinates of the point in the diagram.
+    pub point: Vec<Height>,
+    pub link: Link,
+}
+
+impl fmt::Display for NonManifoldPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.point.iter().join(" "), self.link)
+    }
+}
+
+pub(crate) fn stratum(coord: &[Height]) -> usize {
     coord.iter().fold(0, |stratum, h| {
         stratum
             + match h {
@@ -182,8 +338,8 @@ fn is_sphere(complex: &Complex, dim: usize) -> bool {
     false
 }
 
-#[must_use]
-pub fn is_manifold(diagram: Diagram) -> bool {
+/// The links of the points of the maximal stratum of a diagram.
+fn links(diagram: Diagram) -> impl Iterator<Item = (Vec<Height>, Complex)> {
     let dimension = diagram.dimension();
     assert!(dimension >= 2);
 
@@ -199,8 +355,13 @@ pub fn is_manifold(diagram: Diagram) -> bool {
 
     let neighbourhoods = neighbourhoods(&scaffold);
 
-    for (n, neighbourhood) in neighbourhoods {
-        if stratum(&scaffold[n].key) == max_stratum {
+    neighbourhoods
+        .into_iter()
+        .filter_map(move |(n, neighbourhood)| {
+            if stratum(&scaffold[n].key) != max_stratum {
+                return None;
+            }
+
             let neighbourhood: Vec<Simplex> = neighbourhood
                 .iter()
                 .map(|t| t.iter().copied().collect())
@@ -213,10 +374,82 @@ pub fn is_manifold(diagram: Diagram) -> bool {
 
             let boundary_complex = Complex::from_faces(HashSet::from(&visible_neighbourhood));
 
-            if !is_sphere(&boundary_complex, dimension - 2) {
-                return false;
+            Some((scaffold[n].key.clone(), boundary_complex))
+        })
+}
+
+#[must_use]
+pub fn is_manifold(diagram: Diagram) -> bool {
+    let dimension = diagram.dimension();
+    links(diagram).all(|(_, link)| is_sphere(&link, dimension - 2))
+}
+
+/// Finds the points of the maximal stratum of a diagram at which it is not a manifold, that is
+/// the points at which [`is_manifold`] fails, and describes their links.
+#[must_use]
+pub fn non_manifold_points(diagram: Diagram) -> Vec<NonManifoldPoint> {
+    let dimension = diagram.dimension();
+    links(diagram)
+        .filter(|(_, link)| !is_sphere(link, dimension - 2))
+        .map(|(point, link)| NonManifoldPoint {
+            point,
+            link: Link::new(&link, dimension - 2),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn complex(facets: &[&[usize]]) -> Complex {
+        Complex::from_faces(
+            facets
+                .iter()
+                .map(|facet| {
+                    facet
+                        .iter()
+                        .copied()
+                        .map(NodeIndex::new)
+                        .collect::<Simplex>()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn tetrahedron_boundary() {
+        let sphere = complex(&[&[0, 1, 2], &[0, 1, 3], &[0, 2, 3], &[1, 2, 3]]);
+        assert!(is_sphere(&sphere, 2));
+        assert_eq!(
+            Link::new(&sphere, 2),
+            Link {
+                dimension: 2,
+                face_counts: vec![4, 6, 4],
+                disconnected: false,
+                non_orientable: false,
+                boundary: false,
             }
-        }
+        );
+    }
+
+    #[test]
+    fn mobius_strip() {
+        let mobius = complex(&[&[0, 1, 2], &[1, 2, 3], &[2, 3, 4], &[3, 4, 0], &[4, 0, 1]]);
+        assert!(!is_sphere(&mobius, 2));
+        let link = Link::new(&mobius, 2);
+        assert!(link.non_orientable);
+        assert!(link.boundary);
+        assert!(!link.disconnected);
+    }
+
+    #[test]
+    fn two_circles() {
+        let circles = complex(&[&[0, 1], &[1, 2], &[2, 0], &[3, 4], &[4, 5], &[5, 3]]);
+        assert!(!is_sphere(&circles, 1));
+        let link = Link::new(&circles, 1);
+        assert!(link.disconnected);
+        assert!(!link.non_orientable);
+        assert!(!link.boundary);
     }
-    true
 }
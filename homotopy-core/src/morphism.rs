@@ -0,0 +1,253 @@
+use homotopy_common::hash::FastHashMap;
+use thiserror::Error;
+
+use crate::{
+    diagram::RewritingError,
+    rewrite::Cone,
+    signature::{GeneratorInfo, Signature},
+    substitution::{Substitution, SubstitutionError},
+    typecheck::{typecheck, Mode, TypeError},
+    Cospan, Diagram, Diagram0, DiagramN, Generator, Rewrite, Rewrite0, RewriteN,
+};
+
+#[derive(Debug, Error)]
+pub enum MorphismError {
+    #[error("no image is assigned to generator {0:?}")]
+    Missing(Generator),
+
+    #[error("image of generator {0:?} has the wrong dimension")]
+    Dimension(Generator),
+
+    #[error("image of generator {0:?} is not bounded by the images of its source and target")]
+    Boundary(Generator),
+
+    #[error("image of generator {0:?} is ill-typed: {1}")]
+    IllTyped(Generator, Box<TypeError>),
+
+    #[error(transparent)]
+    Substitution(#[from] SubstitutionError),
+
+    #[error(transparent)]
+    Rewriting(#[from] RewritingError),
+}
+
+/// A morphism of signatures, which assigns to each generator of its source signature a diagram
+/// of the same dimension in its target signature, such that the image of the boundary of a
+/// generator is the boundary of its image.
+///
+/// Generators which are sent to generators are simply renamed in the diagrams that are
+/// transported, while generators which are sent to composite diagrams are substituted, which is
+/// subject to the restrictions of [`Diagram::substitute`].
+#[derive(Clone, Debug)]
+pub struct SignatureMorphism {
+    images: FastHashMap<Generator, Diagram>,
+    /// The simultaneous renaming of the generators of the source signature, either to their
+    /// images or, if they are substituted, out of the way of the target signature.
+    renaming: FastHashMap<Generator, Generator>,
+    /// The generators which are substituted after renaming, in order of dimension, together with
+    /// their renamed diagrams and their images.
+    substitutions: Vec<(Generator, DiagramN, DiagramN)>,
+}
+
+impl SignatureMorphism {
+    /// Constructs the morphism with the given images, checking that every generator of the source
+    /// signature has a well-typed image, dimension by dimension, whose boundary is the image of
+    /// its boundary.
+    pub fn new(
+        source: &impl Signature,
+        target: &impl Signature,
+        images: FastHashMap<Generator, Diagram>,
+    ) -> Result<Self, MorphismError> {
+        let mut generators: Vec<_> = source.generators().collect();
+        generators.sort_by_key(|g| (g.dimension, g.id));
+
+        let fresh = source
+            .generators()
+            .chain(target.generators())
+            .map(|g| g.id + 1)
+            .max()
+            .unwrap_or_default();
+        let moved = |g: Generator| Generator::new(fresh + g.id, g.dimension);
+
+        let mut morphism = Self {
+            images: FastHashMap::default(),
+            renaming: generators.iter().map(|&g| (g, moved(g))).collect(),
+            substitutions: vec![],
+        };
+
+        for &generator in &generators {
+            let image = images
+                .get(&generator)
+                .ok_or(MorphismError::Missing(generator))?
+                .clone();
+            if image.dimension() != generator.dimension {
+                return Err(MorphismError::Dimension(generator));
+            }
+
+            // Images which are generators of the target signature are well-typed already.
+            let max_generator = image.max_generator().generator;
+            let is_generator = target
+                .generator_info(max_generator)
+                .is_some_and(|info| *info.diagram() == image);
+            if !is_generator {
+                typecheck(&image, target, Mode::default(), true)
+                    .map_err(|error| MorphismError::IllTyped(generator, error.into()))?;
+            }
+
+            let diagram = source
+                .generator_info(generator)
+                .ok_or(MorphismError::Missing(generator))?
+                .diagram();
+            if let (Diagram::DiagramN(diagram), Diagram::DiagramN(image)) = (diagram, &image) {
+                if morphism.apply(&diagram.source())? != image.source()
+                    || morphism.apply(&diagram.target())? != image.target()
+                {
+                    return Err(MorphismError::Boundary(generator));
+                }
+            }
+
+            if is_generator {
+                morphism.renaming.insert(generator, max_generator);
+            } else if let (Diagram::DiagramN(diagram), Diagram::DiagramN(image)) = (diagram, &image)
+            {
+                let diagram = Renaming::new(&morphism.renaming).diagram(diagram.clone().into());
+                morphism.substitutions.push((
+                    moved(generator),
+                    diagram.try_into().unwrap(),
+                    image.clone(),
+                ));
+            }
+
+            morphism.images.insert(generator, image);
+        }
+
+        Ok(morphism)
+    }
+
+    /// The image of a generator of the source signature.
+    #[must_use]
+    pub fn image(&self, generator: Generator) -> Option<&Diagram> {
+        self.images.get(&generator)
+    }
+
+    /// Transports a diagram in the source signature to the target signature.
+    pub fn apply(&self, diagram: &Diagram) -> Result<Diagram, MorphismError> {
+        self.substitutions.iter().try_fold(
+            Renaming::new(&self.renaming).diagram(diagram.clone()),
+            |diagram, (generator, application, image)| {
+                Substitution::new(*generator, application, image)
+                    .diagram(&diagram)
+                    .map_err(Into::into)
+            },
+        )
+    }
+
+    /// Transports a rewrite out of a diagram in the source signature to the target signature.
+    pub fn apply_rewrite(
+        &self,
+        rewrite: &Rewrite,
+        source: &Diagram,
+    ) -> Result<Rewrite, MorphismError> {
+        let target = source.clone().rewrite_forward(rewrite)?;
+
+        let mut renaming = Renaming::new(&self.renaming);
+        let mut rewrite = renaming.rewrite(rewrite);
+        let mut source = renaming.diagram(source.clone());
+        let mut target = renaming.diagram(target);
+        for (generator, application, image) in &self.substitutions {
+            let mut substitution = Substitution::new(*generator, application, image);
+            rewrite = substitution.rewrite(&rewrite, &source, &target)?;
+            source = substitution.diagram(&source)?;
+            target = substitution.diagram(&target)?;
+        }
+        Ok(rewrite)
+    }
+}
+
+/// A simultaneous renaming of generators, caching the renamed diagrams and rewrites.
+pub(crate) struct Renaming<'a> {
+    renaming: &'a FastHashMap<Generator, Generator>,
+    diagrams: FastHashMap<DiagramN, DiagramN>,
+    rewrites: FastHashMap<RewriteN, RewriteN>,
+}
+
+impl<'a> Renaming<'a> {
+    pub(crate) fn new(renaming: &'a FastHashMap<Generator, Generator>) -> Self {
+        Self {
+            renaming,
+            diagrams: FastHashMap::default(),
+            rewrites: FastHashMap::default(),
+        }
+    }
+
+    fn diagram0(&self, diagram: Diagram0) -> Diagram0 {
+        match self.renaming.get(&diagram.generator) {
+            Some(&generator) => Diagram0::new(generator, diagram.orientation),
+            None => diagram,
+        }
+    }
+
+    pub(crate) fn diagram(&mut self, diagram: Diagram) -> Diagram {
+        match diagram {
+            Diagram::Diagram0(d) => self.diagram0(d).into(),
+            Diagram::DiagramN(d) => {
+                if let Some(result) = self.diagrams.get(&d) {
+                    return result.clone().into();
+                }
+                let source = self.diagram(d.source());
+                let cospans = d.cospans().iter().map(|c| self.cospan(c)).collect();
+                let result = DiagramN::new(source, cospans);
+                self.diagrams.insert(d, result.clone());
+                result.into()
+            }
+        }
+    }
+
+    fn cospan(&mut self, cospan: &Cospan) -> Cospan {
+        Cospan {
+            forward: self.rewrite(&cospan.forward),
+            backward: self.rewrite(&cospan.backward),
+        }
+    }
+
+    fn rewrite(&mut self, rewrite: &Rewrite) -> Rewrite {
+        match rewrite {
+            Rewrite::Rewrite0(r) => match r.boundaries() {
+                None => r.clone().into(),
+                Some((source, target)) => Rewrite0::new(
+                    self.diagram0(source),
+                    self.diagram0(target),
+                    r.label().cloned(),
+                )
+                .into(),
+            },
+            Rewrite::RewriteN(r) => {
+                if let Some(result) = self.rewrites.get(r) {
+                    return result.clone().into();
+                }
+                let cones = r
+                    .cones()
+                    .iter()
+                    .map(|cone| {
+                        Cone::new(
+                            cone.index,
+                            cone.source().iter().map(|c| self.cospan(c)).collect(),
+                            self.cospan(cone.target()),
+                            cone.regular_slices()
+                                .iter()
+                                .map(|slice| self.rewrite(slice))
+                                .collect(),
+                            cone.singular_slices()
+                                .iter()
+                                .map(|slice| self.rewrite(slice))
+                                .collect(),
+                        )
+                    })
+                    .collect();
+                let result = RewriteN::new(r.dimension(), cones);
+                self.rewrites.insert(r.clone(), result.clone());
+                result.into()
+            }
+        }
+    }
+}
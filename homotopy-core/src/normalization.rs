@@ -0,0 +1,254 @@
+//! Normal forms of diagrams up to the homotopies supported by contraction and expansion.
+//!
+//! A diagram is normalised by removing its redundant levels, splitting every level into levels
+//! with a single component, and then letting each component sink down as far as contraction
+//! allows. Components which end up at the same height are contracted into a single level.
+//!
+//! Every step of the normalisation is a typechecked contraction or expansion, so two diagrams
+//! with the same normal form are always isotopic. Conversely, the normal form is canonical for
+//! diagrams of dimension at most one, and for 2-diagrams whose singular points only interact via
+//! the interchange law. In higher dimensions, isotopic diagrams may fail to be identified.
+
+use std::convert::TryFrom;
+
+use thiserror::Error;
+
+use crate::{
+    common::{Boundary, Direction, Height, SingularHeight},
+    diagram::{Diagram, DiagramN},
+    rewrite::{Cospan, RewriteN},
+    signature::Signature,
+};
+
+#[derive(Debug, Error)]
+pub enum NormalizationError {
+    #[error("splitting the level at height {0} into its components does not make progress")]
+    NoProgress(SingularHeight),
+}
+
+impl Diagram {
+    /// Computes the normal form of a diagram.
+    ///
+    /// The signature is needed to make sure that every homotopy performed during normalisation
+    /// is well-typed.
+    pub fn normalize(&self, signature: &impl Signature) -> Result<Self, NormalizationError> {
+        match self {
+            Self::Diagram0(_) => Ok(self.clone()),
+            Self::DiagramN(d) => Ok(d.normalize(signature)?.into()),
+        }
+    }
+}
+
+impl DiagramN {
+    /// Computes the normal form of a diagram.
+    ///
+    /// See [`Diagram::normalize`].
+    pub fn normalize(&self, signature: &impl Signature) -> Result<Self, NormalizationError> {
+        let mut diagram = self.clone();
+        loop {
+            let normalized = gravity(
+                &decompose(&remove_redundant(&diagram), signature)?,
+                signature,
+            );
+            // Cancellations during contraction can enable further normalisation, but only if
+            // they decrease the size of the diagram, so this terminates.
+            if normalized.size() < diagram.size() {
+                diagram = normalized;
+            } else {
+                return Ok(normalized);
+            }
+        }
+    }
+}
+
+/// Decides whether two diagrams are isotopic, by comparing their normal forms.
+///
+/// This is sound in all dimensions and complete for diagrams of dimension at most one and for
+/// 2-diagrams whose singular points only interact via the interchange law.
+pub fn are_isotopic(
+    a: &Diagram,
+    b: &Diagram,
+    signature: &impl Signature,
+) -> Result<bool, NormalizationError> {
+    if a == b {
+        return Ok(true);
+    }
+
+    match (a, b) {
+        (Diagram::DiagramN(a), Diagram::DiagramN(b)) => {
+            // Normalisation fixes the boundaries.
+            Ok(a.source() == b.source()
+                && a.target() == b.target()
+                && a.normalize(signature)? == b.normalize(signature)?)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Removes all levels whose forward and backward rewrites are equal and redundant.
+fn remove_redundant(diagram: &DiagramN) -> DiagramN {
+    let cospans: Vec<_> = diagram
+        .cospans()
+        .iter()
+        .filter(|cospan| !cospan.is_redundant())
+        .cloned()
+        .collect();
+
+    if cospans.len() == diagram.size() {
+        diagram.clone()
+    } else {
+        DiagramN::new(diagram.source(), cospans)
+    }
+}
+
+/// The singular heights of the components of a level, in increasing order.
+#[must_use]
+pub fn components(cospan: &Cospan) -> Vec<SingularHeight> {
+    let (Ok(forward), Ok(backward)) = (
+        <&RewriteN>::try_from(&cospan.forward),
+        <&RewriteN>::try_from(&cospan.backward),
+    ) else {
+        return vec![];
+    };
+
+    let mut components: Vec<_> = forward.targets().chain(backward.targets()).collect();
+    components.sort_unstable();
+    components.dedup();
+    components
+}
+
+/// Contracts the levels `height` and `height + 1` of a diagram, returning the contracted diagram
+/// together with the contracting rewrite.
+fn contract(
+    diagram: &DiagramN,
+    height: SingularHeight,
+    signature: &impl Signature,
+) -> Option<(DiagramN, Cospan)> {
+    let homotopy = diagram
+        .clone()
+        .identity()
+        .contract(
+            Boundary::Target.into(),
+            &mut [],
+            height,
+            Direction::Forward,
+            1,
+            None,
+            signature,
+        )
+        .ok()?;
+    let cospan = homotopy.cospans().last()?.clone();
+    let contracted = DiagramN::try_from(homotopy.target()).ok()?;
+    Some((contracted, cospan))
+}
+
+/// Expands the component of level `height` at singular height `component`, moving it in the
+/// given direction.
+fn expand(
+    diagram: &DiagramN,
+    height: SingularHeight,
+    component: SingularHeight,
+    direction: Direction,
+    signature: &impl Signature,
+) -> Option<DiagramN> {
+    let homotopy = diagram
+        .clone()
+        .identity()
+        .expand(
+            Boundary::Target.into(),
+            &mut [],
+            [Height::Singular(height), Height::Singular(component)],
+            direction,
+            signature,
+        )
+        .ok()?;
+    DiagramN::try_from(homotopy.target()).ok()
+}
+
+/// Splits every level of a diagram into levels with a single component each, ordered from left
+/// to right. Levels that cannot be split are left as they are.
+fn decompose(
+    diagram: &DiagramN,
+    signature: &impl Signature,
+) -> Result<DiagramN, NormalizationError> {
+    let mut diagram = diagram.clone();
+    let mut height = 0;
+    while height < diagram.size() {
+        let heights = components(&diagram.cospans()[height]);
+        if heights.len() > 1 {
+            // Move the rightmost component into a new level above.
+            if let Some(expanded) = expand(
+                &diagram,
+                height,
+                heights[heights.len() - 1],
+                Direction::Forward,
+                signature,
+            ) {
+                // The level is revisited, so it must have lost a component.
+                if components(&expanded.cospans()[height]).len() >= heights.len() {
+                    return Err(NormalizationError::NoProgress(height));
+                }
+                diagram = expanded;
+                continue;
+            }
+        }
+        height += 1;
+    }
+    Ok(diagram)
+}
+
+/// Lets every single-component level sink down as far as possible, merging it with the lowest
+/// level that it can be contracted with.
+fn gravity(diagram: &DiagramN, signature: &impl Signature) -> DiagramN {
+    let mut diagram = diagram.clone();
+    // The levels below `settled` have already been processed.
+    let mut settled = 0;
+    while settled < diagram.size() {
+        let (sunk, merged) = sink(&diagram, settled, signature);
+        diagram = sunk;
+        if !merged {
+            settled += 1;
+        }
+    }
+    diagram
+}
+
+/// Sinks the level at `height` into the levels below it. Returns whether the level was merged
+/// into another one.
+fn sink(
+    diagram: &DiagramN,
+    height: SingularHeight,
+    signature: &impl Signature,
+) -> (DiagramN, bool) {
+    if height == 0 || components(&diagram.cospans()[height]).len() != 1 {
+        return (diagram.clone(), false);
+    }
+
+    let Some((merged, homotopy)) = contract(diagram, height - 1, signature) else {
+        return (diagram.clone(), false);
+    };
+
+    // If there is a level further down, check whether the component can move past the level
+    // it was merged with. This requires tracking where the component ended up, which is only
+    // possible if the contraction did not cancel anything.
+    if height >= 2 && homotopy.backward.is_identity() {
+        let component = components(&diagram.cospans()[height])[0];
+        let forward = <&RewriteN>::try_from(&homotopy.forward).unwrap();
+        if let Ok(slice) = RewriteN::try_from(forward.slice(height)) {
+            let component = slice.singular_image(component);
+            if let Some(swapped) = expand(
+                &merged,
+                height - 1,
+                component,
+                Direction::Backward,
+                signature,
+            ) {
+                if contract(&swapped, height - 2, signature).is_some() {
+                    return sink(&swapped, height - 1, signature);
+                }
+            }
+        }
+    }
+
+    (merged, true)
+}
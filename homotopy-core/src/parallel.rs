@@ -0,0 +1,122 @@
+//! Support for the `parallel` feature, which spreads independent work over a thread pool.
+//!
+//! Hash consed diagrams and rewrites are compared by identity, so values built on different
+//! threads can only be compared if they come from the same factory. With the feature enabled,
+//! factories are therefore shared between all threads instead of being thread-local. Without it,
+//! everything here runs sequentially on the current thread.
+//!
+//! Every hash consed value is created under the lock of its shared factory, so the feature only
+//! pays off on large inputs, such as collapse in high dimensions, and slows down typechecking of
+//! small diagrams. The benchmarks can be compared with and without the feature.
+
+#[cfg(feature = "parallel")]
+use std::{hash::Hash, sync::Mutex};
+
+#[cfg(feature = "parallel")]
+use hashconsing::HConsign;
+#[cfg(feature = "parallel")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Equivalent to `Send` with the `parallel` feature, and implemented by every type otherwise.
+#[cfg(feature = "parallel")]
+pub trait MaybeSend: Send {}
+#[cfg(feature = "parallel")]
+impl<T: Send> MaybeSend for T {}
+
+/// Equivalent to `Send` with the `parallel` feature, and implemented by every type otherwise.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSend {}
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSend for T {}
+
+/// Equivalent to `Sync` with the `parallel` feature, and implemented by every type otherwise.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Sync {}
+#[cfg(feature = "parallel")]
+impl<T: Sync> MaybeSync for T {}
+
+/// Equivalent to `Sync` with the `parallel` feature, and implemented by every type otherwise.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSync for T {}
+
+/// The cache of a hash consed value, which has to be shared between threads with the `parallel`
+/// feature.
+#[cfg(feature = "parallel")]
+pub(crate) use once_cell::sync::OnceCell;
+#[cfg(not(feature = "parallel"))]
+pub(crate) use once_cell::unsync::OnceCell;
+
+/// A hash consing factory shared between threads.
+#[cfg(feature = "parallel")]
+pub(crate) struct Factory<T: Hash + Eq + Clone>(Lazy<Mutex<HConsign<T>>>);
+
+#[cfg(feature = "parallel")]
+impl<T: Hash + Eq + Clone> Factory<T> {
+    pub(crate) const fn new() -> Self {
+        Self(Lazy::new(|| Mutex::new(HConsign::with_capacity(37))))
+    }
+
+    /// Has the same signature as `LocalKey::with_borrow_mut`, so that shared and thread-local
+    /// factories can be used interchangeably.
+    pub(crate) fn with_borrow_mut<R>(&'static self, f: impl FnOnce(&mut HConsign<T>) -> R) -> R {
+        f(&mut self.0.lock().unwrap())
+    }
+}
+
+/// Declares hash consing factories, which are shared between threads with the `parallel` feature
+/// and thread-local otherwise.
+macro_rules! factories {
+    ($(static $name:ident: $t:ty;)*) => {
+        $(
+            #[cfg(feature = "parallel")]
+            static $name: $crate::parallel::Factory<$t> = $crate::parallel::Factory::new();
+        )*
+
+        #[cfg(not(feature = "parallel"))]
+        thread_local! {
+            $(static $name: std::cell::RefCell<hashconsing::HConsign<$t>> =
+                std::cell::RefCell::new(hashconsing::HConsign::with_capacity(37));)*
+        }
+    };
+}
+
+pub(crate) use factories;
+
+/// Returns the first `Some` produced by `f` on the items, in order.
+pub(crate) fn find_map_first<T, R, F>(items: Vec<T>, f: F) -> Option<R>
+where
+    T: MaybeSend,
+    R: MaybeSend,
+    F: Fn(T) -> Option<R> + MaybeSend + MaybeSync,
+{
+    #[cfg(feature = "parallel")]
+    return items.into_par_iter().find_map_first(f);
+
+    #[cfg(not(feature = "parallel"))]
+    return items.into_iter().find_map(f);
+}
+
+/// Keeps the items satisfying `f`, in order.
+pub(crate) fn filter<T, F>(items: Vec<T>, f: F) -> Vec<T>
+where
+    T: MaybeSend,
+    F: Fn(&T) -> bool + MaybeSend + MaybeSync,
+{
+    #[cfg(feature = "parallel")]
+    return items.into_par_iter().filter(f).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    return items.into_iter().filter(f).collect();
+}
+
+/// Runs `f` on the current thread and, with the `parallel` feature, on every thread of the pool.
+pub(crate) fn on_every_thread(f: impl Fn() + MaybeSync) {
+    #[cfg(feature = "parallel")]
+    rayon::broadcast(|_| f());
+
+    f();
+}
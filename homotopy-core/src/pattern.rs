@@ -0,0 +1,147 @@
+//! Searching for occurrences of a pattern diagram inside a host diagram.
+//!
+//! A pattern of dimension `k` can occur in any `k`-dimensional slice of the host, whether regular
+//! or singular. Occurrences are reported as the path to that slice together with the embedding
+//! of the pattern into it, in the format used by [`Diagram::embeddings`].
+
+use crate::{
+    common::{Generator, Height, RegularHeight},
+    diagram::Diagram,
+};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Occurrence {
+    /// The path of interior heights leading to the slice of the host which contains the
+    /// occurrence.
+    pub path: Vec<Height>,
+    /// The embedding of the pattern into the slice.
+    pub embedding: Vec<RegularHeight>,
+    /// The generators of the pattern which were renamed to obtain this occurrence, together with
+    /// their new names. Empty for exact matches.
+    pub renaming: Vec<(Generator, Generator)>,
+}
+
+/// Finds all occurrences of `pattern` in `host`.
+///
+/// An occurrence which persists unchanged through consecutive slices is only reported in the
+/// first of them.
+#[must_use]
+pub fn occurrences(pattern: &Diagram, host: &Diagram) -> Vec<Occurrence> {
+    if host.dimension() == pattern.dimension() {
+        return host
+            .embeddings(pattern)
+            .map(|embedding| Occurrence {
+                path: vec![],
+                embedding,
+                renaming: vec![],
+            })
+            .collect();
+    }
+
+    let Diagram::DiagramN(host) = host else {
+        return vec![];
+    };
+
+    let mut occurrences = Vec::new();
+    // The occurrences found in the previous slice, relative to that slice.
+    let mut previous = Vec::new();
+    for (height, slice) in Height::for_size(host.size()).zip(host.slices()) {
+        let current = self::occurrences(pattern, &slice);
+        for occurrence in &current {
+            if !previous.contains(occurrence) {
+                let mut path = vec![height];
+                path.extend(&occurrence.path);
+                occurrences.push(Occurrence {
+                    path,
+                    ..occurrence.clone()
+                });
+            }
+        }
+        previous = current;
+    }
+    occurrences
+}
+
+/// Finds all occurrences of `pattern` in `host` up to a renaming of the generators of `pattern`.
+///
+/// Generators are only renamed to generators of the same dimension, and distinct generators are
+/// renamed to distinct generators. This tries every such renaming, so it is exponential in the
+/// number of generators of `pattern`.
+#[must_use]
+pub fn occurrences_up_to_renaming(pattern: &Diagram, host: &Diagram) -> Vec<Occurrence> {
+    let mut from: Vec<Generator> = pattern.generators().into_keys().collect();
+    from.sort_by_key(|g| (g.dimension, g.id));
+    let mut candidates: Vec<Generator> = host.generators().into_keys().collect();
+    candidates.sort_by_key(|g| (g.dimension, g.id));
+
+    // Rename to fresh generators first, so that renamings which permute generators do not clash.
+    let fresh = from
+        .iter()
+        .chain(&candidates)
+        .map(|g| g.id)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let pattern = from
+        .iter()
+        .enumerate()
+        .fold(pattern.clone(), |pattern, (i, g)| {
+            pattern.replace(*g, Generator::new(fresh + i, g.dimension), false)
+        });
+
+    let mut occurrences = Vec::new();
+    let mut to = Vec::with_capacity(from.len());
+    rename(
+        &pattern,
+        host,
+        fresh,
+        &from,
+        &candidates,
+        &mut to,
+        &mut occurrences,
+    );
+    occurrences
+}
+
+fn rename(
+    pattern: &Diagram,
+    host: &Diagram,
+    fresh: usize,
+    from: &[Generator],
+    candidates: &[Generator],
+    to: &mut Vec<Generator>,
+    occurrences: &mut Vec<Occurrence>,
+) {
+    let i = to.len();
+    let Some(generator) = from.get(i) else {
+        let renaming: Vec<_> = from
+            .iter()
+            .copied()
+            .zip(to.iter().copied())
+            .filter(|(from, to)| from != to)
+            .collect();
+        occurrences.extend(
+            self::occurrences(pattern, host)
+                .into_iter()
+                .map(|occurrence| Occurrence {
+                    renaming: renaming.clone(),
+                    ..occurrence
+                }),
+        );
+        return;
+    };
+
+    for candidate in candidates {
+        if candidate.dimension != generator.dimension || to.contains(candidate) {
+            continue;
+        }
+        let renamed = pattern.replace(
+            Generator::new(fresh + i, generator.dimension),
+            *candidate,
+            false,
+        );
+        to.push(*candidate);
+        rename(&renamed, host, fresh, from, candidates, to, occurrences);
+        to.pop();
+    }
+}
@@ -0,0 +1,714 @@
+use homotopy_common::hash::{FastHashMap, FastHashSet};
+use thiserror::Error;
+
+use crate::{
+    common::{Boundary, Generator, Orientation},
+    contraction::{contraction, ContractionError},
+    diagram::{AttachmentError, NewDiagramError},
+    morphism::Renaming,
+    signature::{GeneratorInfo, Invertibility, Signature},
+    Cospan, Diagram, Diagram0, DiagramN, Rewrite,
+};
+
+/// The highest dimension of a product of two diagrams of positive dimension.
+///
+/// Higher products would have to interchange cells of dimension 3 and above in slices which
+/// already contain interchangers, which products do not construct.
+pub const MAX_DIMENSION: usize = 3;
+
+#[derive(Debug, Error)]
+pub enum ProductError {
+    #[error("generator {0:?} is not in the signature of its factor")]
+    UnknownGenerator(Generator),
+
+    #[error(
+        "the product of a {0}-diagram with a {1}-diagram is above dimension {}",
+        MAX_DIMENSION
+    )]
+    Dimension(usize, usize),
+
+    #[error("factors must be composites of positively oriented cells")]
+    Composite,
+
+    #[error("cells of the product can not be interchanged")]
+    Interchange,
+
+    #[error(transparent)]
+    NewDiagram(#[from] NewDiagramError),
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+
+    #[error(transparent)]
+    Contraction(#[from] ContractionError),
+}
+
+/// The Gray product of two signatures, with a generator for every pair of a generator of each.
+///
+/// The product of an n-cell a and an m-cell b is an (n + m)-cell. Its source is the product of
+/// the source of a with b, followed by the product of a with the source of b if n is even and with
+/// the target of b if n is odd. Its target is the product of a with the other boundary of b,
+/// followed by the product of the target of a with b. For example, the product of two 1-cells
+/// f : x -> y and g : u -> v is the square from (x, g) followed by (f, v) to (f, u) followed by
+/// (y, g). Beyond products with 0-cells, which are copies of the other generator, generators are
+/// only formed up to [`MAX_DIMENSION`].
+#[derive(Clone, Debug, Default)]
+pub struct Product {
+    generators: Vec<ProductInfo>,
+    cells: FastHashMap<(Generator, Generator), Generator>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProductInfo {
+    pub factors: (Generator, Generator),
+    diagram: Diagram,
+    invertibility: Invertibility,
+}
+
+impl GeneratorInfo for ProductInfo {
+    fn diagram(&self) -> &Diagram {
+        &self.diagram
+    }
+
+    fn invertibility(&self) -> Invertibility {
+        self.invertibility
+    }
+}
+
+impl Signature for Product {
+    type Info = ProductInfo;
+
+    fn generators(&self) -> impl Iterator<Item = Generator> {
+        self.generators.iter().enumerate().map(|(id, info)| {
+            let (a, b) = info.factors;
+            Generator::new(id, a.dimension + b.dimension)
+        })
+    }
+
+    fn generator_info(&self, g: Generator) -> Option<&ProductInfo> {
+        self.generators.get(g.id)
+    }
+}
+
+impl Product {
+    /// Forms the product of every pair of generators, in order of dimension, leaving out pairs of
+    /// generators of positive dimension whose product is above [`MAX_DIMENSION`].
+    ///
+    /// The product of two generators of positive dimension is as invertible as the least
+    /// invertible of them.
+    pub fn new(left: &impl Signature, right: &impl Signature) -> Result<Self, ProductError> {
+        let mut pairs: Vec<_> = left
+            .generators()
+            .flat_map(|a| right.generators().map(move |b| (a, b)))
+            .filter(|(a, b)| {
+                a.dimension == 0 || b.dimension == 0 || a.dimension + b.dimension <= MAX_DIMENSION
+            })
+            .collect();
+        pairs.sort_by_key(|(a, b)| (a.dimension + b.dimension, a.dimension, a.id, b.id));
+
+        let mut product = Self::default();
+        for (a, b) in pairs {
+            let a_info = left
+                .generator_info(a)
+                .ok_or(ProductError::UnknownGenerator(a))?;
+            let b_info = right
+                .generator_info(b)
+                .ok_or(ProductError::UnknownGenerator(b))?;
+
+            let generator = Generator::new(product.generators.len(), a.dimension + b.dimension);
+            product.cells.insert((a, b), generator);
+            let diagram = match (a_info.diagram(), b_info.diagram()) {
+                (Diagram::Diagram0(_), Diagram::Diagram0(_)) => Diagram0::from(generator).into(),
+                (Diagram::DiagramN(a), Diagram::DiagramN(b)) => {
+                    let (source, target) = product.boundary(a, b)?;
+                    DiagramN::from_generator(generator, source, target)?.into()
+                }
+                (a, b) => product.diagram(a, b)?,
+            };
+            let invertibility = match (a.dimension, b.dimension) {
+                (0, _) => b_info.invertibility(),
+                (_, 0) => a_info.invertibility(),
+                _ => a_info.invertibility().min(b_info.invertibility()),
+            };
+
+            product.generators.push(ProductInfo {
+                factors: (a, b),
+                diagram,
+                invertibility,
+            });
+        }
+
+        Ok(product)
+    }
+
+    /// The product of a generator of each factor.
+    #[must_use]
+    pub fn cell(&self, left: Generator, right: Generator) -> Option<Generator> {
+        self.cells.get(&(left, right)).copied()
+    }
+
+    /// The product of two diagrams, whose dimension is the sum of their dimensions.
+    ///
+    /// Products with a 0-diagram are copies of the other diagram. The product of two 1-diagrams
+    /// is a grid of squares. The product of a 2-diagram with a 1-diagram moves every cell of the
+    /// 2-diagram through the 1-cells of the other, one after the other, with interchangers in
+    /// between which bring the cells that are moved next to each other. Diagrams of positive
+    /// dimension must be composites of positively oriented cells, and their product can be at
+    /// most [`MAX_DIMENSION`]-dimensional.
+    pub fn diagram(&self, left: &Diagram, right: &Diagram) -> Result<Diagram, ProductError> {
+        match (left, right) {
+            (Diagram::Diagram0(x), _) => self.rename(right, |g| (x.generator, g)),
+            (_, Diagram::Diagram0(y)) => self.rename(left, |g| (g, y.generator)),
+            (Diagram::DiagramN(left), Diagram::DiagramN(right)) => {
+                match (left.dimension(), right.dimension()) {
+                    (1, 1) => self.grid(left, right),
+                    (2, 1) => self.left_cylinder(left, right),
+                    (1, 2) => self.right_cylinder(left, right),
+                    (n, m) => Err(ProductError::Dimension(n, m)),
+                }
+                .map(Into::into)
+            }
+        }
+    }
+
+    fn product(&self, left: &Diagram, right: &Diagram) -> Result<DiagramN, ProductError> {
+        self.diagram(left, right)?
+            .try_into()
+            .map_err(|_| ProductError::Dimension(left.dimension(), right.dimension()))
+    }
+
+    /// The diagram of the product of two generators of positive dimension.
+    fn generator(&self, left: Generator, right: Generator) -> Result<DiagramN, ProductError> {
+        let generator = self
+            .cell(left, right)
+            .ok_or(ProductError::UnknownGenerator(left))?;
+        self.generators[generator.id]
+            .diagram
+            .clone()
+            .try_into()
+            .map_err(|_| ProductError::Dimension(left.dimension, right.dimension))
+    }
+
+    fn rename(
+        &self,
+        diagram: &Diagram,
+        pair: impl Fn(Generator) -> (Generator, Generator),
+    ) -> Result<Diagram, ProductError> {
+        let renaming = diagram
+            .generators()
+            .into_keys()
+            .map(|g| {
+                let (a, b) = pair(g);
+                self.cell(a, b)
+                    .map(|cell| (g, cell))
+                    .ok_or(ProductError::UnknownGenerator(g))
+            })
+            .collect::<Result<FastHashMap<_, _>, _>>()?;
+        Ok(Renaming::new(&renaming).diagram(diagram.clone()))
+    }
+
+    /// The source and target of the product of two generators of positive dimension.
+    fn boundary(
+        &self,
+        left: &DiagramN,
+        right: &DiagramN,
+    ) -> Result<(DiagramN, DiagramN), ProductError> {
+        let (a, b): (Diagram, Diagram) = (left.clone().into(), right.clone().into());
+        match (left.dimension(), right.dimension()) {
+            (1, 1) => Ok((
+                compose(
+                    &self.product(&left.source(), &b)?,
+                    &self.product(&a, &right.target())?,
+                )?,
+                compose(
+                    &self.product(&a, &right.source())?,
+                    &self.product(&left.target(), &b)?,
+                )?,
+            )),
+            (2, 1) => Ok((
+                self.product(&left.source(), &b)?.attach(
+                    &self.product(&a, &right.source())?,
+                    Boundary::Target,
+                    &[0],
+                )?,
+                self.product(&left.target(), &b)?.attach(
+                    &self.product(&a, &right.target())?,
+                    Boundary::Source,
+                    &[right.size()],
+                )?,
+            )),
+            (1, 2) => Ok((
+                self.product(&a, &right.target())?.attach(
+                    &self.product(&left.source(), &b)?,
+                    Boundary::Source,
+                    &[0],
+                )?,
+                self.product(&a, &right.source())?.attach(
+                    &self.product(&left.target(), &b)?,
+                    Boundary::Target,
+                    &[left.size()],
+                )?,
+            )),
+            (n, m) => Err(ProductError::Dimension(n, m)),
+        }
+    }
+
+    /// The product of two 1-diagrams, from the product of the source of the left diagram with
+    /// the right diagram followed by the product of the left diagram with the target of the right
+    /// diagram. Every square moves a 1-cell of the right diagram past a 1-cell of the left one.
+    fn grid(&self, left: &DiagramN, right: &DiagramN) -> Result<DiagramN, ProductError> {
+        let (fs, gs) = (points(left)?, points(right)?);
+        let source = compose(
+            &self.product(&left.source(), &right.clone().into())?,
+            &self.product(&left.clone().into(), &right.target())?,
+        )?;
+
+        let mut grid = source.identity();
+        for (i, &f) in fs.iter().enumerate() {
+            for (j, &g) in gs.iter().enumerate().rev() {
+                grid = grid.attach(&self.generator(f, g)?, Boundary::Target, &[i + j])?;
+            }
+        }
+        Ok(grid)
+    }
+
+    /// The product of a 2-diagram with a 1-diagram, which moves each cell of the 2-diagram from
+    /// the source of the 1-diagram to its target, in order of height.
+    fn left_cylinder(&self, left: &DiagramN, right: &DiagramN) -> Result<DiagramN, ProductError> {
+        let Cells {
+            wires,
+            slices,
+            cells,
+        } = decompose(left)?;
+        let (gs, us) = (points(right)?, zeros(right)?);
+
+        let wire = |w: usize, j: usize| -> Result<Placed, ProductError> {
+            Ok(Placed {
+                face: Face::Grid(w, j),
+                diagram: self.generator(wires[w], gs[j - 1])?,
+                position: 0,
+                source: vec![Edge::Cross(j), Edge::Wire(w, j)],
+                target: vec![Edge::Wire(w, j - 1), Edge::Cross(j)],
+            })
+        };
+        let cell = |i: usize, j: usize| -> Result<Placed, ProductError> {
+            let edges = |wires: &[usize]| wires.iter().map(|&w| Edge::Wire(w, j)).collect();
+            Ok(Placed {
+                face: Face::Cell(i, j),
+                diagram: self.generator(cells[i].generator, us[j])?,
+                position: 0,
+                source: edges(&cells[i].source),
+                target: edges(&cells[i].target),
+            })
+        };
+
+        let first: DiagramN = left.source().try_into().unwrap();
+        let mut sweep = Sweep {
+            source: compose(
+                &self.product(&first.source(), &right.clone().into())?,
+                &self.product(&first.into(), &right.target())?,
+            )?,
+            faces: vec![],
+        };
+        for (a, &w) in slices[0].iter().enumerate() {
+            for j in (1..=gs.len()).rev() {
+                sweep.faces.push(wire(w, j)?.at(a + j - 1));
+            }
+        }
+        for (i, c) in cells.iter().enumerate() {
+            sweep.faces.push(cell(i, 0)?.at(c.position));
+        }
+
+        let source = sweep.slice()?;
+        let mut cospans = vec![];
+        for (i, c) in cells.iter().enumerate() {
+            for (j, &g) in gs.iter().enumerate().map(|(j, g)| (j + 1, g)) {
+                let block: Vec<_> = c
+                    .source
+                    .iter()
+                    .map(|&w| Face::Grid(w, j))
+                    .chain([Face::Cell(i, j - 1)])
+                    .collect();
+                let faces = std::iter::once(cell(i, j))
+                    .chain(c.target.iter().map(|&w| wire(w, j)))
+                    .collect::<Result<_, _>>()?;
+                cospans.extend(sweep.gather(&block)?);
+                cospans.push(sweep.apply(&self.generator(c.generator, g)?, &block, faces)?);
+            }
+        }
+        // Arrange the target as the cells followed by the grid of the target of the 2-diagram.
+        let target: Vec<_> = (0..cells.len())
+            .map(|i| Face::Cell(i, gs.len()))
+            .chain(
+                slices[cells.len()]
+                    .iter()
+                    .flat_map(|&w| (1..=gs.len()).rev().map(move |j| Face::Grid(w, j))),
+            )
+            .collect();
+        cospans.extend(sweep.gather(&target)?);
+
+        Ok(DiagramN::new(source.into(), cospans))
+    }
+
+    /// The product of a 1-diagram with a 2-diagram, which moves each cell of the 2-diagram from
+    /// the source of the 1-diagram to its target, from the top down.
+    fn right_cylinder(&self, left: &DiagramN, right: &DiagramN) -> Result<DiagramN, ProductError> {
+        let Cells {
+            wires,
+            slices,
+            cells,
+        } = decompose(right)?;
+        let (fs, xs) = (points(left)?, zeros(left)?);
+
+        let wire = |i: usize, w: usize| -> Result<Placed, ProductError> {
+            Ok(Placed {
+                face: Face::Grid(i, w),
+                diagram: self.generator(fs[i - 1], wires[w])?,
+                position: 0,
+                source: vec![Edge::Wire(i - 1, w), Edge::Cross(i)],
+                target: vec![Edge::Cross(i), Edge::Wire(i, w)],
+            })
+        };
+        let cell = |r: usize, j: usize| -> Result<Placed, ProductError> {
+            let edges = |wires: &[usize]| wires.iter().map(|&w| Edge::Wire(r, w)).collect();
+            Ok(Placed {
+                face: Face::Cell(r, j),
+                diagram: self.generator(xs[r], cells[j].generator)?,
+                position: 0,
+                source: edges(&cells[j].source),
+                target: edges(&cells[j].target),
+            })
+        };
+
+        let first: DiagramN = right.source().try_into().unwrap();
+        let mut sweep = Sweep {
+            source: compose(
+                &self.product(&left.source(), &first.clone().into())?,
+                &self.product(&left.clone().into(), &first.target())?,
+            )?,
+            faces: vec![],
+        };
+        for (j, c) in cells.iter().enumerate() {
+            sweep.faces.push(cell(0, j)?.at(c.position));
+        }
+        for i in 1..=fs.len() {
+            for (a, &w) in slices[cells.len()].iter().enumerate().rev() {
+                sweep.faces.push(wire(i, w)?.at(i - 1 + a));
+            }
+        }
+
+        let source = sweep.slice()?;
+        let mut cospans = vec![];
+        for (j, c) in cells.iter().enumerate().rev() {
+            for (i, &f) in fs.iter().enumerate().map(|(i, f)| (i + 1, f)) {
+                let block: Vec<_> = std::iter::once(Face::Cell(i - 1, j))
+                    .chain(c.target.iter().rev().map(|&w| Face::Grid(i, w)))
+                    .collect();
+                let faces = c
+                    .source
+                    .iter()
+                    .rev()
+                    .map(|&w| wire(i, w))
+                    .chain([cell(i, j)])
+                    .collect::<Result<_, _>>()?;
+                cospans.extend(sweep.gather(&block)?);
+                cospans.push(sweep.apply(&self.generator(f, c.generator)?, &block, faces)?);
+            }
+        }
+        // Arrange the target as the grid of the source of the 2-diagram followed by the cells.
+        let target: Vec<_> = (1..=fs.len())
+            .flat_map(|i| slices[0].iter().rev().map(move |&w| Face::Grid(i, w)))
+            .chain((0..cells.len()).map(|j| Face::Cell(fs.len(), j)))
+            .collect();
+        cospans.extend(sweep.gather(&target)?);
+
+        Ok(DiagramN::new(source.into(), cospans))
+    }
+}
+
+impl DiagramN {
+    /// The Gray product of this diagram with another one, see [`Product::diagram`].
+    pub fn product(&self, other: &Self, signature: &Product) -> Result<Self, ProductError> {
+        signature.product(&self.clone().into(), &other.clone().into())
+    }
+}
+
+fn compose(first: &DiagramN, second: &DiagramN) -> Result<DiagramN, AttachmentError> {
+    first.attach(second, Boundary::Target, &[])
+}
+
+/// The 1-cells of a 1-diagram.
+fn points(diagram: &DiagramN) -> Result<Vec<Generator>, ProductError> {
+    diagram
+        .singular_slices()
+        .map(|slice| match slice {
+            Diagram::Diagram0(d) if d.generator.dimension == 1 => positive(d),
+            _ => Err(ProductError::Composite),
+        })
+        .collect()
+}
+
+/// The 0-cells of a 1-diagram.
+fn zeros(diagram: &DiagramN) -> Result<Vec<Generator>, ProductError> {
+    diagram
+        .regular_slices()
+        .map(|slice| match slice {
+            Diagram::Diagram0(d) => positive(d),
+            Diagram::DiagramN(_) => Err(ProductError::Composite),
+        })
+        .collect()
+}
+
+fn positive(diagram: Diagram0) -> Result<Generator, ProductError> {
+    if diagram.orientation == Orientation::Positive {
+        Ok(diagram.generator)
+    } else {
+        Err(ProductError::Composite)
+    }
+}
+
+/// The position of each cell of a composite of cells in the regular slice below it.
+fn positions(diagram: &DiagramN) -> Vec<usize> {
+    diagram
+        .cospans()
+        .iter()
+        .map(|cospan| match &cospan.forward {
+            Rewrite::RewriteN(r) => r.cones()[0].index,
+            Rewrite::Rewrite0(_) => 0,
+        })
+        .collect()
+}
+
+/// The cells of a 2-diagram, with its wires numbered in order of appearance.
+struct Cells {
+    /// The generator of each wire.
+    wires: Vec<Generator>,
+    /// The wires of each regular slice.
+    slices: Vec<Vec<usize>>,
+    cells: Vec<Cell>,
+}
+
+struct Cell {
+    generator: Generator,
+    position: usize,
+    source: Vec<usize>,
+    target: Vec<usize>,
+}
+
+fn decompose(diagram: &DiagramN) -> Result<Cells, ProductError> {
+    let regular = |slice: Diagram| -> Result<Vec<Generator>, ProductError> {
+        points(&slice.try_into().map_err(|_| ProductError::Composite)?)
+    };
+
+    let wires = regular(diagram.source())?;
+    let mut slice: Vec<_> = (0..wires.len()).collect();
+    let mut decomposition = Cells {
+        wires,
+        slices: vec![slice.clone()],
+        cells: vec![],
+    };
+
+    let slices = diagram
+        .singular_slices()
+        .zip(diagram.regular_slices().skip(1));
+    for (cospan, (singular, next)) in diagram.cospans().iter().zip(slices) {
+        let (Rewrite::RewriteN(forward), Rewrite::RewriteN(backward)) =
+            (&cospan.forward, &cospan.backward)
+        else {
+            return Err(ProductError::Composite);
+        };
+        let ([f], [b]) = (forward.cones(), backward.cones()) else {
+            return Err(ProductError::Composite);
+        };
+        if f.index != b.index {
+            return Err(ProductError::Composite);
+        }
+
+        let singular: DiagramN = singular.try_into().map_err(|_| ProductError::Composite)?;
+        let generator = match singular.singular_slices().nth(f.index) {
+            Some(Diagram::Diagram0(d)) if d.generator.dimension == 2 => positive(d)?,
+            _ => return Err(ProductError::Composite),
+        };
+
+        let created = regular(next)?;
+        let target: Vec<_> = created[f.index..f.index + b.len()]
+            .iter()
+            .map(|&g| {
+                decomposition.wires.push(g);
+                decomposition.wires.len() - 1
+            })
+            .collect();
+        let source = slice
+            .splice(f.index..f.index + f.len(), target.iter().copied())
+            .collect();
+
+        decomposition.cells.push(Cell {
+            generator,
+            position: f.index,
+            source,
+            target,
+        });
+        decomposition.slices.push(slice.clone());
+    }
+
+    Ok(decomposition)
+}
+
+/// A 1-cell in a slice of the product of a 2-diagram with a 1-diagram.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Edge {
+    /// The product of a wire of the 2-diagram with a 0-cell of the 1-diagram.
+    Wire(usize, usize),
+    /// The product of a 1-cell of the 1-diagram with a 0-cell of the 2-diagram.
+    Cross(usize),
+}
+
+/// A 2-cell in a slice of the product of a 2-diagram with a 1-diagram.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Face {
+    /// The product of a wire of the 2-diagram with a 1-cell of the 1-diagram.
+    Grid(usize, usize),
+    /// The product of a cell of the 2-diagram with a 0-cell of the 1-diagram.
+    Cell(usize, usize),
+}
+
+/// A 2-cell placed at a position in the regular slice below it.
+struct Placed {
+    face: Face,
+    diagram: DiagramN,
+    position: usize,
+    source: Vec<Edge>,
+    target: Vec<Edge>,
+}
+
+impl Placed {
+    fn at(self, position: usize) -> Self {
+        Self { position, ..self }
+    }
+}
+
+/// A 2-dimensional slice of a 3-dimensional product, as a sequence of 2-cells on its source.
+struct Sweep {
+    source: DiagramN,
+    faces: Vec<Placed>,
+}
+
+impl Sweep {
+    fn slice(&self) -> Result<DiagramN, AttachmentError> {
+        self.faces
+            .iter()
+            .try_fold(self.source.clone().identity(), |slice, face| {
+                slice.attach(&face.diagram, Boundary::Target, &[face.position])
+            })
+    }
+
+    /// The interchanger which swaps the 2-cell at a height with the one above it.
+    fn swap(&mut self, height: usize) -> Result<Cospan, ProductError> {
+        let before = self.slice()?;
+
+        let (lower, upper) = (&self.faces[height], &self.faces[height + 1]);
+        let (lower_position, upper_position) =
+            if upper.position >= lower.position + lower.target.len() {
+                (
+                    lower.position,
+                    upper.position + lower.source.len() - lower.target.len(),
+                )
+            } else if upper.position + upper.source.len() <= lower.position {
+                (
+                    lower.position + upper.target.len() - upper.source.len(),
+                    upper.position,
+                )
+            } else {
+                return Err(ProductError::Interchange);
+            };
+        self.faces[height].position = lower_position;
+        self.faces[height + 1].position = upper_position;
+        self.faces.swap(height, height + 1);
+
+        let after = self.slice()?;
+        Ok(Cospan {
+            forward: contraction(&before, height)?.into(),
+            backward: contraction(&after, height)?.into(),
+        })
+    }
+
+    /// Brings the 2-cells of a block next to each other in the given order by interchangers,
+    /// moving every 2-cell in between which depends on the block above it and every other one
+    /// below it.
+    fn gather(&mut self, block: &[Face]) -> Result<Vec<Cospan>, ProductError> {
+        let indices = block
+            .iter()
+            .map(|face| {
+                self.faces
+                    .iter()
+                    .position(|placed| placed.face == *face)
+                    .ok_or(ProductError::Interchange)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let first = indices.iter().copied().min().unwrap_or_default();
+
+        let mut produced = FastHashSet::default();
+        let (mut below, mut above) = (vec![], vec![]);
+        for (i, placed) in self.faces.iter().enumerate().skip(first) {
+            if block.contains(&placed.face) {
+                produced.extend(placed.target.iter().copied());
+            } else if placed.source.iter().any(|edge| produced.contains(edge)) {
+                produced.extend(placed.target.iter().copied());
+                above.push(i);
+            } else {
+                below.push(i);
+            }
+        }
+
+        let mut rank = vec![0; self.faces.len()];
+        for (r, i) in (0..first)
+            .chain(below)
+            .chain(indices)
+            .chain(above)
+            .enumerate()
+        {
+            rank[i] = r;
+        }
+
+        let mut cospans = vec![];
+        for end in (1..rank.len()).rev() {
+            for height in 0..end {
+                if rank[height] > rank[height + 1] {
+                    cospans.push(self.swap(height)?);
+                    rank.swap(height, height + 1);
+                }
+            }
+        }
+        Ok(cospans)
+    }
+
+    /// Applies a 3-cell to a gathered block of 2-cells, which are replaced by the given 2-cells
+    /// of its target.
+    fn apply(
+        &mut self,
+        cell: &DiagramN,
+        block: &[Face],
+        faces: Vec<Placed>,
+    ) -> Result<Cospan, ProductError> {
+        let height = self
+            .faces
+            .iter()
+            .position(|placed| placed.face == block[0])
+            .ok_or(ProductError::Interchange)?;
+        let source: DiagramN = cell.source().try_into().unwrap();
+        let target: DiagramN = cell.target().try_into().unwrap();
+        let offset = self.faces[height].position - positions(&source)[0];
+
+        let step = self
+            .slice()?
+            .identity()
+            .attach(cell, Boundary::Target, &[height, offset])?;
+        let faces = faces
+            .into_iter()
+            .zip(positions(&target))
+            .map(|(face, position)| face.at(offset + position));
+        self.faces.splice(height..height + block.len(), faces);
+
+        Ok(step.cospans()[0].clone())
+    }
+}
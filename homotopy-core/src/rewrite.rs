@@ -1,5 +1,4 @@
 use std::{
-    cell::RefCell,
     cmp::Ordering,
     convert::{From, Into},
     fmt,
@@ -7,10 +6,9 @@ use std::{
     ops::Range,
 };
 
-use hashconsing::{HConsed, HConsign, HashConsign};
+use hashconsing::{HConsed, HashConsign};
 use homotopy_common::hash::{FastHashMap, FastHashSet};
 use itertools::Either;
-use once_cell::unsync::OnceCell;
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use thiserror::Error;
 
@@ -19,15 +17,13 @@ use crate::{
         BoundaryPath, DimensionError, Generator, Label, LabelIdentifications, Orientation,
         RegularHeight, SingularHeight, WithDirection,
     },
+    parallel::{factories, OnceCell},
     Boundary, Diagram, Diagram0, DiagramN, Direction, Height,
 };
 
-thread_local! {
-    static REWRITE_FACTORY: RefCell<HConsign<RewriteInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
-
-    static CONE_FACTORY: RefCell<HConsign<ConeInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
+factories! {
+    static REWRITE_FACTORY: RewriteInternal;
+    static CONE_FACTORY: ConeInternal;
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
@@ -363,16 +359,43 @@ impl Rewrite0 {
                         )
                     })
                 };
+                // Unlabelled rewrites, such as those into homotopies, still factor through the
+                // labelled cospan of the suspended target.
+                let regular_label = |height, boundary| {
+                    label(height).or_else(|| {
+                        (target.generator != s && target.generator != t).then(|| {
+                            Label::new(
+                                BoundaryPath(boundary, target.generator.dimension),
+                                std::iter::once(vec![]).collect(),
+                            )
+                        })
+                    })
+                };
                 let (regular_slices, singular_slices) = if s == t && source.generator == s {
                     (
-                        vec![Self::new(s, target.suspended(), label(Regular(0))).into()],
+                        vec![Self::new(
+                            s,
+                            target.suspended(),
+                            regular_label(Regular(0), Boundary::Source),
+                        )
+                        .into()],
                         vec![],
                     )
                 } else {
                     (
                         vec![
-                            Self::new(s, target.suspended(), label(Regular(0))).into(),
-                            Self::new(t, target.suspended(), label(Regular(1))).into(),
+                            Self::new(
+                                s,
+                                target.suspended(),
+                                regular_label(Regular(0), Boundary::Source),
+                            )
+                            .into(),
+                            Self::new(
+                                t,
+                                target.suspended(),
+                                regular_label(Regular(1), Boundary::Target),
+                            )
+                            .into(),
                         ],
                         vec![
                             Self::new(source.suspended(), target.suspended(), label(Singular(0)))
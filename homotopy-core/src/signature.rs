@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     diagram::{globularity, NewDiagramError},
+    parallel::MaybeSync,
     Diagram, Diagram0, DiagramN, Generator,
 };
 
@@ -44,7 +45,7 @@ pub trait GeneratorInfo {
     fn invertibility(&self) -> Invertibility;
 }
 
-pub trait Signature {
+pub trait Signature: MaybeSync {
     type Info: GeneratorInfo;
     fn generators(&self) -> impl Iterator<Item = Generator>;
     fn generator_info(&self, g: Generator) -> Option<&Self::Info>;
@@ -0,0 +1,464 @@
+use homotopy_common::hash::FastHashMap;
+use thiserror::Error;
+
+use crate::{
+    common::{Boundary, Generator, Height},
+    diagram::AttachmentError,
+    rewrite::Cone,
+    signature::{GeneratorInfo, Invertibility, Signature},
+    typecheck::{typecheck, Mode, TypeError},
+    Cospan, Diagram, DiagramN, Rewrite, RewriteN,
+};
+
+#[derive(Debug, Error)]
+pub enum SubstitutionError {
+    #[error("generator to substitute is not in the signature")]
+    UnknownGenerator,
+
+    #[error("definition does not have the same boundary as the generator")]
+    Boundary,
+
+    #[error("occurrence of the generator is not isolated")]
+    NotIsolated,
+
+    #[error("generator is involved in a homotopy or in a higher cell which can not be retyped")]
+    Involved,
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+
+    #[error("result of the substitution is ill-typed: {0}")]
+    IllTyped(#[from] TypeError),
+}
+
+impl Diagram {
+    /// Replaces every occurrence of a generator by a definition with the same boundary, and
+    /// typechecks the result.
+    ///
+    /// Occurrences are replaced in every slice of the diagram, and inverse occurrences are
+    /// replaced by the inverse of the definition. Occurrences in the boundary of a higher cell
+    /// are replaced too, and the cell is applied with the type given to it by [`retype`], which
+    /// the result is typechecked against. This requires that every occurrence is an isolated
+    /// application of the generator which is carried along unchanged by the rewrites of the
+    /// higher dimensions, or absorbed by a cell which can be retyped: an occurrence which is
+    /// contracted together with other cells cannot be substituted.
+    pub fn substitute(
+        &self,
+        generator: Generator,
+        definition: &DiagramN,
+        signature: &impl Signature,
+    ) -> Result<Self, SubstitutionError> {
+        let mut substitution = Substitution::in_signature(generator, definition, signature)?;
+        let result = substitution.diagram(self)?;
+
+        typecheck(
+            &result,
+            &substitution.retyped(signature),
+            Mode::default(),
+            true,
+        )?;
+        Ok(result)
+    }
+}
+
+/// The types of the higher cells of a signature whose boundary mentions a generator, with a
+/// definition substituted for the generator in their boundary.
+///
+/// Cells are retyped from the lowest dimension up, so a cell may mention the generator through
+/// the boundary of another retyped cell. Cells whose boundary can not be substituted keep their
+/// type, and occurrences of the generator in their boundary cannot be substituted.
+pub fn retype(
+    generator: Generator,
+    definition: &DiagramN,
+    signature: &impl Signature,
+) -> Result<FastHashMap<Generator, DiagramN>, SubstitutionError> {
+    let substitution = Substitution::in_signature(generator, definition, signature)?;
+    Ok(substitution
+        .cells
+        .into_iter()
+        .map(|(cell, [(_, retyped), _])| (cell, retyped))
+        .collect())
+}
+
+/// The replacement of a generator by a definition, caching the substituted slices.
+pub(crate) struct Substitution {
+    generator: Generator,
+    /// The positive and negative applications of the generator, paired with their replacements.
+    applications: [(DiagramN, DiagramN); 2],
+    /// The positive and negative applications of the retyped cells, paired with the
+    /// applications of their new types.
+    cells: FastHashMap<Generator, [(DiagramN, DiagramN); 2]>,
+    mentions: FastHashMap<Diagram, bool>,
+    diagrams: FastHashMap<Diagram, Diagram>,
+    rewrites: FastHashMap<(Rewrite, Diagram), Rewrite>,
+}
+
+/// A signature in which some cells have been retyped.
+struct Retyped(FastHashMap<Generator, RetypedInfo>);
+
+struct RetypedInfo(Diagram, Invertibility);
+
+impl GeneratorInfo for RetypedInfo {
+    fn diagram(&self) -> &Diagram {
+        &self.0
+    }
+
+    fn invertibility(&self) -> Invertibility {
+        self.1
+    }
+}
+
+impl Signature for Retyped {
+    type Info = RetypedInfo;
+
+    fn generators(&self) -> impl Iterator<Item = Generator> {
+        self.0.keys().copied()
+    }
+
+    fn generator_info(&self, g: Generator) -> Option<&RetypedInfo> {
+        self.0.get(&g)
+    }
+}
+
+impl Substitution {
+    /// The substitution of a definition for a generator with the given diagram, assuming that
+    /// their boundaries agree.
+    pub(crate) fn new(generator: Generator, diagram: &DiagramN, definition: &DiagramN) -> Self {
+        Self {
+            generator,
+            applications: [
+                (diagram.clone(), definition.clone()),
+                (diagram.inverse(), definition.inverse()),
+            ],
+            cells: FastHashMap::default(),
+            mentions: FastHashMap::default(),
+            diagrams: FastHashMap::default(),
+            rewrites: FastHashMap::default(),
+        }
+    }
+
+    /// The substitution of a definition for a generator of a signature, which retypes the higher
+    /// cells of the signature whose boundary mentions the generator.
+    fn in_signature(
+        generator: Generator,
+        definition: &DiagramN,
+        signature: &impl Signature,
+    ) -> Result<Self, SubstitutionError> {
+        let Diagram::DiagramN(diagram) = signature
+            .generator_info(generator)
+            .ok_or(SubstitutionError::UnknownGenerator)?
+            .diagram()
+        else {
+            return Err(SubstitutionError::Boundary);
+        };
+
+        if definition.dimension() != diagram.dimension()
+            || definition.source() != diagram.source()
+            || definition.target() != diagram.target()
+        {
+            return Err(SubstitutionError::Boundary);
+        }
+
+        let mut substitution = Self::new(generator, diagram, definition);
+        let mut cells: Vec<_> = signature
+            .generators()
+            .filter(|cell| cell.dimension > generator.dimension)
+            .collect();
+        cells.sort_by_key(|cell| (cell.dimension, cell.id));
+        for cell in cells {
+            if let Some(Diagram::DiagramN(diagram)) =
+                signature.generator_info(cell).map(GeneratorInfo::diagram)
+            {
+                substitution.retype(cell, diagram);
+            }
+        }
+        Ok(substitution)
+    }
+
+    /// Substitutes in the boundary of a higher cell, if it mentions the generator and the
+    /// substitution succeeds.
+    fn retype(&mut self, cell: Generator, diagram: &DiagramN) {
+        if !self.mentions(&diagram.source()) && !self.mentions(&diagram.target()) {
+            return;
+        }
+
+        let (Ok(source), Ok(target)) = (
+            self.diagram(&diagram.source()),
+            self.diagram(&diagram.target()),
+        ) else {
+            return;
+        };
+        if let Ok(retyped) = DiagramN::from_generator(cell, source, target) {
+            let inverse = retyped.inverse();
+            self.cells.insert(
+                cell,
+                [(diagram.clone(), retyped), (diagram.inverse(), inverse)],
+            );
+        }
+    }
+
+    /// The signature with the new types of the retyped cells.
+    fn retyped(&self, signature: &impl Signature) -> Retyped {
+        Retyped(
+            signature
+                .generators()
+                .filter_map(|generator| {
+                    let info = signature.generator_info(generator)?;
+                    let diagram = match self.cells.get(&generator) {
+                        Some([(_, retyped), _]) => retyped.clone().into(),
+                        None => info.diagram().clone(),
+                    };
+                    Some((generator, RetypedInfo(diagram, info.invertibility())))
+                })
+                .collect(),
+        )
+    }
+
+    fn dimension(&self) -> usize {
+        self.generator.dimension
+    }
+
+    fn mentions(&mut self, diagram: &Diagram) -> bool {
+        let generator = self.generator;
+        *self
+            .mentions
+            .entry(diagram.clone())
+            .or_insert_with(|| diagram.generators().contains_key(&generator))
+    }
+
+    pub(crate) fn diagram(&mut self, diagram: &Diagram) -> Result<Diagram, SubstitutionError> {
+        if diagram.dimension() < self.dimension() {
+            return Ok(diagram.clone());
+        }
+
+        if let Some(result) = self.diagrams.get(diagram) {
+            return Ok(result.clone());
+        }
+
+        let result = match diagram {
+            _ if !self.mentions(diagram) => diagram.clone(),
+            Diagram::Diagram0(_) => unreachable!(),
+            Diagram::DiagramN(d) if d.dimension() == self.dimension() => self.expand(d)?.into(),
+            Diagram::DiagramN(d) => {
+                // Above the dimension of the generator, substitution preserves the number of
+                // heights, so it suffices to substitute inside the rewrites, except for the
+                // applications of retyped cells, which are attached anew.
+                let source = self.diagram(&d.source())?;
+                let slices: Vec<_> = d.slices().collect();
+                let cospans = d
+                    .cospans()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, cospan)| {
+                        let regular = &slices[2 * i];
+                        let singular = &slices[2 * i + 1];
+                        let cell = singular.max_generator().generator;
+                        if cell.dimension == d.dimension() && self.cells.contains_key(&cell) {
+                            return self.reattach(cell, regular, cospan);
+                        }
+                        Ok(Cospan {
+                            forward: self.rewrite(&cospan.forward, regular, singular)?,
+                            backward: self.rewrite(
+                                &cospan.backward,
+                                &slices[2 * i + 2],
+                                singular,
+                            )?,
+                        })
+                    })
+                    .collect::<Result<_, SubstitutionError>>()?;
+                DiagramN::new(source, cospans).into()
+            }
+        };
+
+        self.diagrams.insert(diagram.clone(), result.clone());
+        Ok(result)
+    }
+
+    /// Replaces the applications of the generator in a diagram of the same dimension.
+    fn expand(&mut self, diagram: &DiagramN) -> Result<DiagramN, SubstitutionError> {
+        let mut cospans = vec![];
+        for ((regular, singular), cospan) in diagram
+            .regular_slices()
+            .zip(diagram.singular_slices())
+            .zip(diagram.cospans())
+        {
+            if !self.mentions(&singular) {
+                cospans.push(cospan.clone());
+                continue;
+            }
+
+            let (definition, embedding) = find_application(&self.applications, &regular, cospan)
+                .ok_or(SubstitutionError::NotIsolated)?;
+            let replacement =
+                regular
+                    .identity()
+                    .attach(definition, Boundary::Target, &embedding)?;
+            cospans.extend(replacement.cospans().iter().cloned());
+        }
+
+        Ok(DiagramN::new(diagram.source(), cospans))
+    }
+
+    /// Replaces an application of a retyped cell, given by a cospan over a regular slice, by an
+    /// application of its new type.
+    fn reattach(
+        &mut self,
+        cell: Generator,
+        regular: &Diagram,
+        cospan: &Cospan,
+    ) -> Result<Cospan, SubstitutionError> {
+        let applications = self.cells[&cell].clone();
+        let (retyped, embedding) = find_application(&applications, regular, cospan)
+            .ok_or(SubstitutionError::NotIsolated)?;
+        let embedding = self.embedding(regular, &embedding);
+        let attached =
+            self.diagram(regular)?
+                .identity()
+                .attach(retyped, Boundary::Target, &embedding)?;
+        Ok(attached.cospans()[0].clone())
+    }
+
+    /// Moves an embedding into a diagram to the corresponding embedding into its substitution,
+    /// past the heights that the definitions take up in the dimension of the generator.
+    fn embedding(&mut self, diagram: &Diagram, embedding: &[usize]) -> Vec<usize> {
+        let mut embedding = embedding.to_vec();
+        let mut slice = diagram.clone();
+        for height in &mut embedding {
+            let Diagram::DiagramN(d) = slice else {
+                break;
+            };
+            if d.dimension() == self.dimension() {
+                let occurrences = d
+                    .singular_slices()
+                    .take(*height)
+                    .filter(|singular| self.mentions(singular))
+                    .count();
+                *height = *height - occurrences + occurrences * self.applications[0].1.size();
+                break;
+            }
+            slice = d.slice(Height::Regular(*height)).unwrap();
+        }
+        embedding
+    }
+
+    pub(crate) fn rewrite(
+        &mut self,
+        rewrite: &Rewrite,
+        source: &Diagram,
+        target: &Diagram,
+    ) -> Result<Rewrite, SubstitutionError> {
+        if rewrite.dimension() < self.dimension()
+            || (!self.mentions(source) && !self.mentions(target))
+        {
+            return Ok(rewrite.clone());
+        }
+
+        let key = (rewrite.clone(), source.clone());
+        if let Some(result) = self.rewrites.get(&key) {
+            return Ok(result.clone());
+        }
+
+        let (Rewrite::RewriteN(r), Diagram::DiagramN(s), Diagram::DiagramN(t)) =
+            (rewrite, source, target)
+        else {
+            unreachable!()
+        };
+        let source_slices: Vec<_> = s.singular_slices().collect();
+        let target_slices: Vec<_> = t.singular_slices().collect();
+
+        let cones = if r.dimension() == self.dimension() {
+            // The cones must leave the applications of the generator alone, and are shifted past
+            // the heights that the definitions take up.
+            let sizes: Vec<_> = source_slices
+                .iter()
+                .map(|slice| {
+                    if self.mentions(slice) {
+                        self.applications[0].1.size()
+                    } else {
+                        1
+                    }
+                })
+                .collect();
+
+            r.cones()
+                .iter()
+                .zip(r.targets())
+                .map(|(cone, height)| {
+                    if self.mentions(&target_slices[height])
+                        || source_slices[cone.index..cone.index + cone.len()]
+                            .iter()
+                            .any(|slice| self.mentions(slice))
+                    {
+                        return Err(SubstitutionError::Involved);
+                    }
+                    Ok(Cone {
+                        index: sizes[..cone.index].iter().sum(),
+                        internal: cone.internal.clone(),
+                    })
+                })
+                .collect::<Result<_, _>>()?
+        } else {
+            let Diagram::DiagramN(s_substituted) = self.diagram(source)? else {
+                unreachable!()
+            };
+            let Diagram::DiagramN(t_substituted) = self.diagram(target)? else {
+                unreachable!()
+            };
+            let regular_slices: Vec<_> = s.regular_slices().collect();
+
+            r.cones()
+                .iter()
+                .zip(r.targets())
+                .map(|(cone, height)| {
+                    let apex = &target_slices[height];
+                    let regular = cone
+                        .regular_slices()
+                        .iter()
+                        .enumerate()
+                        .map(|(j, slice)| {
+                            self.rewrite(slice, &regular_slices[cone.index + j], apex)
+                        })
+                        .collect::<Result<_, _>>()?;
+                    let singular = cone
+                        .singular_slices()
+                        .iter()
+                        .enumerate()
+                        .map(|(j, slice)| self.rewrite(slice, &source_slices[cone.index + j], apex))
+                        .collect::<Result<_, _>>()?;
+                    Ok(Cone::new(
+                        cone.index,
+                        s_substituted.cospans()[cone.index..cone.index + cone.len()].to_vec(),
+                        t_substituted.cospans()[height].clone(),
+                        regular,
+                        singular,
+                    ))
+                })
+                .collect::<Result<_, SubstitutionError>>()?
+        };
+
+        let result: Rewrite = RewriteN::new(r.dimension(), cones).into();
+        self.rewrites.insert(key, result.clone());
+        Ok(result)
+    }
+}
+
+/// An application of one of the given diagrams in a regular slice which is exactly the given
+/// cospan, together with the replacement of the application and its embedding.
+fn find_application<'a>(
+    applications: &'a [(DiagramN, DiagramN); 2],
+    regular: &Diagram,
+    cospan: &Cospan,
+) -> Option<(&'a DiagramN, Vec<usize>)> {
+    applications.iter().find_map(|(application, replacement)| {
+        regular
+            .embeddings(&application.source())
+            .find(|embedding| {
+                regular
+                    .clone()
+                    .identity()
+                    .attach(application, Boundary::Target, embedding)
+                    .is_ok_and(|d| d.cospans() == [cospan.clone()])
+            })
+            .map(|embedding| (replacement, embedding))
+    })
+}
@@ -0,0 +1,545 @@
+//! Topological invariants of the strata of a diagram.
+//!
+//! The points of the fully exploded scaffold of a diagram form a poset, in which the points
+//! labelled by a generator are below the points of the lower-dimensional strata on their
+//! boundary. The closure of the stratum of a generator is triangulated by the order complex of
+//! the points above it, so its invariants can be computed by simplicial homology.
+//!
+//! The strata of a diagram extend to the boundary of the cube it lives in, and are closed off
+//! there; a surface that meets the boundary of the diagram therefore has boundary.
+//!
+//! The triangulation lists every maximal chain of points, of which there can be exponentially
+//! many in the size of the diagram, so its size is bounded by [`MAX_CHAINS`].
+
+use std::{collections::BTreeSet, fmt};
+
+use homotopy_common::{
+    hash::{FastHashMap, FastHashSet},
+    idx::IdxVec,
+};
+use im::HashSet;
+use itertools::Itertools;
+use petgraph::{
+    graph::NodeIndex,
+    unionfind::UnionFind,
+    visit::{Dfs, EdgeRef},
+    Direction::{Incoming, Outgoing},
+};
+use thiserror::Error;
+
+use crate::{
+    common::{Generator, Height},
+    diagram::Diagram,
+    manifold::{stratum, Complex, Simplex},
+    scaffold::Scaffold,
+};
+
+/// The most maximal chains of points in the closure of a stratum whose invariants are computed.
+pub const MAX_CHAINS: usize = 100_000;
+
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum TopologyError {
+    #[error("the closure of a stratum has more than {} maximal chains", MAX_CHAINS)]
+    Chains,
+    #[error("the homology of a stratum has coefficients too large to compute")]
+    Overflow,
+}
+
+/// An integral homology group, given by its rank and torsion coefficients.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Homology {
+    pub rank: usize,
+    pub torsion: Vec<u64>,
+}
+
+impl fmt::Display for Homology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let free = match self.rank {
+            0 => None,
+            1 => Some("Z".to_owned()),
+            rank => Some(format!("Z^{rank}")),
+        };
+        let summands = free
+            .into_iter()
+            .chain(self.torsion.iter().map(|n| format!("Z/{n}")))
+            .collect::<Vec<_>>();
+        if summands.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", summands.join(" ⊕ "))
+        }
+    }
+}
+
+/// Topological invariants of the closure of a stratum.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Invariants {
+    /// The dimension of the closure of the stratum.
+    pub dimension: usize,
+    pub euler_characteristic: isize,
+    /// Whether the top-dimensional simplices can be coherently oriented.
+    pub orientable: bool,
+    pub boundary_components: usize,
+    /// The Betti numbers with coefficients in Z/2, starting in degree zero.
+    pub betti_numbers_mod_2: Vec<usize>,
+    /// The integral homology groups, starting in degree zero.
+    pub homology: Vec<Homology>,
+}
+
+impl Invariants {
+    fn new(facets: FastHashSet<Simplex>) -> Result<Self, TopologyError> {
+        let dimension = facets
+            .iter()
+            .map(|facet| facet.len() - 1)
+            .max()
+            .unwrap_or_default();
+
+        // Index the faces of each dimension.
+        let mut faces: Vec<FastHashMap<Vec<NodeIndex>, usize>> =
+            vec![FastHashMap::default(); dimension + 1];
+        for facet in &facets {
+            for face in facet.iter().copied().powerset().skip(1) {
+                let faces = &mut faces[face.len() - 1];
+                let index = faces.len();
+                faces.entry(face).or_insert(index);
+            }
+        }
+
+        let euler_characteristic = faces
+            .iter()
+            .enumerate()
+            .map(|(k, faces)| if k % 2 == 0 { 1 } else { -1 } * faces.len() as isize)
+            .sum();
+
+        // The boundary map from k-faces to (k - 1)-faces, by columns, for k from 1 to dimension.
+        let boundaries: Vec<Vec<Vec<(usize, i64)>>> = (1..=dimension)
+            .map(|k| {
+                let mut columns = vec![vec![]; faces[k].len()];
+                for (face, &j) in &faces[k] {
+                    for i in 0..face.len() {
+                        let mut ridge = face.clone();
+                        ridge.remove(i);
+                        let sign = if i % 2 == 0 { 1 } else { -1 };
+                        columns[j].push((faces[k - 1][&ridge], sign));
+                    }
+                }
+                columns
+            })
+            .collect();
+
+        let ranks_mod_2: Vec<usize> = boundaries.iter().map(|b| rank_mod_2(b)).collect();
+        let factors: Vec<Vec<u64>> = boundaries
+            .into_iter()
+            .map(invariant_factors)
+            .collect::<Option<_>>()
+            .ok_or(TopologyError::Overflow)?;
+
+        let betti_numbers_mod_2 = (0..=dimension)
+            .map(|k| {
+                let incoming = if k == 0 { 0 } else { ranks_mod_2[k - 1] };
+                let outgoing = ranks_mod_2.get(k).copied().unwrap_or_default();
+                faces[k].len() - incoming - outgoing
+            })
+            .collect();
+        let homology = (0..=dimension)
+            .map(|k| {
+                let incoming = if k == 0 { 0 } else { factors[k - 1].len() };
+                let outgoing = factors.get(k).map(Vec::as_slice).unwrap_or_default();
+                Homology {
+                    rank: faces[k].len() - incoming - outgoing.len(),
+                    torsion: outgoing.iter().copied().filter(|&n| n > 1).collect(),
+                }
+            })
+            .collect();
+
+        // The boundary consists of the ridges of exactly one top-dimensional facet.
+        let mut ridges: FastHashMap<Vec<NodeIndex>, usize> = FastHashMap::default();
+        for facet in facets.iter().filter(|facet| facet.len() == dimension + 1) {
+            for ridge in facet.iter().copied().combinations(dimension) {
+                *ridges.entry(ridge).or_default() += 1;
+            }
+        }
+        let boundary: Vec<Vec<NodeIndex>> = ridges
+            .into_iter()
+            .filter_map(|(ridge, count)| (dimension > 0 && count == 1).then_some(ridge))
+            .collect();
+        let vertices: Vec<NodeIndex> = boundary
+            .iter()
+            .flatten()
+            .copied()
+            .unique()
+            .sorted()
+            .collect();
+        let mut union_find = UnionFind::new(vertices.len());
+        for ridge in &boundary {
+            for (u, v) in ridge.iter().tuple_windows() {
+                union_find.union(
+                    vertices.binary_search(u).unwrap(),
+                    vertices.binary_search(v).unwrap(),
+                );
+            }
+        }
+        let boundary_components = union_find.into_labeling().into_iter().unique().count();
+
+        let complex = Complex {
+            facets: facets.into_iter().collect::<HashSet<_>>(),
+        };
+
+        Ok(Self {
+            dimension,
+            euler_characteristic,
+            orientable: complex.is_orientable(dimension),
+            boundary_components,
+            betti_numbers_mod_2,
+            homology,
+        })
+    }
+}
+
+impl fmt::Display for Invariants {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dimension {}, Euler characteristic {}, {}, {} boundary components, homology {}",
+            self.dimension,
+            self.euler_characteristic,
+            if self.orientable {
+                "orientable"
+            } else {
+                "non-orientable"
+            },
+            self.boundary_components,
+            self.homology.iter().join(", ")
+        )
+    }
+}
+
+/// Computes the invariants of the closure of the stratum of each generator appearing in a
+/// diagram, ordered by dimension.
+pub fn stratum_invariants(
+    diagram: &Diagram,
+) -> Result<Vec<(Generator, Invariants)>, TopologyError> {
+    let scaffold: Scaffold<Vec<Height>> = diagram.clone().fully_explode();
+
+    let mut strata: Vec<(Generator, Vec<NodeIndex>)> = vec![];
+    for n in scaffold.node_indices() {
+        let generator = scaffold[n].diagram.max_generator().generator;
+        match strata.iter_mut().find(|(g, _)| *g == generator) {
+            Some((_, nodes)) => nodes.push(n),
+            None => strata.push((generator, vec![n])),
+        }
+    }
+    strata.sort_by_key(|(g, _)| (g.dimension, g.id));
+
+    strata
+        .into_iter()
+        .map(|(generator, nodes)| Ok((generator, Invariants::new(closure(&scaffold, nodes)?)?)))
+        .collect()
+}
+
+/// The maximal chains of points above the given points in the fully exploded scaffold.
+fn closure(
+    scaffold: &Scaffold<Vec<Height>>,
+    nodes: Vec<NodeIndex>,
+) -> Result<FastHashSet<Simplex>, TopologyError> {
+    let mut included: IdxVec<NodeIndex, bool> = IdxVec::splat(false, scaffold.node_count());
+    let mut dfs = Dfs::empty(scaffold);
+    dfs.stack = nodes;
+    while let Some(n) = dfs.next(scaffold) {
+        included[n] = true;
+    }
+
+    // Every edge of the scaffold goes up one stratum, so paths are maximal chains.
+    let mut chains: IdxVec<NodeIndex, Vec<Vec<NodeIndex>>> =
+        IdxVec::splat(vec![], scaffold.node_count());
+    let mut facets = FastHashSet::default();
+    let mut size = 0;
+    for n in scaffold
+        .node_indices()
+        .filter(|&n| included[n])
+        .sorted_by_cached_key(|&n| stratum(&scaffold[n].key))
+    {
+        let sources = scaffold
+            .edges_directed(n, Incoming)
+            .map(|e| e.source())
+            .filter(|&s| included[s])
+            .unique()
+            .collect::<Vec<_>>();
+        size += sources
+            .iter()
+            .map(|&s| chains[s].len())
+            .sum::<usize>()
+            .max(1);
+        if size > MAX_CHAINS {
+            return Err(TopologyError::Chains);
+        }
+        let chains_to_n: Vec<Vec<NodeIndex>> = if sources.is_empty() {
+            vec![vec![n]]
+        } else {
+            sources
+                .iter()
+                .flat_map(|&s| chains[s].iter())
+                .map(|chain| [chain.as_slice(), &[n]].concat())
+                .collect()
+        };
+        if scaffold.edges_directed(n, Outgoing).next().is_none() {
+            facets.extend(
+                chains_to_n
+                    .iter()
+                    .map(|chain| chain.iter().copied().collect()),
+            );
+        }
+        chains[n] = chains_to_n;
+    }
+    Ok(facets)
+}
+
+/// The rank of a matrix over Z/2, given by the row indices of the odd entries of its columns.
+fn rank_mod_2(columns: &[Vec<(usize, i64)>]) -> usize {
+    let mut pivots: FastHashMap<usize, BTreeSet<usize>> = FastHashMap::default();
+    for column in columns {
+        let mut column: BTreeSet<usize> = column
+            .iter()
+            .filter_map(|&(i, a)| (a % 2 != 0).then_some(i))
+            .collect();
+        while let Some(&low) = column.last() {
+            match pivots.get(&low) {
+                Some(pivot) => column = &column ^ pivot,
+                None => {
+                    pivots.insert(low, column);
+                    break;
+                }
+            }
+        }
+    }
+    pivots.len()
+}
+
+/// The non-zero invariant factors of an integral matrix, given by its columns, or `None` if an
+/// entry overflows during the elimination.
+///
+/// Unit entries are eliminated sparsely first, since boundary matrices rarely have anything
+/// else; the remaining matrix is put into Smith normal form densely.
+fn invariant_factors(columns: Vec<Vec<(usize, i64)>>) -> Option<Vec<u64>> {
+    let mut columns: Vec<FastHashMap<usize, i64>> = columns
+        .into_iter()
+        .map(|column| column.into_iter().collect())
+        .collect();
+    let row_count = columns
+        .iter()
+        .flat_map(FastHashMap::keys)
+        .max()
+        .map_or(0, |i| i + 1);
+    let mut rows: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); row_count];
+    for (j, column) in columns.iter().enumerate() {
+        for &i in column.keys() {
+            rows[i].insert(j);
+        }
+    }
+
+    let mut factors = vec![];
+    let mut progress = true;
+    while progress {
+        progress = false;
+        for j in 0..columns.len() {
+            let Some((i, a)) = columns[j]
+                .iter()
+                .filter(|(_, a)| a.abs() == 1)
+                .map(|(&i, &a)| (i, a))
+                .min_by_key(|&(i, _)| (rows[i].len(), i))
+            else {
+                continue;
+            };
+            // Clear row i using column j, then drop both.
+            let pivot = std::mem::take(&mut columns[j]);
+            for k in std::mem::take(&mut rows[i]) {
+                if k == j {
+                    continue;
+                }
+                let b = columns[k].remove(&i).unwrap_or_default().checked_mul(a)?;
+                for (&l, &c) in &pivot {
+                    if l == i {
+                        continue;
+                    }
+                    let entry = columns[k].entry(l).or_default();
+                    *entry = entry.checked_sub(b.checked_mul(c)?)?;
+                    if *entry == 0 {
+                        columns[k].remove(&l);
+                        rows[l].remove(&k);
+                    } else {
+                        rows[l].insert(k);
+                    }
+                }
+            }
+            for &l in pivot.keys() {
+                rows[l].remove(&j);
+            }
+            factors.push(1);
+            progress = true;
+        }
+    }
+
+    let remaining_rows: Vec<usize> = (0..row_count).filter(|&i| !rows[i].is_empty()).collect();
+    let remaining_columns: Vec<usize> = (0..columns.len())
+        .filter(|&j| !columns[j].is_empty())
+        .collect();
+    let matrix: Vec<Vec<i64>> = remaining_rows
+        .iter()
+        .map(|i| {
+            remaining_columns
+                .iter()
+                .map(|&j| columns[j].get(i).copied().unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    factors.extend(smith_normal_form(matrix)?);
+    Some(factors)
+}
+
+/// The non-zero diagonal entries of the Smith normal form of a dense integral matrix, or `None`
+/// if an entry overflows.
+fn smith_normal_form(mut matrix: Vec<Vec<i64>>) -> Option<Vec<u64>> {
+    let rows = matrix.len();
+    let cols = matrix.first().map_or(0, Vec::len);
+    let mut factors = vec![];
+    for t in 0..rows.min(cols) {
+        // Move the smallest non-zero entry to the pivot.
+        let Some((i, j)) = (t..rows)
+            .cartesian_product(t..cols)
+            .filter(|&(i, j)| matrix[i][j] != 0)
+            .min_by_key(|&(i, j)| matrix[i][j].unsigned_abs())
+        else {
+            break;
+        };
+        matrix.swap(t, i);
+        for row in &mut matrix {
+            row.swap(t, j);
+        }
+
+        loop {
+            let p = matrix[t][t];
+            let (pivot_rows, rest) = matrix.split_at_mut(t + 1);
+            for row in rest {
+                let q = row[t].checked_div(p)?;
+                for (a, b) in row[t..].iter_mut().zip(&pivot_rows[t][t..]) {
+                    *a = a.checked_sub(q.checked_mul(*b)?)?;
+                }
+            }
+            for j in t + 1..cols {
+                let q = matrix[t][j].checked_div(p)?;
+                for row in &mut matrix[t..] {
+                    row[j] = row[j].checked_sub(q.checked_mul(row[t])?)?;
+                }
+            }
+
+            // A remainder in the pivot row or column becomes the new, smaller pivot.
+            if let Some(i) = (t + 1..rows).find(|&i| matrix[i][t] != 0) {
+                matrix.swap(t, i);
+                continue;
+            }
+            if let Some(j) = (t + 1..cols).find(|&j| matrix[t][j] != 0) {
+                for row in &mut matrix {
+                    row.swap(t, j);
+                }
+                continue;
+            }
+
+            // The pivot must divide every remaining entry.
+            match (t + 1..rows)
+                .cartesian_product(t + 1..cols)
+                .find(|&(i, j)| matrix[i][j].checked_rem(p) != Some(0))
+            {
+                Some((i, _)) => {
+                    let row = matrix[i].clone();
+                    for (a, b) in matrix[t][t..].iter_mut().zip(&row[t..]) {
+                        *a = a.checked_add(*b)?;
+                    }
+                }
+                None => break,
+            }
+        }
+        factors.push(matrix[t][t].unsigned_abs());
+    }
+    Some(factors)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invariants(facets: &[&[usize]]) -> Invariants {
+        Invariants::new(
+            facets
+                .iter()
+                .map(|facet| facet.iter().copied().map(NodeIndex::new).collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn homology(ranks: &[usize], torsion: &[&[u64]]) -> Vec<Homology> {
+        ranks
+            .iter()
+            .zip(torsion)
+            .map(|(&rank, torsion)| Homology {
+                rank,
+                torsion: torsion.to_vec(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sphere() {
+        let sphere = invariants(&[&[0, 1, 2], &[0, 1, 3], &[0, 2, 3], &[1, 2, 3]]);
+        assert_eq!(sphere.euler_characteristic, 2);
+        assert!(sphere.orientable);
+        assert_eq!(sphere.boundary_components, 0);
+        assert_eq!(sphere.betti_numbers_mod_2, vec![1, 0, 1]);
+        assert_eq!(sphere.homology, homology(&[1, 0, 1], &[&[], &[], &[]]));
+    }
+
+    #[test]
+    fn mobius_strip() {
+        let mobius = invariants(&[&[0, 1, 2], &[1, 2, 3], &[2, 3, 4], &[3, 4, 0], &[4, 0, 1]]);
+        assert_eq!(mobius.euler_characteristic, 0);
+        assert!(!mobius.orientable);
+        assert_eq!(mobius.boundary_components, 1);
+        assert_eq!(mobius.homology, homology(&[1, 1, 0], &[&[], &[], &[]]));
+    }
+
+    #[test]
+    fn projective_plane() {
+        // The six-vertex triangulation of the real projective plane.
+        let rp2 = invariants(&[
+            &[0, 1, 2],
+            &[0, 2, 3],
+            &[0, 3, 4],
+            &[0, 4, 5],
+            &[0, 5, 1],
+            &[1, 2, 4],
+            &[2, 3, 5],
+            &[3, 4, 1],
+            &[4, 5, 2],
+            &[5, 1, 3],
+        ]);
+        assert_eq!(rp2.euler_characteristic, 1);
+        assert!(!rp2.orientable);
+        assert_eq!(rp2.boundary_components, 0);
+        assert_eq!(rp2.betti_numbers_mod_2, vec![1, 1, 1]);
+        assert_eq!(rp2.homology, homology(&[1, 0, 0], &[&[], &[2], &[]]));
+    }
+
+    #[test]
+    fn two_arcs() {
+        let arcs = invariants(&[&[0, 1], &[1, 2], &[3, 4]]);
+        assert_eq!(arcs.euler_characteristic, 2);
+        assert_eq!(arcs.boundary_components, 4);
+        assert_eq!(arcs.homology, homology(&[2, 0], &[&[], &[]]));
+    }
+
+    #[test]
+    fn overflow() {
+        let matrix = vec![vec![i64::MAX, 1], vec![1, i64::MAX]];
+        assert_eq!(smith_normal_form(matrix), None);
+    }
+}
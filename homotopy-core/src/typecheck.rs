@@ -1,6 +1,7 @@
 use std::{
     cell::RefCell,
     convert::{Into, TryInto},
+    fmt,
     rc::Rc,
 };
 
@@ -15,9 +16,10 @@ use thiserror::Error;
 
 use crate::{
     collapse::Collapsible,
-    common::{Generator, Height, Label, SingularHeight},
+    common::{Generator, Height, Label, RegularHeight, SingularHeight},
     diagram::{Diagram, DiagramN},
-    manifold::is_manifold,
+    manifold::{non_manifold_points, NonManifoldPoint},
+    parallel,
     rewrite::{Cone, Cospan, Rewrite, RewriteN},
     scaffold::{Explodable, Scaffold},
     signature::{GeneratorInfo, Invertibility, Signature},
@@ -34,11 +36,68 @@ pub enum TypeError {
     #[error("diagram is ill-typed")]
     IllTyped,
 
-    #[error("directed generator appears in non-positive orientation")]
-    Directed,
+    #[error("neighbourhood of generator {:?} does not match its type at {0}", .0.generator)]
+    Mismatch(Location),
 
-    #[error("dualisable generator exhibits illegal non-manifold behaviour")]
-    Dualisable,
+    #[error("directed generator {:?} appears in non-positive orientation at {0}", .0.generator)]
+    Directed(Location),
+
+    /// Carries the points of the singular slice of the neighbourhood at which it is not a
+    /// manifold.
+    #[error("dualisable generator {:?} exhibits illegal non-manifold behaviour at {0}", .0.generator)]
+    Dualisable(Location, Vec<NonManifoldPoint>),
+}
+
+impl TypeError {
+    /// The point at which the error was found, if it is known.
+    #[must_use]
+    pub const fn location(&self) -> Option<&Location> {
+        match self {
+            Self::Mismatch(location) | Self::Directed(location) | Self::Dualisable(location, _) => {
+                Some(location)
+            }
+            Self::UnknownGenerator(_) | Self::IllTyped => None,
+        }
+    }
+
+    pub fn location_mut(&mut self) -> Option<&mut Location> {
+        match self {
+            Self::Mismatch(location) | Self::Directed(location) | Self::Dualisable(location, _) => {
+                Some(location)
+            }
+            Self::UnknownGenerator(_) | Self::IllTyped => None,
+        }
+    }
+
+    /// Locates the error in a diagram of which the typechecked diagram is the given slice.
+    #[must_use]
+    pub fn in_slice(mut self, index: SliceIndex) -> Self {
+        if let Some(location) = self.location_mut() {
+            location.point.insert(0, index);
+        }
+        self
+    }
+}
+
+/// The point of a diagram at which typechecking failed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    /// The coordinates of the point in the typechecked diagram.
+    pub point: Vec<SliceIndex>,
+    /// The generator at the point.
+    pub generator: Generator,
+    /// The source of the neighbourhood of the point, which is compared against the source of
+    /// the generator.
+    pub source: Diagram,
+    /// The target of the neighbourhood of the point, which is compared against the target of
+    /// the generator.
+    pub target: Diagram,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.point.iter().join(" "))
+    }
 }
 
 thread_local! {
@@ -74,7 +133,23 @@ pub fn typecheck(
         return Err(TypeError::IllTyped);
     }
 
-    typecheck_worker(diagram, signature, mode, recursive)
+    let result = typecheck_worker(diagram, signature, mode, recursive);
+    parallel::on_every_thread(|| RESTRICT_CACHE.with_borrow_mut(FastHashMap::clear));
+    result
+}
+
+/// An independent check performed while typechecking a diagram.
+enum Check {
+    /// Typecheck a regular slice recursively.
+    Slice(RegularHeight, Diagram),
+    /// Check the neighbourhood of a point in the target of a cospan.
+    Target {
+        source: Diagram,
+        cospan: Cospan,
+        height: SingularHeight,
+        point: Point,
+        target: Diagram0,
+    },
 }
 
 fn typecheck_worker(
@@ -94,75 +169,208 @@ fn typecheck_worker(
         Diagram::DiagramN(d) => d,
     };
 
+    // The checks are independent, but they are listed in the order in which they would be
+    // performed sequentially so that the same error is reported either way.
+    let mut checks = vec![];
+
     if recursive {
-        typecheck_worker(&diagram.source(), signature, mode, recursive)?;
+        checks.push(Check::Slice(0, diagram.source()));
     }
 
-    for (cospan, (regular0, regular1)) in
-        std::iter::zip(diagram.cospans(), diagram.regular_slices().tuple_windows())
+    for (i, (cospan, (regular0, regular1))) in
+        std::iter::zip(diagram.cospans(), diagram.regular_slices().tuple_windows()).enumerate()
     {
         if recursive {
-            typecheck_worker(&regular1, signature, mode, recursive)?;
+            checks.push(Check::Slice(i + 1, regular1));
         }
 
-        let target_embeddings = target_points(&[cospan.forward.clone(), cospan.backward.clone()])
-            .into_iter()
-            .map(|(point, target)| (Embedding::from_point(&point), target));
-
-        for (target_embedding, target) in target_embeddings {
+        for (point, target) in target_points(&[cospan.forward.clone(), cospan.backward.clone()]) {
             if mode.generator.is_some_and(|g| target.generator != g) {
                 continue;
             }
-            let info = signature
-                .generator_info(target.generator)
-                .ok_or(TypeError::UnknownGenerator(target.generator))?;
-
-            if mode.directed
-                && info.invertibility() == Invertibility::Directed
-                && target.orientation != Orientation::Positive
-            {
-                return Err(TypeError::Directed);
-            }
+            checks.push(Check::Target {
+                source: regular0.clone(),
+                cospan: cospan.clone(),
+                height: i,
+                point,
+                target,
+            });
+        }
+    }
 
-            let source = restrict_diagram(&regular0, &target_embedding.preimage(&cospan.forward));
+    let dimension = diagram.dimension();
+    match parallel::find_map_first(checks, |check| match check {
+        Check::Slice(height, slice) => typecheck_worker(&slice, signature, mode, recursive)
+            .err()
+            .map(|error| error.in_slice(Height::Regular(height).into())),
+        Check::Target {
+            source,
+            cospan,
+            height,
+            point,
+            target,
+        } => typecheck_target(dimension, &source, &cospan, &point, target, signature, mode)
+            .err()
+            .map(|error| error.in_slice(Height::Singular(height).into())),
+    }) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
 
-            let forward = restrict_rewrite(&cospan.forward, &target_embedding);
-            let backward = restrict_rewrite(&cospan.backward, &target_embedding);
-            let restricted = DiagramN::new(source, vec![Cospan { forward, backward }]);
+/// Checks the neighbourhood of a point in the target of a cospan. Errors are located in the
+/// singular slice of the cospan.
+fn typecheck_target(
+    dimension: usize,
+    source: &Diagram,
+    cospan: &Cospan,
+    point: &[SingularHeight],
+    target: Diagram0,
+    signature: &impl Signature,
+    mode: Mode,
+) -> Result<(), TypeError> {
+    let info = signature
+        .generator_info(target.generator)
+        .ok_or(TypeError::UnknownGenerator(target.generator))?;
+
+    let target_embedding = Embedding::from_point(point);
+    let source = restrict_diagram(source, &target_embedding.preimage(&cospan.forward));
+
+    let forward = restrict_rewrite(&cospan.forward, &target_embedding);
+    let backward = restrict_rewrite(&cospan.backward, &target_embedding);
+    let restricted = DiagramN::new(source, vec![Cospan { forward, backward }]);
+
+    let location = || Location {
+        point: point
+            .iter()
+            .map(|&height| Height::Singular(height).into())
+            .collect(),
+        generator: target.generator,
+        source: restricted.source(),
+        target: restricted.target(),
+    };
 
-            if let Invertibility::Dualisable(k) = info.invertibility() {
-                if mode.dualisable
-                    && diagram.dimension() > target.generator.dimension + k + 1
-                    && !is_manifold(restricted.slice(Height::Singular(0)).unwrap())
-                {
-                    return Err(TypeError::Dualisable);
-                }
-            }
+    if mode.directed
+        && info.invertibility() == Invertibility::Directed
+        && target.orientation != Orientation::Positive
+    {
+        return Err(TypeError::Directed(location()));
+    }
 
-            if mode.simplices
-                && collapse_simplicies(restricted) != collapse_simplicies(info.diagram().clone())
-            {
-                return Err(TypeError::IllTyped);
+    if let Invertibility::Dualisable(k) = info.invertibility() {
+        if mode.dualisable && dimension > target.generator.dimension + k + 1 {
+            let points = non_manifold_points(restricted.slice(Height::Singular(0)).unwrap());
+            if !points.is_empty() {
+                return Err(TypeError::Dualisable(location(), points));
             }
         }
     }
 
-    RESTRICT_CACHE.with_borrow_mut(FastHashMap::clear);
+    if mode.simplices
+        && collapse_simplicies(restricted.clone()) != collapse_simplicies(info.diagram().clone())
+    {
+        return Err(TypeError::Mismatch(location()));
+    }
 
     Ok(())
 }
 
+/// Typechecks a cospan over a diagram. Errors are found in the target of the cospan, or in its
+/// singular slice if they concern the cospan itself, and located at a preimage of that point in
+/// the source of the cospan.
 pub fn typecheck_cospan(
     source: Diagram,
     cospan: Cospan,
     signature: &impl Signature,
 ) -> Result<(), TypeError> {
-    let diagram = DiagramN::new(source, vec![cospan]);
+    let diagram = DiagramN::new(source, vec![cospan.clone()]);
 
-    typecheck(&diagram.target(), signature, Mode::default(), false)?;
+    typecheck(&diagram.target(), signature, Mode::default(), false)
+        .map_err(|error| locate_in_source(error, &cospan, true))?;
 
-    // should never return `IllTyped` but may return `NonManifold`
+    // should never return `Mismatch` but may return `Dualisable`
     typecheck(&diagram.into(), signature, Mode::default(), false)
+        .map_err(|error| locate_in_source(error, &cospan, false))
+}
+
+/// Moves the location of an error found at a point of the diagram of a cospan, or of its target
+/// if `in_target` is set, to a preimage of that point in the source of the cospan. The error is
+/// left where it was found if the point can not be pulled back.
+fn locate_in_source(mut error: TypeError, cospan: &Cospan, in_target: bool) -> TypeError {
+    let Some(location) = error.location_mut() else {
+        return error;
+    };
+    if let Some(preimage) = source_point(&location.point, location.generator, cospan, in_target) {
+        location.point = preimage.into_iter().map(Into::into).collect();
+    }
+    error
+}
+
+fn source_point(
+    point: &[SliceIndex],
+    generator: Generator,
+    cospan: &Cospan,
+    in_target: bool,
+) -> Option<Vec<Height>> {
+    // A point of the diagram of the cospan starts with the height of its singular slice.
+    let point = if in_target {
+        point
+    } else {
+        point.split_first()?.1
+    };
+    let mut point: Point = point
+        .iter()
+        .map(|index| match index {
+            SliceIndex::Interior(Height::Singular(height)) => Some(*height),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    if in_target {
+        point = point_image(&cospan.backward, &point)?;
+    }
+    // Prefer a preimage at which the generator of the error appears.
+    point_preimage(&cospan.forward, &point, Some(generator))
+        .or_else(|| point_preimage(&cospan.forward, &point, None))
+}
+
+/// The image of a point in the source of a rewrite.
+fn point_image(rewrite: &Rewrite, point: &[SingularHeight]) -> Option<Point> {
+    let Some((&height, rest)) = point.split_first() else {
+        return Some(vec![]);
+    };
+    let rewrite: &RewriteN = rewrite.try_into().ok()?;
+    let mut image = point_image(&rewrite.slice(height), rest)?;
+    image.insert(0, rewrite.singular_image(height));
+    Some(image)
+}
+
+/// A preimage of a point in the target of a rewrite at which `generator` appears, if it is given.
+/// The preimage lies in a regular slice of the source if the rewrite inserts the singular slice
+/// of the point, and then any point of that slice will do.
+fn point_preimage(
+    rewrite: &Rewrite,
+    point: &[SingularHeight],
+    generator: Option<Generator>,
+) -> Option<Vec<Height>> {
+    let Some((&height, rest)) = point.split_first() else {
+        let rewrite: &Rewrite0 = rewrite.try_into().ok()?;
+        let source = rewrite.source().map(|source| source.generator);
+        return (generator.is_none() || source.is_none() || source == generator).then(Vec::new);
+    };
+    let rewrite: &RewriteN = rewrite.try_into().ok()?;
+    let preimage = rewrite.singular_preimage(height);
+    if preimage.is_empty() {
+        let cone = rewrite.cone_over_target(height).left()?;
+        let mut result = point_preimage(&cone.target().forward, rest, None)?;
+        result.insert(0, Height::Regular(rewrite.regular_image(height)));
+        Some(result)
+    } else {
+        preimage.into_iter().find_map(|source_height| {
+            let mut result = point_preimage(&rewrite.slice(source_height), rest, generator)?;
+            result.insert(0, Height::Singular(source_height));
+            Some(result)
+        })
+    }
 }
 
 fn target_points(rewrites: &[Rewrite]) -> Vec<(Point, Diagram0)> {
@@ -0,0 +1,59 @@
+use homotopy_core::{signature::SignatureBuilder, Boundary, DiagramN};
+use pretty_assertions::assert_eq;
+
+mod common;
+use common::assert_well_typed;
+
+// Invertible 2-cells f => g => h between wires from x to y, and a wire k from w to x.
+fn setup() -> (SignatureBuilder, [DiagramN; 2], DiagramN) {
+    let mut sig = SignatureBuilder::default();
+    let [w, x, y] = [(); 3].map(|()| sig.add_zero());
+    let [f, g, h] = [(); 3].map(|()| sig.add(x, y).unwrap());
+    let alpha = sig.add(f, g.clone()).unwrap();
+    let beta = sig.add(g, h).unwrap();
+    let k = sig.add(w, x).unwrap();
+    (sig, [alpha, beta], k)
+}
+
+#[test]
+fn composites_are_bubbled() {
+    let (sig, [alpha, beta], _) = setup();
+    let composite = alpha.attach(&beta, Boundary::Target, &[]).unwrap();
+
+    let bubble = composite.bubble(&sig).unwrap();
+    assert_eq!(bubble.size(), 2);
+    assert_eq!(bubble.source(), composite.source().identity().into());
+    assert_eq!(
+        bubble.target(),
+        composite
+            .attach(&composite.inverse(), Boundary::Target, &[])
+            .unwrap()
+            .into()
+    );
+    assert_well_typed(&bubble, &sig);
+}
+
+#[test]
+fn pairs_are_introduced_and_cancelled_locally() {
+    let (sig, [alpha, beta], k) = setup();
+    let host = alpha
+        .attach(&beta, Boundary::Target, &[])
+        .unwrap()
+        .attach(&k, Boundary::Source, &[])
+        .unwrap();
+
+    let introduced = host.bubble_at(&beta, &[1, 1], &sig).unwrap();
+    let expected = [&beta, &beta.inverse(), &beta]
+        .into_iter()
+        .fold(alpha.clone(), |composite, cell| {
+            composite.attach(cell, Boundary::Target, &[]).unwrap()
+        })
+        .attach(&k, Boundary::Source, &[])
+        .unwrap();
+    assert_eq!(introduced.target(), expected.clone().into());
+    assert_well_typed(&introduced, &sig);
+
+    let cancelled = expected.cancel_at(&beta, &[1, 1], &sig).unwrap();
+    assert_eq!(cancelled.target(), host.into());
+    assert_well_typed(&cancelled, &sig);
+}
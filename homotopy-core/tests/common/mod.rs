@@ -0,0 +1,9 @@
+use homotopy_core::{
+    signature::Signature,
+    typecheck::{typecheck, Mode},
+    DiagramN,
+};
+
+pub fn assert_well_typed(diagram: &DiagramN, sig: &impl Signature) {
+    typecheck(&diagram.clone().into(), sig, Mode::default(), true).unwrap();
+}
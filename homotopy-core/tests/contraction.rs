@@ -1,3 +1,5 @@
+use std::error::Error;
+
 use homotopy_core::{
     examples,
     signature::{GeneratorInfo, Signature, SignatureBuilder},
@@ -391,7 +393,7 @@ fn bubble_pop_2d() {
 // | |
 #[test]
 #[allow(clippy::many_single_char_names)]
-fn bead_tensor_bead_and_inverse() -> anyhow::Result<()> {
+fn bead_tensor_bead_and_inverse() -> Result<(), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let f = sig.add(x, x)?;
@@ -438,7 +440,7 @@ fn bead_tensor_bead_and_inverse() -> anyhow::Result<()> {
 //  m  e     / \
 // / \ |
 #[test]
-fn monoid_tensor_bead_and_inverse() -> anyhow::Result<()> {
+fn monoid_tensor_bead_and_inverse() -> Result<(), Box<dyn Error>> {
     let (mut sig, monoid) = examples::two_monoid();
     let f = DiagramN::try_from(
         sig.generator_info(Generator::new(1, 1))
@@ -518,7 +520,7 @@ fn monoid_tensor_bead_and_inverse() -> anyhow::Result<()> {
 // | / \
 // (this diagram is 3D)
 #[test]
-fn cap_braid() -> anyhow::Result<()> {
+fn cap_braid() -> Result<(), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let s = sig.add(x.identity(), x.identity())?;
@@ -604,7 +606,7 @@ fn cap_braid() -> anyhow::Result<()> {
 // / \
 // does not contract
 #[test]
-fn no_reidemeister_1() -> anyhow::Result<()> {
+fn no_reidemeister_1() -> Result<(), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let s = sig.add(x.identity(), x.identity())?;
@@ -671,7 +673,7 @@ fn no_reidemeister_1() -> anyhow::Result<()> {
 // |
 // etc.
 #[test]
-fn bead_through_cap_cup() -> anyhow::Result<()> {
+fn bead_through_cap_cup() -> Result<(), Box<dyn Error>> {
     let (_, cap) = examples::cap();
     let (mut sig, cup) = examples::cup();
     let f = DiagramN::try_from(
@@ -818,7 +820,8 @@ fn bead_through_cap_cup() -> anyhow::Result<()> {
 }
 
 #[test]
-fn three_dimensional_scalar_across_wire_preserves_label_neighbourhood() -> anyhow::Result<()> {
+fn three_dimensional_scalar_across_wire_preserves_label_neighbourhood() -> Result<(), Box<dyn Error>>
+{
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let two = sig.add(x.identity(), x.identity())?;
@@ -877,7 +880,7 @@ fn three_dimensional_scalar_across_wire_preserves_label_neighbourhood() -> anyho
 // / \ / \
 // contracts (horizontally)
 #[test]
-fn contract_two_caps() -> anyhow::Result<()> {
+fn contract_two_caps() -> Result<(), Box<dyn Error>> {
     let (mut sig, cap) = examples::cap();
     let x = sig
         .generator_info(Generator::new(0, 0))
@@ -963,7 +966,7 @@ fn contract_two_caps() -> anyhow::Result<()> {
 //  -
 //  does not contract (3D hourglass)
 #[test]
-fn hourglass_no_absorb() -> anyhow::Result<()> {
+fn hourglass_no_absorb() -> Result<(), Box<dyn Error>> {
     let (sig, endomorphism) = endomorphism_on_ring()?;
     let hourglass = endomorphism
         .contract(
@@ -1018,7 +1021,7 @@ fn hourglass_no_absorb() -> anyhow::Result<()> {
 // | |   /|
 // etc. (in 3D)
 #[test]
-fn counit_braid() -> anyhow::Result<()> {
+fn counit_braid() -> Result<(), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let s = sig.add(x.identity(), x.identity())?;
@@ -1077,7 +1080,7 @@ fn counit_braid() -> anyhow::Result<()> {
 // | |   | |   | |   | |   | |
 // contracts
 #[test]
-fn bead_interchanger() -> anyhow::Result<()> {
+fn bead_interchanger() -> Result<(), Box<dyn Error>> {
     let (sig, beads) = examples::two_beads();
 
     let contracted = beads.clone().identity().contract(
@@ -1146,7 +1149,7 @@ fn bead_interchanger() -> anyhow::Result<()> {
 //  |   u       |
 //  |
 #[test]
-fn pull_through_braid() -> anyhow::Result<()> {
+fn pull_through_braid() -> Result<(), Box<dyn Error>> {
     let (mut sig, braid) = examples::crossing();
     let braid: DiagramN = braid
         .identity()
@@ -1219,7 +1222,7 @@ fn pull_through_braid() -> anyhow::Result<()> {
 //   -
 // don't contract
 #[test]
-fn no_bend_cup() -> anyhow::Result<()> {
+fn no_bend_cup() -> Result<(), Box<dyn Error>> {
     let (_sig, cup) = examples::cup();
     let (mut sig, e) = examples::bead_series(1);
     let cup_then_e = cup.attach(&e, Boundary::Target, &[])?;
@@ -1270,7 +1273,7 @@ fn no_bend_cup() -> anyhow::Result<()> {
 // | | |
 // to make simultaneous braids
 #[test]
-fn double_braid() -> anyhow::Result<()> {
+fn double_braid() -> Result<(), Box<dyn Error>> {
     let (sig, s) = examples::scalar();
     let three_wires = s
         .attach(&s, Boundary::Target, &[])?
@@ -1438,7 +1441,7 @@ fn double_braid() -> anyhow::Result<()> {
 // e | | ⤳ e  >
 // | | |   | | |
 #[test]
-fn braid_next_to_endomorphism() -> anyhow::Result<()> {
+fn braid_next_to_endomorphism() -> Result<(), Box<dyn Error>> {
     let (mut sig, s) = examples::scalar();
     let e = sig.add(s.clone(), s.clone())?;
     let e_wire_wire = e
@@ -1500,7 +1503,7 @@ fn braid_next_to_endomorphism() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn endomorphism_on_weak_unit() -> anyhow::Result<(impl Signature, DiagramN)> {
+fn endomorphism_on_weak_unit() -> Result<(impl Signature, DiagramN), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let weak_x = Diagram::from(x).weak_identity();
@@ -1508,7 +1511,7 @@ fn endomorphism_on_weak_unit() -> anyhow::Result<(impl Signature, DiagramN)> {
     Ok((sig, f))
 }
 
-fn counit_on_endomorphism() -> anyhow::Result<(impl Signature, DiagramN)> {
+fn counit_on_endomorphism() -> Result<(impl Signature, DiagramN), Box<dyn Error>> {
     let (mut sig, endomorphism) = examples::bead_series(1);
     let f = sig
         .generator_info(Generator::new(1, 1))
@@ -1519,7 +1522,7 @@ fn counit_on_endomorphism() -> anyhow::Result<(impl Signature, DiagramN)> {
     Ok((sig, counit))
 }
 
-fn counit_on_scalar() -> anyhow::Result<(impl Signature, DiagramN)> {
+fn counit_on_scalar() -> Result<(impl Signature, DiagramN), Box<dyn Error>> {
     let (mut sig, scalar) = examples::scalar();
     let x = sig
         .generator_info(Generator::new(0, 0))
@@ -1530,13 +1533,13 @@ fn counit_on_scalar() -> anyhow::Result<(impl Signature, DiagramN)> {
     Ok((sig, counit))
 }
 
-fn endomorphism_on_braid() -> anyhow::Result<(impl Signature, DiagramN)> {
+fn endomorphism_on_braid() -> Result<(impl Signature, DiagramN), Box<dyn Error>> {
     let (mut sig, braid) = examples::crossing();
     let endomorphism = sig.add(braid.clone(), braid)?;
     Ok((sig, endomorphism))
 }
 
-fn endomorphism_on_ring() -> anyhow::Result<(impl Signature, DiagramN)> {
+fn endomorphism_on_ring() -> Result<(impl Signature, DiagramN), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let f = sig.add(x, x)?;
@@ -1569,7 +1572,7 @@ fn endomorphism_on_ring() -> anyhow::Result<(impl Signature, DiagramN)> {
     Ok((sig, endomorphism))
 }
 
-fn endomorphism_on_algebraic_ring() -> anyhow::Result<(impl Signature, DiagramN)> {
+fn endomorphism_on_algebraic_ring() -> Result<(impl Signature, DiagramN), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let f = sig.add(x, x)?;
@@ -1580,7 +1583,7 @@ fn endomorphism_on_algebraic_ring() -> anyhow::Result<(impl Signature, DiagramN)
     Ok((sig, endomorphism))
 }
 
-fn endomorphism_on_half_algebraic_ring() -> anyhow::Result<(impl Signature, DiagramN)> {
+fn endomorphism_on_half_algebraic_ring() -> Result<(impl Signature, DiagramN), Box<dyn Error>> {
     let mut sig = SignatureBuilder::default();
     let x = sig.add_zero();
     let f = sig.add(x, x)?;
@@ -1604,7 +1607,7 @@ fn endomorphism_on_half_algebraic_ring() -> anyhow::Result<(impl Signature, Diag
 
 // ensure that the singular braid on two cups is not smoothed away
 #[test]
-fn singular_braid_on_two_cups() -> anyhow::Result<()> {
+fn singular_braid_on_two_cups() -> Result<(), Box<dyn Error>> {
     let (sig, two_cups) = examples::two_cups();
 
     let braid = two_cups.identity().identity().contract(
@@ -1653,7 +1656,7 @@ fn singular_braid_on_two_cups() -> anyhow::Result<()> {
 #[test_case(endomorphism_on_ring().expect("failed to create endomorphism on homotopy ring"))]
 #[test_case(endomorphism_on_algebraic_ring().expect("failed to create endomorphism on algebraic ring"))]
 #[test_case(endomorphism_on_half_algebraic_ring().expect("failed to create endomorphism on half algebraic ring"))]
-fn contract_with_weak_id((sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+fn contract_with_weak_id((sig, diagram): (impl Signature, DiagramN)) -> Result<(), Box<dyn Error>> {
     let pre_weak = diagram
         .source()
         .weak_identity()
@@ -1698,3 +1701,47 @@ fn contract_with_weak_id((sig, diagram): (impl Signature, DiagramN)) -> anyhow::
     );
     Ok(())
 }
+
+#[test]
+fn contractions_of_two_scalars() {
+    let (sig, scalar) = examples::scalar();
+    let two = scalar.attach(&scalar, Boundary::Target, &[]).unwrap();
+    let contract = |bias| {
+        two.clone()
+            .identity()
+            .contract(
+                Boundary::Target.into(),
+                &mut [],
+                0,
+                Direction::Forward,
+                1,
+                Some(bias),
+                &sig,
+            )
+            .unwrap()
+    };
+
+    let contractions = |bias, bound| {
+        two.clone().identity().contractions(
+            Boundary::Target.into(),
+            &[],
+            0,
+            Direction::Forward,
+            1,
+            bias,
+            &sig,
+            bound,
+        )
+    };
+    assert_eq!(
+        contractions(None, 8),
+        vec![
+            (Some(Bias::Lower), contract(Bias::Lower), vec![]),
+            (Some(Bias::Higher), contract(Bias::Higher), vec![]),
+        ]
+    );
+    assert_eq!(
+        contractions(Some(Bias::Higher), 1),
+        vec![(Some(Bias::Higher), contract(Bias::Higher), vec![])]
+    );
+}
@@ -1,7 +1,7 @@
 use homotopy_core::{
     examples,
     signature::{GeneratorInfo, Signature},
-    Boundary, Generator,
+    Boundary, Diagram, Generator,
 };
 use insta::assert_debug_snapshot;
 
@@ -38,3 +38,71 @@ fn monoid_with_unit_to_id() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn rewrite_all_occurrences() -> anyhow::Result<()> {
+    let (mut sig, f, [a, b, c]) = examples::bead_generators();
+    let rule = sig.add(a.clone(), c.clone())?;
+
+    let host = examples::beads(&f, &[(&a, 0), (&a, 0), (&b, 0), (&a, 0)]);
+    let witness = Diagram::from(host.clone()).rewrite_all(&rule)?;
+
+    assert_eq!(witness.size(), 3);
+    assert_eq!(witness.source(), host.into());
+    assert_eq!(
+        witness.target(),
+        examples::beads(&f, &[(&c, 0), (&c, 0), (&b, 0), (&c, 0)]).into()
+    );
+    witness.check(true).unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn rewrite_all_skips_overlapping_occurrences() -> anyhow::Result<()> {
+    let (mut sig, f, [a, _, c]) = examples::bead_generators();
+    let rule = sig.add(examples::beads(&f, &[(&a, 0), (&a, 0)]), c.clone())?;
+
+    let host: Diagram = examples::beads(&f, &[(&a, 0), (&a, 0), (&a, 0)]).into();
+    let witness = host.rewrite_all(&rule)?;
+
+    assert_eq!(witness.size(), 1);
+    assert_eq!(
+        witness.target(),
+        examples::beads(&f, &[(&c, 0), (&a, 0)]).into()
+    );
+    assert!(Diagram::from(c).rewrite_all(&rule).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn rewrite_all_skips_occurrences_overlapping_in_the_second_coordinate() -> anyhow::Result<()> {
+    let (mut sig, f, _) = examples::bead_generators();
+    let wires = |n: usize| {
+        (1..n).fold(f.clone(), |wires, _| {
+            wires.attach(&f, Boundary::Target, &[]).unwrap()
+        })
+    };
+    let d = sig.add(wires(2), wires(2))?;
+    let rule = sig.add(wires(2).identity(), d.clone())?;
+
+    // Occurrences at the same height on wires 0 and 1 share wire 1.
+    let witness = Diagram::from(wires(3).identity()).rewrite_all(&rule)?;
+    assert_eq!(witness.size(), 1);
+    assert_eq!(
+        witness.target(),
+        examples::beads(&wires(3), &[(&d, 0)]).into()
+    );
+
+    // Occurrences at the same height on wires 0 and 2 are disjoint.
+    let witness = Diagram::from(wires(4).identity()).rewrite_all(&rule)?;
+    assert_eq!(witness.size(), 2);
+    assert_eq!(
+        witness.target(),
+        examples::beads(&wires(4), &[(&d, 0), (&d, 2)]).into()
+    );
+    witness.check(true).unwrap();
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
+use std::error::Error;
+
 use homotopy_core::{
     examples,
+    expansion::ExpansionError,
     signature::{GeneratorInfo, Signature},
     Bias, Boundary, DiagramN, Direction, Generator, Height,
 };
@@ -44,7 +47,7 @@ fn matchsticks() {
 // |  / \       |  / \
 // | |   |      | |   |
 #[test]
-fn bead_with_half_braid() -> anyhow::Result<()> {
+fn bead_with_half_braid() -> Result<(), Box<dyn Error>> {
     let (mut sig, half_braid) = examples::half_braid();
     let s = DiagramN::try_from(
         sig.generator_info(Generator::new(1, 2))
@@ -162,7 +165,7 @@ fn bead_with_half_braid() -> anyhow::Result<()> {
 // |   |
 // should not create weak identity
 #[test]
-fn braid_smooth() -> anyhow::Result<()> {
+fn braid_smooth() -> Result<(), Box<dyn Error>> {
     let (sig, touching) = examples::touching();
     // make touching have only one singular height
     let touching = touching
@@ -205,7 +208,7 @@ fn braid_smooth() -> anyhow::Result<()> {
 // e
 // |
 #[test]
-fn bead_no_stretch() -> anyhow::Result<()> {
+fn bead_no_stretch() -> Result<(), Box<dyn Error>> {
     let (sig, e) = examples::bead_series(1);
     let f = DiagramN::try_from(
         sig.generator_info(Generator::new(1, 1))
@@ -277,3 +280,92 @@ fn bead_no_stretch() -> anyhow::Result<()> {
         .is_err());
     Ok(())
 }
+
+// expanding along an axis of a projection descends into the slice at the coordinates before it
+#[test]
+fn expand_along_axis() -> Result<(), Box<dyn Error>> {
+    use Height::Singular;
+
+    let (sig, touching) = examples::touching();
+    let touching = touching
+        .identity()
+        .contract(
+            Boundary::Target.into(),
+            &mut [],
+            0,
+            Direction::Forward,
+            1,
+            None,
+            &sig,
+        )?
+        .target();
+    let expanded = touching.clone().identity().expand(
+        Boundary::Target.into(),
+        &mut [Singular(0)],
+        [Singular(0), Singular(0)],
+        Direction::Backward,
+        &sig,
+    )?;
+    let expanded_along = touching.clone().identity().expand_along(
+        Boundary::Target.into(),
+        &mut [],
+        &[Singular(0), Singular(0), Singular(0)],
+        1,
+        Direction::Backward,
+        &sig,
+    )?;
+    assert_eq!(expanded_along, expanded);
+
+    assert!(matches!(
+        touching.identity().expand_along(
+            Boundary::Target.into(),
+            &mut [],
+            &[Singular(0), Singular(0)],
+            1,
+            Direction::Backward,
+            &sig,
+        ),
+        Err(ExpansionError::WrongLocation)
+    ));
+    Ok(())
+}
+
+#[test]
+fn expansions_start_with_the_expansion() -> Result<(), Box<dyn Error>> {
+    use Height::Singular;
+
+    let (sig, diagram) = examples::matchsticks();
+    let contracted = diagram
+        .identity()
+        .contract(
+            Boundary::Target.into(),
+            &mut [],
+            0,
+            Direction::Forward,
+            1,
+            Some(Bias::Lower),
+            &sig,
+        )?
+        .target();
+
+    let point = [Singular(0), Singular(1)];
+    let expanded = contracted.clone().identity().expand_along(
+        Boundary::Target.into(),
+        &mut [],
+        &point,
+        0,
+        Direction::Forward,
+        &sig,
+    )?;
+    let expansions = contracted.identity().expansions(
+        Boundary::Target.into(),
+        &[],
+        &point,
+        0,
+        Direction::Forward,
+        &sig,
+        8,
+    )?;
+    assert_eq!(expansions[0], (expanded, vec![]));
+    Ok(())
+}
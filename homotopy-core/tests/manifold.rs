@@ -0,0 +1,42 @@
+use homotopy_core::{
+    manifold::{is_manifold, non_manifold_points},
+    signature::SignatureBuilder,
+    Boundary, Height,
+};
+
+#[test]
+fn bead_is_manifold() {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let e = sig.add(f.clone(), f).unwrap();
+
+    assert!(is_manifold(e.clone().into()));
+    assert!(non_manifold_points(e.into()).is_empty());
+}
+
+#[test]
+fn monoid_link_is_described() {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let m = sig.add(ff, f).unwrap();
+
+    assert!(!is_manifold(m.clone().into()));
+    let points = non_manifold_points(m.into());
+    assert_eq!(points.len(), 1);
+
+    // The link of the vertex consists of its three wires.
+    let point = &points[0];
+    assert_eq!(point.point, vec![Height::Singular(0), Height::Singular(0)]);
+    assert_eq!(point.link.dimension, 0);
+    assert_eq!(point.link.face_counts, vec![3]);
+    assert!(point.link.disconnected);
+    assert!(!point.link.non_orientable);
+    assert!(!point.link.boundary);
+    assert_eq!(
+        point.to_string(),
+        "S0 S0: link is not a 0-sphere (face counts 3), disconnected"
+    );
+}
@@ -0,0 +1,145 @@
+use homotopy_common::hash::FastHashMap;
+use homotopy_core::{
+    examples,
+    morphism::{MorphismError, SignatureMorphism},
+    signature::SignatureBuilder,
+    Boundary, Diagram, Diagram0, DiagramN, Generator,
+};
+use pretty_assertions::assert_eq;
+
+struct Magma {
+    sig: SignatureBuilder,
+    wire: DiagramN,
+    multiplication: DiagramN,
+}
+
+// A magma in a signature whose generators start with the given number of extra points.
+fn magma(points: usize) -> Magma {
+    let mut sig = SignatureBuilder::default();
+    for _ in 0..points {
+        sig.add_zero();
+    }
+    let x = sig.add_zero();
+    let wire = sig.add(x, x).unwrap();
+    let source = wire.attach(&wire, Boundary::Target, &[]).unwrap();
+    let multiplication = sig.add(source, wire.clone()).unwrap();
+    Magma {
+        sig,
+        wire,
+        multiplication,
+    }
+}
+
+fn point(id: usize) -> Diagram {
+    Diagram0::from(Generator::new(id, 0)).into()
+}
+
+fn images(pairs: &[(usize, usize, Diagram)]) -> FastHashMap<Generator, Diagram> {
+    pairs
+        .iter()
+        .map(|(id, dimension, image)| (Generator::new(*id, *dimension), image.clone()))
+        .collect()
+}
+
+#[test]
+fn generators_are_renamed() {
+    let source = magma(0);
+    let target = magma(2);
+    let morphism = SignatureMorphism::new(
+        &source.sig,
+        &target.sig,
+        images(&[
+            (0, 0, point(2)),
+            (1, 1, target.wire.clone().into()),
+            (2, 2, target.multiplication.clone().into()),
+        ]),
+    )
+    .unwrap();
+
+    let diagram = examples::multiply_thrice(&source.wire, &source.multiplication);
+    let expected = examples::multiply_thrice(&target.wire, &target.multiplication);
+    assert_eq!(
+        morphism.apply(&diagram.clone().into()).unwrap(),
+        expected.clone().into()
+    );
+    assert_eq!(
+        morphism.apply(&diagram.clone().identity().into()).unwrap(),
+        expected.clone().identity().into()
+    );
+    assert_eq!(
+        morphism
+            .apply_rewrite(&diagram.cospans()[0].forward, &diagram.source())
+            .unwrap(),
+        expected.cospans()[0].forward
+    );
+}
+
+#[test]
+fn generators_are_substituted() {
+    let source = magma(0);
+    let mut target = magma(0);
+    let twist = target
+        .sig
+        .add(target.wire.clone(), target.wire.clone())
+        .unwrap();
+    let image = target
+        .multiplication
+        .attach(&twist, Boundary::Target, &[0])
+        .unwrap();
+    let morphism = SignatureMorphism::new(
+        &source.sig,
+        &target.sig,
+        images(&[
+            (0, 0, point(0)),
+            (1, 1, target.wire.clone().into()),
+            (2, 2, image.clone().into()),
+        ]),
+    )
+    .unwrap();
+
+    let diagram = examples::multiply_thrice(&source.wire, &source.multiplication);
+    let expected = examples::multiply_thrice(&target.wire, &image);
+    assert_eq!(morphism.apply(&diagram.into()).unwrap(), expected.into());
+}
+
+#[test]
+fn boundaries_are_checked() {
+    let source = magma(0);
+    let mut target = magma(0);
+    let twist = target
+        .sig
+        .add(target.wire.clone(), target.wire.clone())
+        .unwrap();
+
+    let error = SignatureMorphism::new(
+        &source.sig,
+        &target.sig,
+        images(&[
+            (0, 0, point(0)),
+            (1, 1, target.wire.clone().into()),
+            (2, 2, twist.into()),
+        ]),
+    )
+    .unwrap_err();
+    assert!(matches!(error, MorphismError::Boundary(g) if g == Generator::new(2, 2)));
+
+    let error = SignatureMorphism::new(
+        &source.sig,
+        &target.sig,
+        images(&[(0, 0, point(0)), (1, 1, target.wire.clone().into())]),
+    )
+    .unwrap_err();
+    assert!(matches!(error, MorphismError::Missing(g) if g == Generator::new(2, 2)));
+
+    let error = SignatureMorphism::new(
+        &source.sig,
+        &target.sig,
+        images(&[
+            (0, 0, point(0)),
+            (1, 1, target.wire.clone().into()),
+            (2, 2, target.wire.into()),
+        ]),
+    )
+    .unwrap_err();
+    assert!(matches!(error, MorphismError::Dimension(g) if g == Generator::new(2, 2)));
+}
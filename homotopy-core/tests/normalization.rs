@@ -0,0 +1,83 @@
+use homotopy_core::{
+    examples, normalization::are_isotopic, Boundary::Target, Cospan, Diagram, DiagramN, Rewrite,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn identity_levels() {
+    let (sig, scalar) = examples::scalar();
+    let identity = Cospan {
+        forward: Rewrite::identity(1),
+        backward: Rewrite::identity(1),
+    };
+    let padded = DiagramN::new(
+        scalar.source(),
+        vec![identity.clone(), scalar.cospans()[0].clone(), identity],
+    );
+
+    assert_eq!(padded.normalize(&sig).unwrap(), scalar);
+    assert!(are_isotopic(&padded.into(), &scalar.into(), &sig).unwrap());
+}
+
+#[test]
+fn interchange() {
+    let (sig, f, [a, b, _]) = examples::bead_generators();
+    let ff = f.attach(&f, Target, &[]).unwrap();
+
+    let ab = Diagram::from(examples::beads(&ff, &[(&a, 0), (&b, 1)]));
+    let ba = Diagram::from(examples::beads(&ff, &[(&b, 1), (&a, 0)]));
+
+    assert_ne!(ab, ba);
+    assert!(are_isotopic(&ab, &ba, &sig).unwrap());
+    assert_eq!(ab.normalize(&sig).unwrap().size(), Some(1));
+}
+
+#[test]
+fn gravity() {
+    let (sig, f, [a, b, c]) = examples::bead_generators();
+    let ff = f.attach(&f, Target, &[]).unwrap();
+
+    let abc = Diagram::from(examples::beads(&ff, &[(&a, 0), (&b, 1), (&c, 0)]));
+    let bac = Diagram::from(examples::beads(&ff, &[(&b, 1), (&a, 0), (&c, 0)]));
+    let acb = Diagram::from(examples::beads(&ff, &[(&a, 0), (&c, 0), (&b, 1)]));
+
+    let normal = abc.normalize(&sig).unwrap();
+    assert_eq!(normal.size(), Some(2));
+    assert_eq!(bac.normalize(&sig).unwrap(), normal);
+    assert_eq!(acb.normalize(&sig).unwrap(), normal);
+    assert_eq!(normal.normalize(&sig).unwrap(), normal);
+}
+
+#[test]
+fn not_isotopic() {
+    let (sig, f, [a, b, c]) = examples::bead_generators();
+    let ff = f.attach(&f, Target, &[]).unwrap();
+
+    let ac = Diagram::from(examples::beads(&ff, &[(&a, 0), (&c, 0)]));
+    let ca = Diagram::from(examples::beads(&ff, &[(&c, 0), (&a, 0)]));
+    let ab = Diagram::from(examples::beads(&ff, &[(&a, 0), (&b, 1)]));
+    let ba = Diagram::from(examples::beads(&ff, &[(&a, 1), (&b, 0)]));
+
+    assert!(!are_isotopic(&ac, &ca, &sig).unwrap());
+    assert!(!are_isotopic(&ab, &ba, &sig).unwrap());
+    assert!(!are_isotopic(&ab, &ff.identity().into(), &sig).unwrap());
+}
+
+#[test]
+fn examples_stay_well_formed() {
+    for (sig, diagram) in [
+        examples::two_beads(),
+        examples::three_beads(),
+        examples::stacks(),
+        examples::matchsticks(),
+        examples::snake(),
+        examples::bubble(),
+        examples::lips(),
+        examples::associator(),
+    ] {
+        let normal = diagram.normalize(&sig).unwrap();
+        normal.check(true).unwrap();
+        assert_eq!(normal.normalize(&sig).unwrap(), normal);
+        assert!(are_isotopic(&diagram.into(), &normal.into(), &sig).unwrap());
+    }
+}
@@ -0,0 +1,123 @@
+// Typechecking, collapse and contraction must give the same results with the `parallel` feature
+// as without it. CI runs these tests in both builds, and with the feature the results on the
+// thread pool are also compared against those on a single thread.
+use homotopy_core::{
+    examples,
+    signature::SignatureBuilder,
+    typecheck::{typecheck, Mode},
+    Boundary, Diagram, DiagramN, Direction, Generator, Height,
+};
+use pretty_assertions::assert_eq;
+
+#[cfg(feature = "parallel")]
+fn sequentially<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap()
+        .install(f)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn sequentially<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+fn examples() -> Vec<(SignatureBuilder, DiagramN)> {
+    vec![
+        examples::associator(),
+        examples::two_monoid(),
+        examples::two_scalars(),
+        examples::crossing(),
+        examples::three_beads(),
+        examples::stacks(),
+        examples::matchsticks(),
+        examples::snake(),
+        examples::lips(),
+        examples::pants_unit(),
+    ]
+}
+
+// The errors of a diagram using the generator `m : f ; f -> f` twice as if it were of type
+// `f -> f`, of which the lowest is reported.
+fn mistyped() -> (SignatureBuilder, Generator, DiagramN) {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let m = sig.add(ff, f.clone()).unwrap().max_generator().generator;
+    let cell = DiagramN::from_generator(m, f.clone(), f).unwrap();
+    let diagram = cell.attach(&cell, Boundary::Target, &[]).unwrap();
+    (sig, m, diagram)
+}
+
+#[test]
+fn typechecking_is_deterministic() {
+    for (sig, diagram) in examples() {
+        let diagram: Diagram = diagram.into();
+        let check = || typecheck(&diagram, &sig, Mode::default(), true).is_ok();
+        assert!(check());
+        assert!(sequentially(check));
+    }
+
+    let (sig, m, diagram) = mistyped();
+    let diagram: Diagram = diagram.identity().into();
+    let check = || {
+        typecheck(&diagram, &sig, Mode::default(), true)
+            .unwrap_err()
+            .location()
+            .cloned()
+            .unwrap()
+    };
+    let location = check();
+    assert_eq!(location.generator, m);
+    assert_eq!(
+        location.point,
+        vec![
+            Height::Regular(0).into(),
+            Height::Singular(0).into(),
+            Height::Singular(0).into()
+        ]
+    );
+    assert_eq!(sequentially(check), location);
+}
+
+#[test]
+fn collapse_is_deterministic() {
+    for dimension in 2..6 {
+        let (_, diagram) = examples::iterated_endomorphism(dimension);
+        let (_, sequential) = sequentially(|| examples::iterated_endomorphism(dimension));
+        assert_eq!(diagram, sequential);
+        assert_eq!(diagram.dimension(), dimension);
+    }
+}
+
+#[test]
+fn contraction_is_deterministic() {
+    let mut contracted = 0;
+    for (sig, diagram) in examples() {
+        let size = diagram.size();
+        let identity = diagram.identity();
+        for height in 0..size.saturating_sub(1) {
+            for direction in [Direction::Forward, Direction::Backward] {
+                let contract = || {
+                    identity
+                        .contract(
+                            Boundary::Target.into(),
+                            &mut [],
+                            height,
+                            direction,
+                            1,
+                            None,
+                            &sig,
+                        )
+                        .ok()
+                };
+                let result = contract();
+                assert_eq!(result, sequentially(contract));
+                contracted += usize::from(result.is_some());
+            }
+        }
+    }
+    assert!(contracted > 0);
+}
@@ -0,0 +1,81 @@
+use homotopy_core::{
+    examples,
+    pattern::{occurrences, occurrences_up_to_renaming, Occurrence},
+    Boundary::Target,
+    Diagram, DiagramN,
+    Height::Regular,
+};
+use pretty_assertions::assert_eq;
+
+// Two wires with a bead a on the left followed by a bead b on the right.
+fn ab() -> (DiagramN, DiagramN, DiagramN, DiagramN) {
+    let (_, f, [a, b, _]) = examples::bead_generators();
+    let ff = f.attach(&f, Target, &[]).unwrap();
+    let ab = examples::beads(&ff, &[(&a, 0), (&b, 1)]);
+
+    (f, a, b, ab)
+}
+
+fn occurrence(path: Vec<homotopy_core::Height>, embedding: Vec<usize>) -> Occurrence {
+    Occurrence {
+        path,
+        embedding,
+        renaming: vec![],
+    }
+}
+
+#[test]
+fn same_dimension() {
+    let (_, a, b, ab) = ab();
+
+    assert_eq!(
+        occurrences(&a.into(), &ab.clone().into()),
+        vec![occurrence(vec![], vec![0, 0])]
+    );
+    assert_eq!(
+        occurrences(&b.into(), &ab.into()),
+        vec![occurrence(vec![], vec![1, 1])]
+    );
+}
+
+#[test]
+fn lower_dimension() {
+    let (f, _, _, ab) = ab();
+
+    // Wires are reported in the first slice in which they appear.
+    assert_eq!(
+        occurrences(&f.into(), &ab.into()),
+        vec![
+            occurrence(vec![Regular(0)], vec![0]),
+            occurrence(vec![Regular(0)], vec![1]),
+            occurrence(vec![Regular(1)], vec![0]),
+            occurrence(vec![Regular(2)], vec![1]),
+        ]
+    );
+}
+
+#[test]
+fn renaming() {
+    let (_, a, b, ab) = ab();
+
+    let found = occurrences_up_to_renaming(&a.clone().into(), &ab.into());
+    assert_eq!(
+        found,
+        vec![
+            occurrence(vec![], vec![0, 0]),
+            Occurrence {
+                renaming: vec![(a.max_generator().generator, b.max_generator().generator)],
+                ..occurrence(vec![], vec![1, 1])
+            },
+        ]
+    );
+}
+
+#[test]
+fn no_occurrences() {
+    let (f, a, _, _) = ab();
+
+    let host: Diagram = f.identity().into();
+    assert!(occurrences(&a.clone().into(), &host).is_empty());
+    assert!(occurrences_up_to_renaming(&a.into(), &host).is_empty());
+}
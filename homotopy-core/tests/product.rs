@@ -0,0 +1,273 @@
+use homotopy_core::{
+    product::{Product, ProductError},
+    signature::SignatureBuilder,
+    Boundary, Diagram, Diagram0, DiagramN,
+};
+use pretty_assertions::assert_eq;
+
+mod common;
+use common::assert_well_typed;
+
+struct Left {
+    sig: SignatureBuilder,
+    // Points x, y, z, wires f, f' from x to y and h from y to z, and a 2-cell alpha from f to f'.
+    points: [Diagram0; 3],
+    fs: [DiagramN; 2],
+    h: DiagramN,
+    alpha: DiagramN,
+}
+
+struct Right {
+    sig: SignatureBuilder,
+    // Points u, v, w, wires g, g' from u to v and k from v to w, and a 2-cell beta from g to g'.
+    points: [Diagram0; 3],
+    gs: [DiagramN; 2],
+    k: DiagramN,
+    beta: DiagramN,
+}
+
+fn left() -> Left {
+    let mut sig = SignatureBuilder::default();
+    let points = [(); 3].map(|()| sig.add_zero());
+    let [x, y, z] = points;
+    let fs = [(); 2].map(|()| sig.add(x, y).unwrap());
+    let h = sig.add(y, z).unwrap();
+    let alpha = sig.add(fs[0].clone(), fs[1].clone()).unwrap();
+    Left {
+        sig,
+        points,
+        fs,
+        h,
+        alpha,
+    }
+}
+
+fn right() -> Right {
+    let Left {
+        sig,
+        points,
+        fs,
+        h,
+        alpha,
+    } = left();
+    Right {
+        sig,
+        points,
+        gs: fs,
+        k: h,
+        beta: alpha,
+    }
+}
+
+fn compose(diagrams: &[&DiagramN]) -> DiagramN {
+    diagrams[1..]
+        .iter()
+        .fold(diagrams[0].clone(), |composite, diagram| {
+            composite.attach(diagram, Boundary::Target, &[]).unwrap()
+        })
+}
+
+fn product(signature: &Product, left: impl Into<Diagram>, right: impl Into<Diagram>) -> DiagramN {
+    signature
+        .diagram(&left.into(), &right.into())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn products_of_wires_are_squares() {
+    let (left, right) = (left(), right());
+    let signature = Product::new(&left.sig, &right.sig).unwrap();
+    let ([x, y, _], [u, v, _]) = (left.points, right.points);
+    let (f, g) = (&left.fs[0], &right.gs[0]);
+
+    let square = f.product(g, &signature).unwrap();
+    assert_eq!(square.dimension(), 2);
+    assert_eq!(square.size(), 1);
+    assert_eq!(
+        square.source(),
+        compose(&[
+            &product(&signature, x, g.clone()),
+            &product(&signature, f.clone(), v)
+        ])
+        .into()
+    );
+    assert_eq!(
+        square.target(),
+        compose(&[
+            &product(&signature, f.clone(), u),
+            &product(&signature, y, g.clone())
+        ])
+        .into()
+    );
+    let cell = signature
+        .cell(f.max_generator().generator, g.max_generator().generator)
+        .unwrap();
+    assert_eq!(cell.dimension, 2);
+    assert_eq!(square, product(&signature, f.clone(), g.clone()));
+    assert_well_typed(&square, &signature);
+
+    let composite = compose(&[f, &left.h]);
+    let grid = composite
+        .product(&compose(&[g, &right.k]), &signature)
+        .unwrap();
+    assert_eq!(grid.size(), 4);
+    assert_well_typed(&grid, &signature);
+}
+
+#[test]
+fn products_with_points_are_copies() {
+    let (left, right) = (left(), right());
+    let signature = Product::new(&left.sig, &right.sig).unwrap();
+    let [x, ..] = left.points;
+
+    let copy = product(&signature, x, right.beta.clone());
+    assert_eq!(copy.dimension(), 2);
+    assert_eq!(
+        copy.source(),
+        product(&signature, x, right.gs[0].clone()).into()
+    );
+    assert_well_typed(&copy, &signature);
+}
+
+#[test]
+fn cells_are_moved_through_wires() {
+    let (left, right) = (left(), right());
+    let signature = Product::new(&left.sig, &right.sig).unwrap();
+    let [u, v, _] = right.points;
+    let g = &right.gs[0];
+
+    // The cell moves from above the wire to below it.
+    let cylinder = left.alpha.product(g, &signature).unwrap();
+    assert_eq!(cylinder.dimension(), 3);
+    assert_eq!(cylinder.size(), 1);
+    assert_eq!(
+        cylinder.source(),
+        product(&signature, left.fs[0].clone(), g.clone())
+            .attach(
+                &product(&signature, left.alpha.clone(), u),
+                Boundary::Target,
+                &[0]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_eq!(
+        cylinder.target(),
+        product(&signature, left.fs[1].clone(), g.clone())
+            .attach(
+                &product(&signature, left.alpha.clone(), v),
+                Boundary::Source,
+                &[1]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_well_typed(&cylinder, &signature);
+
+    // A cell beside another wire has to be interchanged with the square of that wire first.
+    let whiskered = left.alpha.attach(&left.h, Boundary::Target, &[]).unwrap();
+    let composite = compose(&[g, &right.k]);
+    let cylinder = whiskered.product(&composite, &signature).unwrap();
+    assert!(cylinder.size() > 2);
+    assert_eq!(
+        cylinder.source(),
+        product(&signature, whiskered.source(), composite.clone())
+            .attach(
+                &product(&signature, whiskered.clone(), u),
+                Boundary::Target,
+                &[0]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_eq!(
+        cylinder.target(),
+        product(&signature, whiskered.target(), composite.clone())
+            .attach(
+                &product(&signature, whiskered.clone(), right.points[2]),
+                Boundary::Source,
+                &[2]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_well_typed(&cylinder, &signature);
+}
+
+#[test]
+fn wires_are_moved_through_cells() {
+    let (left, right) = (left(), right());
+    let signature = Product::new(&left.sig, &right.sig).unwrap();
+    let [x, y, z] = left.points;
+    let f = &left.fs[0];
+
+    let cylinder = f.product(&right.beta, &signature).unwrap();
+    assert_eq!(cylinder.dimension(), 3);
+    assert_eq!(cylinder.size(), 1);
+    assert_eq!(
+        cylinder.source(),
+        product(&signature, f.clone(), right.gs[1].clone())
+            .attach(
+                &product(&signature, x, right.beta.clone()),
+                Boundary::Source,
+                &[0]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_eq!(
+        cylinder.target(),
+        product(&signature, f.clone(), right.gs[0].clone())
+            .attach(
+                &product(&signature, y, right.beta.clone()),
+                Boundary::Target,
+                &[1]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_well_typed(&cylinder, &signature);
+
+    let composite = compose(&[f, &left.h]);
+    let whiskered = right.beta.attach(&right.k, Boundary::Target, &[]).unwrap();
+    let cylinder = composite.product(&whiskered, &signature).unwrap();
+    assert!(cylinder.size() > 2);
+    assert_eq!(
+        cylinder.source(),
+        product(&signature, composite.clone(), whiskered.target())
+            .attach(
+                &product(&signature, x, whiskered.clone()),
+                Boundary::Source,
+                &[0]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_eq!(
+        cylinder.target(),
+        product(&signature, composite.clone(), whiskered.source())
+            .attach(
+                &product(&signature, z, whiskered.clone()),
+                Boundary::Target,
+                &[2]
+            )
+            .unwrap()
+            .into()
+    );
+    assert_well_typed(&cylinder, &signature);
+}
+
+#[test]
+fn higher_products_are_not_supported() {
+    let (left, right) = (left(), right());
+    let signature = Product::new(&left.sig, &right.sig).unwrap();
+
+    let cells = [&left.alpha, &right.beta].map(|cell| cell.max_generator().generator);
+    assert_eq!(signature.cell(cells[0], cells[1]), None);
+    assert!(matches!(
+        left.alpha.product(&right.beta, &signature),
+        Err(ProductError::Dimension(2, 2))
+    ));
+}
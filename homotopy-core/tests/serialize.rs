@@ -1,5 +1,10 @@
 use base64::prelude::{Engine, BASE64_STANDARD};
-use homotopy_core::{examples, serialize::Store, Diagram};
+use homotopy_core::{
+    examples,
+    json::{ImportError, Tables},
+    serialize::Store,
+    Diagram,
+};
 use insta::assert_debug_snapshot;
 
 #[test]
@@ -22,3 +27,60 @@ fn serialize_associator() {
 
     assert_eq!(Diagram::from(diagram), deserialized);
 }
+
+#[test]
+fn json_round_trip() {
+    for (_, diagram) in [
+        examples::associator(),
+        examples::crossing(),
+        examples::snake(),
+    ] {
+        let diagram = Diagram::from(diagram);
+
+        let (json, index) = {
+            let mut tables = Tables::default();
+            let index = tables.pack_diagram(&diagram);
+            (serde_json::to_string(&tables).unwrap(), index)
+        };
+
+        let mut tables: Tables = serde_json::from_str(&json).unwrap();
+        assert_eq!(tables.unpack_diagram(index).unwrap(), diagram);
+    }
+}
+
+#[test]
+fn json_invalid_index() {
+    let mut tables: Tables = serde_json::from_str(
+        r#"{
+            "diagrams": [{ "type": "DiagramN", "dimension": 1, "source": 1, "cospans": [] }],
+            "rewrites": [],
+            "cones": []
+        }"#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        tables.unpack_diagram(0),
+        Err(ImportError::Index("diagram", 1))
+    ));
+}
+
+#[test]
+fn json_wrong_dimension() {
+    let mut tables: Tables = serde_json::from_str(
+        r#"{
+            "diagrams": [
+                { "type": "Diagram0", "generator": { "id": 0, "dimension": 0 }, "orientation": "Positive" },
+                { "type": "DiagramN", "dimension": 2, "source": 0, "cospans": [] }
+            ],
+            "rewrites": [],
+            "cones": []
+        }"#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        tables.unpack_diagram(1),
+        Err(ImportError::Dimension("diagram", 0, 1))
+    ));
+}
@@ -0,0 +1,121 @@
+use homotopy_core::{
+    examples::stack_scalars as stack,
+    signature::SignatureBuilder,
+    substitution::{retype, SubstitutionError},
+    Bias, Boundary, Diagram, DiagramN, Direction, Generator,
+};
+use pretty_assertions::assert_eq;
+
+// Interchanges the scalars at the given height and the one above it.
+fn interchange(diagram: &DiagramN, height: usize, sig: &SignatureBuilder) -> DiagramN {
+    diagram
+        .clone()
+        .identity()
+        .contract(
+            Boundary::Target.into(),
+            &mut [],
+            height,
+            Direction::Forward,
+            1,
+            Some(Bias::Lower),
+            sig,
+        )
+        .unwrap()
+}
+
+// A signature with a scalar t defined as the scalar a followed by the scalar b.
+fn setup() -> (SignatureBuilder, Generator, DiagramN, [DiagramN; 4]) {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let [t, a, b, c] = [(); 4].map(|()| sig.add(x.identity(), x.identity()).unwrap());
+    let definition = stack(&[&a, &b]);
+    (sig, Generator::new(1, 2), definition, [t, a, b, c])
+}
+
+#[test]
+fn scalars_are_unfolded() {
+    let (sig, generator, definition, [t, a, b, c]) = setup();
+
+    let diagram: Diagram = stack(&[&t, &c, &t]).into();
+    let expected: Diagram = stack(&[&a, &b, &c, &a, &b]).into();
+    assert_eq!(
+        diagram.substitute(generator, &definition, &sig).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn inverses_are_unfolded() {
+    let (sig, generator, definition, [t, a, b, c]) = setup();
+
+    let diagram: Diagram = stack(&[&c, &t.inverse()]).into();
+    let expected: Diagram = stack(&[&c, &b.inverse(), &a.inverse()]).into();
+    assert_eq!(
+        diagram.substitute(generator, &definition, &sig).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn homotopies_are_carried_along() {
+    let (sig, generator, definition, [t, a, b, c]) = setup();
+
+    let diagram: Diagram = interchange(&stack(&[&t, &c, &a]), 1, &sig).into();
+    let expected: Diagram = interchange(&stack(&[&a, &b, &c, &a]), 2, &sig).into();
+    assert_eq!(
+        diagram.substitute(generator, &definition, &sig).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn boundaries_of_higher_cells_are_substituted() {
+    let (mut sig, generator, definition, [t, a, b, c]) = setup();
+    let tt = stack(&[&t, &t]);
+    let beta = sig.add(tt.clone(), c.clone()).unwrap();
+    let beta_generator = beta.max_generator().generator;
+
+    let retyped = retype(generator, &definition, &sig).unwrap();
+    let expected =
+        DiagramN::from_generator(beta_generator, stack(&[&a, &b, &a, &b]), c.clone()).unwrap();
+    assert_eq!(retyped.len(), 1);
+    assert_eq!(retyped[&beta_generator], expected);
+
+    // The cell is applied above another occurrence of the generator, which moves it up.
+    let diagram: Diagram = stack(&[&t, &t, &t])
+        .identity()
+        .attach(&beta, Boundary::Target, &[1, 0])
+        .unwrap()
+        .into();
+    let expected: Diagram = stack(&[&a, &b, &a, &b, &a, &b])
+        .identity()
+        .attach(&expected, Boundary::Target, &[2, 0])
+        .unwrap()
+        .into();
+    assert_eq!(
+        diagram.substitute(generator, &definition, &sig).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn involved_occurrences_are_rejected() {
+    let (sig, generator, definition, [t, _, _, c]) = setup();
+
+    let diagram: Diagram = interchange(&stack(&[&t, &c]), 0, &sig).into();
+    assert!(matches!(
+        diagram.substitute(generator, &definition, &sig),
+        Err(SubstitutionError::Involved)
+    ));
+}
+
+#[test]
+fn definition_must_have_the_same_boundary() {
+    let (sig, generator, _, [t, _, _, c]) = setup();
+
+    let diagram: Diagram = t.clone().into();
+    assert!(matches!(
+        diagram.substitute(generator, &c.identity(), &sig),
+        Err(SubstitutionError::Boundary)
+    ));
+}
@@ -0,0 +1,75 @@
+use homotopy_core::{
+    examples,
+    signature::SignatureBuilder,
+    topology::{stratum_invariants, Homology, Invariants},
+    Boundary, Generator,
+};
+
+fn invariants_of(diagram: impl Into<homotopy_core::Diagram>, generator: Generator) -> Invariants {
+    stratum_invariants(&diagram.into())
+        .unwrap()
+        .into_iter()
+        .find_map(|(g, invariants)| (g == generator).then_some(invariants))
+        .expect("generator does not appear in the diagram")
+}
+
+fn homology(ranks: &[usize]) -> Vec<Homology> {
+    ranks
+        .iter()
+        .map(|&rank| Homology {
+            rank,
+            torsion: vec![],
+        })
+        .collect()
+}
+
+#[test]
+fn wire_is_an_arc() {
+    let (_, diagram) = examples::two_endomorphism();
+    let f = Generator::new(1, 1);
+
+    let arc = invariants_of(diagram, f);
+    assert_eq!(arc.dimension, 1);
+    assert_eq!(arc.euler_characteristic, 1);
+    assert_eq!(arc.boundary_components, 2);
+    assert_eq!(arc.homology, homology(&[1, 0]));
+}
+
+#[test]
+fn bubble_is_a_circle() {
+    let (_, bubble) = examples::bubble();
+    let f = Generator::new(1, 1);
+
+    let circle = invariants_of(bubble, f);
+    assert_eq!(circle.dimension, 1);
+    assert_eq!(circle.euler_characteristic, 0);
+    assert!(circle.orientable);
+    assert_eq!(circle.boundary_components, 0);
+    assert_eq!(circle.betti_numbers_mod_2, vec![1, 1]);
+    assert_eq!(circle.homology, homology(&[1, 1]));
+}
+
+#[test]
+fn sphere() {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let cap = sig.add(ff.clone(), x.identity()).unwrap();
+    let cup = sig.add(x.identity(), ff).unwrap();
+    let circle = cup.attach(&cap, Boundary::Target, &[]).unwrap();
+    let birth = sig.add(x.identity().identity(), circle.clone()).unwrap();
+    let death = sig.add(circle, x.identity().identity()).unwrap();
+    let sphere = birth.attach(&death, Boundary::Target, &[]).unwrap();
+
+    let sphere = invariants_of(sphere, Generator::new(1, 1));
+    assert_eq!(sphere.dimension, 2);
+    assert_eq!(sphere.euler_characteristic, 2);
+    assert!(sphere.orientable);
+    assert_eq!(sphere.boundary_components, 0);
+    assert_eq!(sphere.homology, homology(&[1, 0, 1]));
+    assert_eq!(
+        sphere.to_string(),
+        "dimension 2, Euler characteristic 2, orientable, 0 boundary components, homology Z, 0, Z"
+    );
+}
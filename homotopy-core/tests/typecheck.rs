@@ -0,0 +1,81 @@
+use homotopy_core::{
+    contraction::ContractionError,
+    examples,
+    signature::SignatureBuilder,
+    typecheck::{typecheck, Mode, TypeError},
+    Boundary, Diagram, DiagramN, Direction, Generator, Height, SliceIndex,
+};
+
+// A diagram using the generator `m : f ; f -> f` as if it were of type `f -> f`.
+fn mistyped() -> (SignatureBuilder, Generator, DiagramN, DiagramN) {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let m = sig.add(ff, f.clone()).unwrap().max_generator().generator;
+    let diagram = DiagramN::from_generator(m, f.clone(), f.clone()).unwrap();
+    (sig, m, f, diagram)
+}
+
+#[test]
+fn mismatch_is_located() {
+    let (sig, m, f, diagram) = mistyped();
+
+    let error = typecheck(&diagram.into(), &sig, Mode::default(), true).unwrap_err();
+    let TypeError::Mismatch(location) = &error else {
+        panic!("unexpected error: {error}");
+    };
+    assert_eq!(
+        location.point,
+        vec![SliceIndex::from(Height::Singular(0)); 2]
+    );
+    assert_eq!(location.generator, m);
+    assert_eq!(location.source, Diagram::from(f.clone()));
+    assert_eq!(location.target, Diagram::from(f));
+}
+
+#[test]
+fn recursive_errors_are_located_in_slices() {
+    let (sig, _, _, diagram) = mistyped();
+
+    let error = typecheck(&diagram.identity().into(), &sig, Mode::default(), true).unwrap_err();
+    assert_eq!(
+        error.location().unwrap().point,
+        vec![
+            Height::Regular(0).into(),
+            Height::Singular(0).into(),
+            Height::Singular(0).into()
+        ]
+    );
+    assert!(error.to_string().ends_with("at R0 S0 S0"));
+}
+
+#[test]
+fn contraction_errors_are_located() {
+    let (_sig, cup) = examples::cup();
+    let (sig, e) = examples::bead_series(1);
+    let cup_then_e = cup.attach(&e, Boundary::Target, &[]).unwrap();
+
+    let error = cup_then_e
+        .identity()
+        .contract(
+            Boundary::Target.into(),
+            &mut [],
+            0,
+            Direction::Forward,
+            1,
+            None,
+            &sig,
+        )
+        .unwrap_err();
+    let ContractionError::IllTyped(error) = error else {
+        panic!("unexpected error: {error}");
+    };
+    // The error is located at the bead in the 2-diagram which was contracted.
+    let location = error.location().unwrap();
+    assert_eq!(location.generator, e.max_generator().generator);
+    assert_eq!(
+        location.point,
+        vec![Height::Singular(1).into(), Height::Singular(0).into()]
+    );
+}
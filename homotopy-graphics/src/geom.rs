@@ -149,15 +149,7 @@ impl CubicalGeometry {
             return Err(DimensionError);
         }
 
-        let embedding = match (N, animated) {
-            (0, false) => [None, None, None, None],
-            (1, false) => [Some(0), None, None, None],
-            (2, false) => [Some(0), Some(1), None, None],
-            (3, false) => [Some(0), Some(1), Some(2), None],
-            (3, true) => [Some(0), Some(1), None, Some(2)],
-            (4, true) => [Some(0), Some(1), Some(2), Some(3)],
-            _ => unreachable!(),
-        };
+        let embedding = embedding::<N>(animated);
 
         // Extract the mesh and layout.
         let mesh = Mesh::new(diagram)?;
@@ -167,9 +159,7 @@ impl CubicalGeometry {
         let mut coord_to_vert: FastHashMap<[SliceIndex; N], Vert> = FastHashMap::default();
 
         for (path, diagram) in mesh.nodes() {
-            let position = layout[&path];
-            let position =
-                Vec4::from(embedding.map(|i| i.map(|i| position[i]).unwrap_or_default()));
+            let position = embed(embedding, layout[&path]);
 
             let boundary = calculate_boundary(path);
             let boundary = embedding.map(|i| i.map(|i| boundary[i]).unwrap_or_default());
@@ -246,20 +236,79 @@ impl CubicalGeometry {
             }
         }
 
-        // Center animation on origin and make time interval [-1,1]
         let (min, max) = geom.bounds();
-        let translation = 0.5 * (max + min);
-        let duration = 0.5 * (max.w - min.w);
-
         for vert in geom.verts.values_mut() {
-            vert.position -= translation;
-            vert.position.w /= duration;
+            center(&mut vert.position, min, max);
         }
 
         Ok(geom)
     }
 }
 
+/// The paths of the nodes in the mesh of a diagram together with the positions at which
+/// [`CubicalGeometry::new`] places them.
+pub fn node_positions<const N: usize>(
+    diagram: &Diagram,
+    animated: bool,
+) -> Result<Vec<([SliceIndex; N], Vec4)>, DimensionError> {
+    if diagram.dimension() < N {
+        return Err(DimensionError);
+    }
+
+    let embedding = embedding::<N>(animated);
+    let mesh = Mesh::new(diagram)?;
+    let layout = Layout::new(diagram)?;
+
+    let mut nodes: Vec<_> = mesh
+        .nodes()
+        .map(|(path, _)| (path, embed(embedding, layout[&path])))
+        .collect();
+
+    let (min, max) = nodes.iter().fold(
+        (
+            Vec4::broadcast(f32::INFINITY),
+            Vec4::broadcast(f32::NEG_INFINITY),
+        ),
+        |(min, max), (_, position)| {
+            (
+                min.min_by_component(*position),
+                max.max_by_component(*position),
+            )
+        },
+    );
+    for (_, position) in &mut nodes {
+        center(position, min, max);
+    }
+
+    Ok(nodes)
+}
+
+/// Which coordinate of the layout is placed in each component of the geometry.
+fn embedding<const N: usize>(animated: bool) -> [Option<usize>; 4] {
+    match (N, animated) {
+        (0, false) => [None, None, None, None],
+        (1, false) => [Some(0), None, None, None],
+        (2, false) => [Some(0), Some(1), None, None],
+        (3, false) => [Some(0), Some(1), Some(2), None],
+        (3, true) => [Some(0), Some(1), None, Some(2)],
+        (4, true) => [Some(0), Some(1), Some(2), Some(3)],
+        _ => unreachable!(),
+    }
+}
+
+fn embed<const N: usize>(embedding: [Option<usize>; 4], position: [f32; N]) -> Vec4 {
+    Vec4::from(embedding.map(|i| i.map(|i| position[i]).unwrap_or_default()))
+}
+
+/// Center animation on origin and make time interval [-1,1].
+fn center(position: &mut Vec4, min: Vec4, max: Vec4) {
+    let translation = 0.5 * (max + min);
+    let duration = 0.5 * (max.w - min.w);
+
+    *position -= translation;
+    position.w /= duration;
+}
+
 // Triangulation
 
 impl CubicalGeometry {
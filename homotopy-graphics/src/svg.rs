@@ -1,8 +1,20 @@
 use std::fmt::Write;
 
-use homotopy_core::{Generator, Orientation};
+use euclid::default::Transform2D;
+use homotopy_core::{
+    common::DimensionError,
+    complex::make_complex,
+    layout::Layout,
+    projection::{Depths, Projection},
+    Boundary, Diagram, Generator, Orientation,
+};
+use serde::Serialize;
 
-use crate::style::{GeneratorRepresentation, GeneratorStyle, SignatureStyleData};
+use self::{
+    render::GraphicElement,
+    shape::{path_to_svg, project_2d, Point},
+};
+use crate::style::{GeneratorRepresentation, GeneratorStyle, SignatureStyleData, VertexShape};
 
 pub mod render;
 pub mod shape;
@@ -39,6 +51,28 @@ macro_rules! write_styles_for {
     }};
 }
 
+#[derive(Copy, Clone, PartialEq, Debug, Serialize)]
+pub struct SvgOptions {
+    /// The number of pixels per unit of layout.
+    pub scale: f32,
+
+    /// The stroke width of wires.
+    pub wire_thickness: f32,
+
+    /// The radius of points.
+    pub point_radius: f32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            scale: 40.0,
+            wire_thickness: 8.0,
+            point_radius: 6.0,
+        }
+    }
+}
+
 pub fn stylesheet(styles: &impl SignatureStyleData) -> String {
     let mut stylesheet = String::new();
 
@@ -90,3 +124,129 @@ fn generator_class(generator: Generator, c: usize, orientation: Orientation) ->
         }
     )
 }
+
+/// Render a diagram to a standalone SVG image, including its stylesheet.
+///
+/// This produces the same picture as the 2D view in the web app, without any of the interactive
+/// elements.
+pub fn render(
+    diagram: &Diagram,
+    dimension: u8,
+    signature_styles: &impl SignatureStyleData,
+    options: SvgOptions,
+) -> Result<String, DimensionError> {
+    match dimension {
+        0 => render_generic::<0>(diagram, signature_styles, options),
+        1 => render_generic::<1>(diagram, signature_styles, options),
+        2 => render_generic::<2>(diagram, signature_styles, options),
+        _ => Err(DimensionError),
+    }
+}
+
+fn render_generic<const N: usize>(
+    diagram: &Diagram,
+    signature_styles: &impl SignatureStyleData,
+    options: SvgOptions,
+) -> Result<String, DimensionError> {
+    let dimension = diagram.dimension();
+    let layout = Layout::<N>::new(diagram)?;
+    let complex = make_complex(diagram);
+    let depths = Depths::<N>::new(diagram)?;
+    let projection = Projection::<N>::new(diagram, &layout, &depths)?;
+    let graphic = GraphicElement::build(&complex, &layout, &projection, &depths);
+
+    let size = Point::from(project_2d(layout[&[Boundary::Target.into(); N]]))
+        .max((1.0, 1.0).into())
+        .to_vector()
+        .to_size()
+        * options.scale;
+
+    // Flip the vertical direction so that diagrams are read from bottom to top.
+    let transform =
+        Transform2D::scale(options.scale, -options.scale).then_translate((0.0, size.height).into());
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = size.width,
+        h = size.height,
+    )
+    .unwrap();
+    writeln!(svg, "<style>{}</style>", stylesheet(signature_styles)).unwrap();
+
+    for (i, element) in graphic.iter().enumerate() {
+        let class = generator_class_from_diagram_dim(
+            element.generator(),
+            element.orientation(),
+            dimension,
+            element.clone().into(),
+        );
+
+        match element.transformed(&transform) {
+            GraphicElement::Surface(_, path) => {
+                writeln!(svg, r#"<path d="{}" class="{class}"/>"#, path_to_svg(&path)).unwrap();
+            }
+            GraphicElement::Wire(_, _, path, mask) if mask.is_empty() => {
+                writeln!(
+                    svg,
+                    r#"<path d="{}" class="{class}" stroke-width="{}"/>"#,
+                    path_to_svg(&path),
+                    options.wire_thickness,
+                )
+                .unwrap();
+            }
+            GraphicElement::Wire(_, _, path, mask) => {
+                writeln!(
+                    svg,
+                    r#"<defs><mask maskUnits="userSpaceOnUse" id="mask-{i}"><rect width="100%" height="100%" fill="white"/>"#,
+                )
+                .unwrap();
+                for mask_path in mask {
+                    writeln!(
+                        svg,
+                        r#"<path d="{}" stroke-width="{}" fill="none" stroke="black" stroke-linecap="round"/>"#,
+                        path_to_svg(&mask_path),
+                        options.wire_thickness * 2.0,
+                    )
+                    .unwrap();
+                }
+                writeln!(svg, "</mask></defs>").unwrap();
+                writeln!(
+                    svg,
+                    r#"<path d="{}" class="{class}" stroke-width="{}" mask="url(#mask-{i})"/>"#,
+                    path_to_svg(&path),
+                    options.wire_thickness,
+                )
+                .unwrap();
+            }
+            GraphicElement::Point(d, point) => {
+                let radius = options.point_radius;
+                let shape = signature_styles
+                    .generator_style(d.generator)
+                    .map(GeneratorStyle::shape)
+                    .unwrap_or_default();
+                match shape {
+                    VertexShape::Circle => writeln!(
+                        svg,
+                        r#"<circle r="{radius}" cx="{}" cy="{}" class="{class}"/>"#,
+                        point.x, point.y,
+                    ),
+                    VertexShape::Square => writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" class="{class}"/>"#,
+                        point.x - radius,
+                        point.y - radius,
+                        radius * 2.0,
+                        radius * 2.0,
+                    ),
+                }
+                .unwrap();
+            }
+        }
+    }
+
+    writeln!(svg, "</svg>").unwrap();
+
+    Ok(svg)
+}
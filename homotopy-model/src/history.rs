@@ -3,27 +3,41 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-use homotopy_common::tree::{Node, NodeData, Tree};
+use homotopy_common::{
+    hash::FastHashMap,
+    tree::{Node, NodeData, Tree},
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::proof::ProofState;
+use super::proof::{ProofState, Workspace};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action {
     Move(Direction),
-    // TODO: history pruning
+    /// Discard the branch of the history starting at a snapshot, which must not lie on the path
+    /// to the current snapshot.
+    Prune(Node),
 }
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Direction {
     Linear(homotopy_core::Direction),
-    // TODO: branch moves
+    /// Switch to the previous or next branch leaving the parent of the current snapshot.
+    Sibling(homotopy_core::Direction),
+    /// Jump to any snapshot in the history.
+    Jump(Node),
 }
 
 #[derive(Clone, Eq, PartialEq, Default)]
 pub struct Snapshot {
     proof: ProofState,
     action: Option<super::proof::Action>,
+    /// The navigation in the parent snapshot which preceded the action.
+    pub(crate) navigation: Vec<super::proof::Action>,
+    /// The workspace as it was when the snapshot was taken, before any navigation.
+    pub(crate) origin: Option<Workspace>,
+    /// The child which was visited most recently, and which redo moves to.
+    redo: Option<Node>,
 }
 
 impl Deref for Snapshot {
@@ -47,10 +61,10 @@ pub trait UndoState {
 
     fn can_redo(&self) -> bool;
 
-    fn can_move(&self, dir: &Direction) -> bool {
+    fn can_move(&self, dir: homotopy_core::Direction) -> bool {
         match dir {
-            Direction::Linear(homotopy_core::Direction::Forward) => self.can_redo(),
-            Direction::Linear(homotopy_core::Direction::Backward) => self.can_undo(),
+            homotopy_core::Direction::Forward => self.can_redo(),
+            homotopy_core::Direction::Backward => self.can_undo(),
         }
     }
 }
@@ -72,12 +86,32 @@ impl fmt::Debug for Snapshot {
 }
 
 impl Snapshot {
-    const fn new(action: Option<super::proof::Action>, proof: ProofState) -> Self {
-        Self { proof, action }
+    pub(crate) fn new(action: Option<super::proof::Action>, proof: ProofState) -> Self {
+        Self {
+            origin: proof.workspace.clone(),
+            proof,
+            action,
+            navigation: vec![],
+            redo: None,
+        }
+    }
+
+    /// The action which led to this snapshot, if it is not the initial one.
+    #[must_use]
+    pub const fn action(&self) -> Option<&super::proof::Action> {
+        self.action.as_ref()
     }
 }
 
-#[derive(Debug, Clone)]
+/// The shape of a [History], with the action which led to each snapshot, indexed by the same
+/// nodes as the history itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outline {
+    pub actions: Tree<Option<super::proof::Action>>,
+    pub current: Node,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct History {
     snapshots: Tree<Snapshot>,
     current: Node,
@@ -97,6 +131,12 @@ pub enum HistoryError {
     Undo,
     #[error("error while performing redo")]
     Redo,
+    #[error("error while switching branch")]
+    Branch,
+    #[error("error while jumping to snapshot")]
+    Jump,
+    #[error("error while pruning history")]
+    Prune,
 }
 
 impl History {
@@ -105,15 +145,121 @@ impl History {
         &self.snapshots[self.current]
     }
 
+    /// Records the proof resulting from an action. Navigation replaces the current snapshot
+    /// instead of adding an undo step, so that undo returns to where the user last was.
     pub fn add(&mut self, action: super::proof::Action, proof: Proof) {
-        if let Some(child) = self.snapshots.push_onto(
-            self.current,
-            Snapshot::new(Some(action), proof.into_inner().proof),
-        ) {
-            self.current = child;
+        if action.is_navigation() {
+            self.snapshots[self.current].proof = proof.into_inner().proof;
+            return;
+        }
+
+        let mut snapshot = Snapshot::new(Some(action), proof.into_inner().proof);
+        snapshot.navigation = self.navigation();
+        if let Some(child) = self.snapshots.push_onto(self.current, snapshot) {
+            self.visit(child);
+        }
+    }
+
+    /// The navigation actions which lead from the workspace as it was when the current snapshot
+    /// was taken to the workspace as it is now.
+    #[must_use]
+    pub fn navigation(&self) -> Vec<super::proof::Action> {
+        match (&self.proof().origin, &self.proof().workspace) {
+            (Some(origin), Some(workspace)) => origin.navigation(workspace),
+            _ => vec![],
+        }
+    }
+
+    /// Grafts a history restored from a file onto the current snapshot, keeping the rest of this
+    /// history, and moves to the snapshot which was current in the restored one. The empty proof
+    /// at the root of the restored history becomes a child of the current snapshot.
+    pub fn graft(&mut self, history: Self) {
+        let mut nodes = FastHashMap::default();
+        for (node, snapshot) in history.snapshots.iter() {
+            let parent = snapshot
+                .parent()
+                .map_or(self.current, |parent| nodes[&parent]);
+            if let Some(grafted) = self.snapshots.push_onto(parent, (**snapshot).clone()) {
+                nodes.insert(node, grafted);
+            }
+        }
+        for grafted in nodes.values() {
+            let snapshot = &mut self.snapshots[*grafted];
+            snapshot.redo = snapshot.redo.and_then(|node| nodes.get(&node).copied());
+        }
+        self.visit(nodes[&history.current]);
+    }
+
+    /// Rebuilds a history from its snapshots, positioned at `current`.
+    pub(crate) fn from_snapshots(snapshots: Tree<Snapshot>, current: Node) -> Option<Self> {
+        let mut history = Self { snapshots, current };
+        history.contains(current).then(|| {
+            history.visit(current);
+            history
+        })
+    }
+
+    pub(crate) const fn snapshots(&self) -> &Tree<Snapshot> {
+        &self.snapshots
+    }
+
+    /// The snapshot which is currently shown.
+    #[must_use]
+    pub const fn current(&self) -> Node {
+        self.current
+    }
+
+    #[must_use]
+    pub fn outline(&self) -> Outline {
+        Outline {
+            actions: self.snapshots.map_ref(|snapshot| snapshot.action.clone()),
+            current: self.current,
+        }
+    }
+
+    /// Makes `node` the current snapshot, remembering the way to it for redo.
+    fn visit(&mut self, node: Node) {
+        self.current = node;
+        let path: Vec<_> = self.snapshots.ancestors_of(node).collect();
+        for pair in path.windows(2) {
+            self.snapshots[pair[1]].redo = Some(pair[0]);
+        }
+    }
+
+    /// Determines if `node` is a snapshot of this history.
+    fn contains(&self, node: Node) -> bool {
+        self.snapshots.get(node).is_some()
+            && self.snapshots.ancestors_of(node).last() == Some(self.snapshots.root())
+    }
+
+    fn sibling(&self, dir: homotopy_core::Direction) -> Option<Node> {
+        let parent = self.proof().parent()?;
+        let siblings: Vec<_> = self.snapshots[parent].children().collect();
+        let index = siblings.iter().position(|node| *node == self.current)?;
+        match dir {
+            homotopy_core::Direction::Forward => siblings.get(index + 1).copied(),
+            homotopy_core::Direction::Backward => index.checked_sub(1).map(|index| siblings[index]),
+        }
+    }
+
+    #[must_use]
+    pub fn can_move(&self, dir: &Direction) -> bool {
+        match dir {
+            Direction::Linear(dir) => self.proof().can_move(*dir),
+            Direction::Sibling(dir) => self.sibling(*dir).is_some(),
+            Direction::Jump(node) => self.contains(*node),
         }
     }
 
+    #[must_use]
+    pub fn can_prune(&self, node: Node) -> bool {
+        self.contains(node)
+            && self
+                .snapshots
+                .ancestors_of(self.current)
+                .all(|ancestor| ancestor != node)
+    }
+
     pub fn undo(&mut self) -> Result<(), HistoryError> {
         let prev = self.proof().parent().ok_or(HistoryError::Undo)?;
         self.current = prev;
@@ -121,50 +267,106 @@ impl History {
     }
 
     pub fn redo(&mut self) -> Result<(), HistoryError> {
-        let next = self.proof().last().ok_or(HistoryError::Redo)?;
+        let next = self
+            .proof()
+            .redo
+            .or_else(|| self.proof().last())
+            .ok_or(HistoryError::Redo)?;
         self.current = next;
         Ok(())
     }
 
+    pub fn move_to_sibling(&mut self, dir: homotopy_core::Direction) -> Result<(), HistoryError> {
+        let sibling = self.sibling(dir).ok_or(HistoryError::Branch)?;
+        self.visit(sibling);
+        Ok(())
+    }
+
+    pub fn jump(&mut self, node: Node) -> Result<(), HistoryError> {
+        if !self.contains(node) {
+            return Err(HistoryError::Jump);
+        }
+        self.visit(node);
+        Ok(())
+    }
+
+    /// Discards the branch starting at `node` and frees the memory held by its snapshots.
+    pub fn prune(&mut self, node: Node) -> Result<(), HistoryError> {
+        if !self.can_prune(node) {
+            return Err(HistoryError::Prune);
+        }
+        self.snapshots.remove(node);
+        let indices = self.snapshots.clean_up();
+        self.current = indices[&self.current];
+        for node in indices.values() {
+            let snapshot = &mut self.snapshots[*node];
+            snapshot.redo = snapshot.redo.and_then(|node| indices.get(&node).copied());
+        }
+        Ok(())
+    }
+
+    /// Moves to the child of the current snapshot which results from `action`, if it was taken
+    /// after the same navigation as the current one.
     pub fn try_redo(&mut self, action: &super::proof::Action) -> Result<(), HistoryError> {
+        let navigation = self.navigation();
         let next = self
             .proof()
             .children()
             .find(|id| {
                 self.snapshots
-                    .with(*id, |n| n.action.as_ref() == Some(action))
+                    .with(*id, |n| {
+                        n.action.as_ref() == Some(action) && n.navigation == navigation
+                    })
                     .unwrap_or_default()
             })
             .ok_or(HistoryError::Redo)?;
-        self.current = next;
+        self.visit(next);
         Ok(())
     }
 
     #[must_use]
     pub fn get_actions(&self) -> Vec<super::proof::Action> {
-        let mut actions: Vec<_> = self
-            .snapshots
-            .ancestors_of(self.current)
-            .filter_map(|n| self.snapshots.with(n, |s| s.action.clone()).flatten())
-            .collect();
-        actions.reverse();
-        actions
+        self.actions_along(&self.path())
     }
 
     #[must_use]
     pub fn get_last_import_segment(&self) -> Vec<super::proof::Action> {
-        let mut actions = Vec::new();
-        for a in self
-            .snapshots
-            .ancestors_of(self.current)
-            .filter_map(|n| self.snapshots.with(n, |s| s.action.clone()).flatten())
-        {
-            actions.push(a.clone());
-            if matches!(a, super::proof::Action::ImportProof(_)) {
-                break;
+        let path = self.path();
+        let start = path
+            .iter()
+            .rposition(|node| {
+                matches!(
+                    self.snapshots[*node].action,
+                    Some(super::proof::Action::ImportProof(_))
+                )
+            })
+            .unwrap_or_default();
+        self.actions_along(&path[start..])
+    }
+
+    /// The snapshots from the start of the history to the current one.
+    fn path(&self) -> Vec<Node> {
+        let mut path: Vec<_> = self.snapshots.ancestors_of(self.current).collect();
+        path.reverse();
+        path
+    }
+
+    /// The actions along a path of snapshots, each preceded by the navigation before it. The
+    /// actions start again from the root of a grafted history, which is an empty proof.
+    fn actions_along(&self, path: &[Node]) -> Vec<super::proof::Action> {
+        let mut actions = vec![];
+        for (i, node) in path.iter().enumerate() {
+            let snapshot = &self.snapshots[*node];
+            match &snapshot.action {
+                Some(action) => {
+                    if i > 0 {
+                        actions.extend(snapshot.navigation.iter().cloned());
+                    }
+                    actions.push(action.clone());
+                }
+                None => actions.clear(),
             }
         }
-        actions.reverse();
         actions
     }
 
@@ -0,0 +1,259 @@
+//! A JSON representation of proofs for exchange with other tools.
+//!
+//! Unlike the format produced by [`crate::serialize`], this is meant to be read and written by
+//! external scripts. A proof is exported as an object of the form
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "signature": [
+//!     {
+//!       "type": "Folder",
+//!       "name": "Objects",
+//!       "open": true,
+//!       "children": [
+//!         {
+//!           "type": "Generator",
+//!           "generator": { "id": 0, "dimension": 0 },
+//!           "name": "x",
+//!           "color": "#2980b9",
+//!           "shape": "Circle",
+//!           "oriented": false,
+//!           "invertibility": "Directed",
+//!           "single_preview": true,
+//!           "diagram": 0
+//!         }
+//!       ]
+//!     }
+//!   ],
+//!   "workspace": { "diagram": 0, "path": [], "view": { "dimension": 0 } },
+//!   "metadata": { "title": null, "author": null, "abstr": null },
+//!   "tables": { "diagrams": [...], "rewrites": [...], "cones": [...] }
+//! }
+//! ```
+//!
+//! where `signature` lists the top-level items of the signature, and diagrams are referred to by
+//! their index into `tables`, whose format is described in [`homotopy_core::json`]. Generator
+//! shapes are `"Circle"` or `"Square"`, and invertibility is one of `"Directed"`,
+//! `"Invertible"` or `{ "Dualisable": n }`. The workspace is `null` when there is none.
+
+use std::str::FromStr;
+
+use homotopy_common::tree::{Node, Tree};
+use homotopy_core::{json::Tables, signature::Invertibility, Generator, SliceIndex};
+use homotopy_graphics::style::{Color, VertexShape};
+use im::Vector;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::proof::{
+    generators::GeneratorInfo, FolderInfo, Metadata, Signature, SignatureItem, View, Workspace,
+};
+
+/// The version of the format produced by [`export`].
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unsupported version {0}, expected version {VERSION}")]
+    Version(u32),
+
+    #[error("{0} is not a valid color")]
+    Color(String),
+
+    #[error("generator {name} has dimension {expected} but its diagram has dimension {found}")]
+    Dimension {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error(transparent)]
+    Tables(#[from] homotopy_core::json::ImportError),
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProofJson {
+    version: u32,
+    signature: Vec<ItemJson>,
+    workspace: Option<WorkspaceJson>,
+    metadata: Metadata,
+    tables: Tables,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum ItemJson {
+    Folder {
+        name: String,
+        open: bool,
+        children: Vec<ItemJson>,
+    },
+    Generator {
+        generator: Generator,
+        name: String,
+        color: String,
+        shape: VertexShape,
+        oriented: bool,
+        invertibility: Invertibility,
+        single_preview: bool,
+        diagram: usize,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct WorkspaceJson {
+    diagram: usize,
+    #[serde(default)]
+    path: Vector<SliceIndex>,
+    #[serde(default)]
+    view: Option<View>,
+}
+
+/// Exports a proof as pretty-printed JSON.
+#[must_use]
+pub fn export(signature: &Signature, workspace: Option<&Workspace>, metadata: &Metadata) -> String {
+    let mut tables = Tables::default();
+
+    let tree = signature.as_tree();
+    let signature = tree
+        .get(tree.root())
+        .unwrap()
+        .children()
+        .map(|node| export_item(&tree, node, &mut tables))
+        .collect();
+
+    let workspace = workspace.map(|workspace| WorkspaceJson {
+        diagram: tables.pack_diagram(&workspace.diagram),
+        path: workspace.path.clone(),
+        view: Some(workspace.view),
+    });
+
+    let proof = ProofJson {
+        version: VERSION,
+        signature,
+        workspace,
+        metadata: metadata.clone(),
+        tables,
+    };
+    serde_json::to_string_pretty(&proof).unwrap()
+}
+
+fn export_item(tree: &Tree<SignatureItem>, node: Node, tables: &mut Tables) -> ItemJson {
+    let data = tree.get(node).unwrap();
+    match data.inner() {
+        SignatureItem::Folder(info) => ItemJson::Folder {
+            name: info.name.clone(),
+            open: info.open,
+            children: data
+                .children()
+                .map(|child| export_item(tree, child, tables))
+                .collect(),
+        },
+        SignatureItem::Item(info) => ItemJson::Generator {
+            generator: info.generator,
+            name: info.name.clone(),
+            color: info.color.hex(),
+            shape: info.shape.clone(),
+            oriented: info.oriented,
+            invertibility: info.invertibility,
+            single_preview: info.single_preview,
+            diagram: tables.pack_diagram(&info.diagram),
+        },
+    }
+}
+
+/// Imports a proof exported by [`export`], without checking its diagrams.
+pub fn import(json: &str) -> Result<((Signature, Option<Workspace>), Metadata), ImportError> {
+    let mut proof: ProofJson = serde_json::from_str(json)?;
+    if proof.version != VERSION {
+        return Err(ImportError::Version(proof.version));
+    }
+
+    let mut tree = Tree::<SignatureItem>::default();
+    let root = tree.root();
+    let mut folder_index = 0;
+    for item in proof.signature {
+        import_item(item, &mut tree, root, &mut folder_index, &mut proof.tables)?;
+    }
+
+    let workspace = proof
+        .workspace
+        .map(|workspace| -> Result<_, ImportError> {
+            let diagram = proof.tables.unpack_diagram(workspace.diagram)?;
+            let mut imported = Workspace::new(diagram);
+            imported.path = workspace.path;
+            if let Some(view) = workspace.view {
+                imported.view = view;
+            }
+            Ok(imported)
+        })
+        .transpose()?;
+
+    Ok(((tree.into(), workspace), proof.metadata))
+}
+
+fn import_item(
+    item: ItemJson,
+    tree: &mut Tree<SignatureItem>,
+    parent: Node,
+    folder_index: &mut usize,
+    tables: &mut Tables,
+) -> Result<(), ImportError> {
+    match item {
+        ItemJson::Folder {
+            name,
+            open,
+            children,
+        } => {
+            *folder_index += 1;
+            let node = tree
+                .push_onto(
+                    parent,
+                    SignatureItem::Folder(FolderInfo {
+                        id: *folder_index,
+                        open,
+                        name,
+                    }),
+                )
+                .unwrap();
+            for child in children {
+                import_item(child, tree, node, folder_index, tables)?;
+            }
+        }
+        ItemJson::Generator {
+            generator,
+            name,
+            color,
+            shape,
+            oriented,
+            invertibility,
+            single_preview,
+            diagram,
+        } => {
+            let diagram = tables.unpack_diagram(diagram)?;
+            if diagram.dimension() != generator.dimension {
+                return Err(ImportError::Dimension {
+                    name,
+                    expected: generator.dimension,
+                    found: diagram.dimension(),
+                });
+            }
+            let info = GeneratorInfo {
+                generator,
+                oriented,
+                invertibility,
+                single_preview,
+                color: Color::from_str(&color).map_err(|_err| ImportError::Color(color))?,
+                shape,
+                diagram,
+                name,
+            };
+            tree.push_onto(parent, SignatureItem::Item(info));
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,7 @@
 pub mod history;
+pub mod json;
 pub mod migration;
 pub mod proof;
+pub mod regression;
 pub mod serialize;
+pub mod text;
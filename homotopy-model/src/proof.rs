@@ -1,13 +1,19 @@
-use std::convert::{Into, TryFrom};
+use std::{
+    cmp::Ordering,
+    convert::{Into, TryFrom},
+};
 
 use homotopy::Homotopy;
 use homotopy_core::{
+    bubble::BubbleError,
     common::{Boundary, BoundaryPath, Direction, Generator, Height, SliceIndex},
-    contraction::ContractionError,
+    contraction::{Bias, ContractionError},
     diagram::{AttachmentError, NewDiagramError},
     expansion::ExpansionError,
+    morphism::{MorphismError, SignatureMorphism},
     signature::{Invertibility, Signature as _},
-    typecheck::{typecheck, Mode},
+    substitution::{retype, SubstitutionError},
+    typecheck::{typecheck, Mode, TypeError},
     Diagram, Diagram0, DiagramN,
 };
 use im::Vector;
@@ -16,12 +22,13 @@ pub use signature::*;
 use thiserror::Error;
 
 use self::homotopy::{Contract, Expand};
-use crate::{migration, proof::generators::GeneratorInfo, serialize};
+use crate::{history::History, migration, proof::generators::GeneratorInfo, serialize};
 
 mod signature;
 
 pub mod generators;
 pub mod homotopy;
+pub mod search;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct View {
@@ -69,6 +76,45 @@ impl Workspace {
     pub fn visible_dimension(&self) -> usize {
         self.diagram.dimension() - self.path.len()
     }
+
+    /// The shortest sequence of navigation actions which takes this workspace to the slice and
+    /// view dimension of another workspace with the same diagram.
+    #[must_use]
+    pub fn navigation(&self, to: &Self) -> Vec<Action> {
+        let common = self
+            .path
+            .iter()
+            .zip(&to.path)
+            .take_while(|(from, to)| from == to)
+            .count();
+
+        let mut actions = vec![];
+        let mut dimension = self.view.dimension;
+        if self.path.len() > common {
+            actions.push(Action::AscendSlice(self.path.len() - common));
+            for _ in common..self.path.len() {
+                if dimension < 2 {
+                    dimension += 1;
+                }
+            }
+        }
+        if to.path.len() > common {
+            actions.extend(
+                to.path
+                    .iter()
+                    .skip(common)
+                    .copied()
+                    .map(Action::DescendSlice),
+            );
+            dimension = dimension.min(to.visible_dimension() as u8);
+        }
+        match dimension.cmp(&to.view.dimension) {
+            Ordering::Less => actions.push(Action::IncreaseView(to.view.dimension - dimension)),
+            Ordering::Greater => actions.push(Action::DecreaseView(dimension - to.view.dimension)),
+            Ordering::Equal => {}
+        }
+        actions
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -128,6 +174,16 @@ pub enum Action {
 
     Attach(AttachOption),
 
+    /// Rewrite every non-overlapping occurrence of the source of a generator in the visible
+    /// diagram by its target in one step. Does nothing if the visible diagram is an interior
+    /// slice of the workspace.
+    RewriteAll(Generator),
+
+    /// Insert a diagram followed by its inverse into the visible diagram at an embedding, or
+    /// cancel such a pair, provided the generators of the diagram are invertible. Does nothing if
+    /// the visible diagram is an interior slice of the workspace.
+    Bubble(BubbleOption),
+
     Homotopy(Homotopy),
 
     Squash,
@@ -142,6 +198,12 @@ pub enum Action {
 
     Theorem,
 
+    /// Inline a definition made by [Action::Theorem]: given a generator rewriting a single
+    /// application of another generator into a diagram, replace every occurrence of the latter
+    /// in the workspace, boundary and stash by that diagram, and likewise in the types of the
+    /// other cells whose boundary mentions it.
+    Inline(Generator),
+
     SuspendSignature,
 
     Suspend(Generator, Generator),
@@ -150,6 +212,10 @@ pub enum Action {
 
     ImportProof(SerializedData),
 
+    /// Transport the proof into the signature of another proof, sending each generator to the
+    /// generator of the same name and dimension.
+    ApplyMorphism(SerializedData),
+
     EditSignature(SignatureEdit),
 
     EditMetadata(MetadataEdit),
@@ -195,10 +261,12 @@ impl Action {
                 .workspace
                 .as_ref()
                 .is_some_and(|ws| ws.visible_dimension() > 0),
-            Self::SwitchSlice(_) => proof.workspace.as_ref().is_some_and(|ws| {
-                ws.path
-                    .last()
-                    .is_some_and(|index| matches!(index, Interior(_)))
+            Self::SwitchSlice(direction) => proof.workspace.as_ref().is_some_and(|ws| {
+                let mut parent = ws.clone();
+                parent.path.pop_back().is_some_and(|index| {
+                    DiagramN::try_from(parent.visible_diagram())
+                        .is_ok_and(|diagram| index.step(diagram.size(), *direction).is_some())
+                })
             }),
             Self::IncreaseView(count) => {
                 *count > 0
@@ -217,6 +285,19 @@ impl Action {
                 .workspace
                 .as_ref()
                 .is_some_and(|ws| option.boundary_path.is_none() || ws.diagram.dimension() > 0),
+            Self::RewriteAll(generator) => proof.workspace.as_ref().is_some_and(|ws| {
+                let path: Vec<_> = ws.path.iter().copied().collect();
+                BoundaryPath::split(&path).1.is_empty()
+                    && proof
+                        .signature
+                        .generator_info(*generator)
+                        .is_some_and(|info| info.diagram.dimension() == ws.visible_dimension() + 1)
+            }),
+            Self::Bubble(option) => proof.workspace.as_ref().is_some_and(|ws| {
+                let path: Vec<_> = ws.path.iter().copied().collect();
+                BoundaryPath::split(&path).1.is_empty()
+                    && option.diagram.dimension() == ws.visible_dimension()
+            }),
             Self::Homotopy(_) => proof
                 .workspace
                 .as_ref()
@@ -253,9 +334,10 @@ impl Action {
                 .workspace
                 .as_ref()
                 .is_some_and(|ws| ws.diagram.dimension() > 0),
+            Self::Inline(generator) => proof.definition(*generator).is_some(),
             Self::Suspend(_, _) | Self::SuspendSignature => proof.signature.has_generators(),
             Self::Merge(_, _) => true,
-            Self::ImportProof(_) => true,
+            Self::ImportProof(_) | Self::ApplyMorphism(_) => true,
             Self::EditSignature(_) | Self::EditMetadata(_) => true, /* technically the edits could be trivial but do not worry about that for now */
             Self::FlipBoundary | Self::RecoverBoundary => proof.boundary.is_some(),
             Self::Stash => proof.workspace.is_some(),
@@ -276,6 +358,66 @@ impl Action {
             ))
         )
     }
+
+    /// Determines if a given [Action] only changes which part of the workspace is shown, rather
+    /// than the proof itself.
+    #[must_use]
+    pub const fn is_navigation(&self) -> bool {
+        matches!(
+            self,
+            Self::AscendSlice(_)
+                | Self::DescendSlice(_)
+                | Self::SwitchSlice(_)
+                | Self::IncreaseView(_)
+                | Self::DecreaseView(_)
+        )
+    }
+
+    /// A short description of the kind of a given [Action], for display in the history.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::CreateGeneratorZero => "Add 0-cell",
+            Self::SetBoundary(Boundary::Source) => "Set source",
+            Self::SetBoundary(Boundary::Target) => "Set target",
+            Self::TakeIdentityDiagram => "Identity",
+            Self::ClearWorkspace => "Clear workspace",
+            Self::ClearBoundary => "Clear boundary",
+            Self::SelectGenerator(_) => "Select generator",
+            Self::AscendSlice(_) => "Ascend slice",
+            Self::DescendSlice(_) => "Descend slice",
+            Self::SwitchSlice(_) => "Switch slice",
+            Self::IncreaseView(_) => "Increase view",
+            Self::DecreaseView(_) => "Decrease view",
+            Self::Attach(_) => "Attach",
+            Self::RewriteAll(_) => "Rewrite all",
+            Self::Bubble(BubbleOption { cancel: false, .. }) => "Bubble",
+            Self::Bubble(BubbleOption { cancel: true, .. }) => "Cancel bubble",
+            Self::Homotopy(Homotopy::Contract(_)) => "Contract",
+            Self::Homotopy(Homotopy::Expand(_)) => "Expand",
+            Self::Squash => "Squash",
+            Self::Behead => "Behead",
+            Self::Befoot => "Befoot",
+            Self::Invert => "Invert",
+            Self::Restrict => "Restrict",
+            Self::Theorem => "Theorem",
+            Self::Inline(_) => "Inline",
+            Self::SuspendSignature => "Suspend signature",
+            Self::Suspend(_, _) => "Suspend",
+            Self::Merge(_, _) => "Merge",
+            Self::ImportProof(_) => "Import proof",
+            Self::ApplyMorphism(_) => "Apply morphism",
+            Self::EditSignature(_) => "Edit signature",
+            Self::EditMetadata(_) => "Edit metadata",
+            Self::FlipBoundary => "Flip boundary",
+            Self::RecoverBoundary => "Recover boundary",
+            Self::Stash => "Stash",
+            Self::StashDrop => "Drop stash",
+            Self::StashPop => "Pop stash",
+            Self::StashApply => "Apply stash",
+            Self::Nothing => "Nothing",
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -298,6 +440,34 @@ pub enum ProofError {
     Import,
     #[error(transparent)]
     SignatureError(#[from] SignatureError),
+    #[error(transparent)]
+    SubstitutionError(#[from] SubstitutionError),
+    #[error(transparent)]
+    MorphismError(#[from] MorphismError),
+}
+
+impl ProofError {
+    /// The type error behind a failed homotopy, located in the workspace diagram.
+    #[must_use]
+    pub const fn type_error(&self) -> Option<&TypeError> {
+        match self {
+            Self::ContractionError(ContractionError::IllTyped(error))
+            | Self::ExpansionError(ExpansionError::IllTyped(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Locates a type error found in the slice of the workspace diagram at `path`.
+    fn in_slice(mut self, path: &[SliceIndex]) -> Self {
+        if let Self::ContractionError(ContractionError::IllTyped(error))
+        | Self::ExpansionError(ExpansionError::IllTyped(error)) = &mut self
+        {
+            if let Some(location) = error.location_mut() {
+                location.point.splice(0..0, path.iter().copied());
+            }
+        }
+        self
+    }
 }
 
 impl ProofState {
@@ -336,6 +506,8 @@ impl ProofState {
             Action::IncreaseView(count) => self.increase_view(*count),
             Action::DecreaseView(count) => self.decrease_view(*count),
             Action::Attach(option) => self.attach(option)?,
+            Action::RewriteAll(generator) => self.rewrite_all(*generator)?,
+            Action::Bubble(option) => self.bubble(option)?,
             Action::Homotopy(Homotopy::Expand(homotopy)) => self.homotopy_expand(homotopy)?,
             Action::Homotopy(Homotopy::Contract(homotopy)) => self.homotopy_contract(homotopy)?,
             Action::Squash => self.squash()?,
@@ -344,6 +516,7 @@ impl ProofState {
             Action::Invert => self.invert()?,
             Action::Restrict => self.restrict(),
             Action::Theorem => self.theorem()?,
+            Action::Inline(generator) => self.inline(*generator)?,
             Action::SuspendSignature => self.suspend_signature(),
             Action::Suspend(s, t) => self.suspend(*s, *t),
             Action::Merge(from, to) => self.merge(*from, *to)?,
@@ -354,13 +527,64 @@ impl ProofState {
             Action::StashDrop => self.stash_drop(),
             Action::StashPop => self.stash_pop(),
             Action::StashApply => self.stash_apply(),
-            Action::ImportProof(data) => self.import_proof(data)?,
+            Action::ImportProof(data) => {
+                self.import_proof(data)?;
+                true
+            }
+            Action::ApplyMorphism(data) => self.apply_morphism(data)?,
             Action::EditMetadata(edit) => self.edit_metadata(edit),
             Action::Nothing => false,
         };
         Ok(result)
     }
 
+    /// The distinct outcomes of a homotopy in the workspace, each with the homotopy which
+    /// produces it.
+    ///
+    /// Contractions are tried with every bias, starting with the given one, and expansions with
+    /// every choice of factorization. At most [`homotopy::MAX_OUTCOMES`] options are returned.
+    #[must_use]
+    pub fn homotopy_options(&self, homotopy: &Homotopy) -> Vec<HomotopyOption> {
+        let Some(ws) = &self.workspace else {
+            return vec![];
+        };
+
+        match homotopy {
+            Homotopy::Contract(contract) => self
+                .contractions(contract)
+                .into_iter()
+                .map(|(bias, diagram, path)| HomotopyOption {
+                    homotopy: Homotopy::Contract(Contract {
+                        bias,
+                        ..contract.clone()
+                    }),
+                    workspace: Workspace {
+                        diagram,
+                        path,
+                        ..ws.clone()
+                    },
+                })
+                .collect(),
+            Homotopy::Expand(expand) => self
+                .expansions(expand)
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .map(|(choice, (diagram, path))| HomotopyOption {
+                    homotopy: Homotopy::Expand(Expand {
+                        choice,
+                        ..expand.clone()
+                    }),
+                    workspace: Workspace {
+                        diagram,
+                        path,
+                        ..ws.clone()
+                    },
+                })
+                .collect(),
+        }
+    }
+
     /// Determines if a given [Action] should reset the panzoom state, given the current [ProofState].
     #[must_use]
     pub fn resets_panzoom(&self, action: &Action) -> bool {
@@ -593,10 +817,118 @@ impl ProofState {
         Ok(true)
     }
 
+    /// Handler for [Action::RewriteAll].
+    ///
+    /// Invalid if the workspace is empty, the visible diagram is an interior slice, or the
+    /// generator is not one dimension higher than the visible diagram.
+    fn rewrite_all(&mut self, generator: Generator) -> Result<bool, ProofError> {
+        let Some(ws) = &mut self.workspace else {
+            return Ok(false);
+        };
+        let Diagram::DiagramN(rule) = &self
+            .signature
+            .generator_info(generator)
+            .ok_or(ProofError::UnknownGeneratorSelected)?
+            .diagram
+        else {
+            return Ok(false);
+        };
+
+        let path: Vec<_> = ws.path.iter().copied().collect();
+        let (boundary_path, interior_path) = BoundaryPath::split(&path);
+        if !interior_path.is_empty() || rule.dimension() != ws.visible_dimension() + 1 {
+            return Ok(false);
+        }
+
+        if let Some(bp) = boundary_path {
+            let Diagram::DiagramN(diagram) = &ws.diagram else {
+                return Ok(false);
+            };
+            let host = diagram.boundary(bp).ok_or(ProofError::InvalidSlice)?;
+            // Attaching to the source requires a rewrite into the source.
+            let witness = match bp.boundary() {
+                Boundary::Source => host.rewrite_all(&rule.inverse())?.inverse(),
+                Boundary::Target => host.rewrite_all(rule)?,
+            };
+            let embedding = vec![0; host.dimension()];
+            ws.diagram = diagram.attach(&witness, bp.boundary(), &embedding)?.into();
+        } else {
+            ws.diagram = ws.diagram.rewrite_all(rule)?.target();
+        }
+
+        Ok(true)
+    }
+
+    /// Handler for [Action::Bubble].
+    ///
+    /// Invalid if the workspace is empty, the visible diagram is an interior slice, or the diagram
+    /// does not have the same dimension as the visible diagram.
+    fn bubble(&mut self, option: &BubbleOption) -> Result<bool, ProofError> {
+        let Some(ws) = &mut self.workspace else {
+            return Ok(false);
+        };
+
+        let path: Vec<_> = ws.path.iter().copied().collect();
+        let (boundary_path, interior_path) = BoundaryPath::split(&path);
+        if !interior_path.is_empty() || option.diagram.dimension() != ws.visible_dimension() {
+            return Ok(false);
+        }
+
+        let embedding: Vec<_> = option.embedding.iter().copied().collect();
+        let bubble = |host: &DiagramN| {
+            if option.cancel {
+                host.cancel_at(&option.diagram, &embedding, &self.signature)
+            } else {
+                host.bubble_at(&option.diagram, &embedding, &self.signature)
+            }
+            .map_err(|error| match error {
+                BubbleError::NotInvertible => ProofError::NotInvertible,
+                BubbleError::Attachment(error) => error.into(),
+            })
+        };
+
+        let Diagram::DiagramN(diagram) = &ws.diagram else {
+            return Ok(false);
+        };
+        if let Some(bp) = boundary_path {
+            let host: DiagramN = diagram
+                .boundary(bp)
+                .ok_or(ProofError::InvalidSlice)?
+                .try_into()
+                .map_err(|_err| ProofError::InvalidSlice)?;
+            // Attaching to the source requires a diagram into the source.
+            let witness = match bp.boundary() {
+                Boundary::Source => bubble(&host)?.inverse(),
+                Boundary::Target => bubble(&host)?,
+            };
+            ws.diagram = diagram
+                .attach(&witness, bp.boundary(), &vec![0; host.dimension()])?
+                .into();
+        } else {
+            ws.diagram = bubble(diagram)?.target();
+        }
+
+        Ok(true)
+    }
+
     /// Handler for [Action::Homotopy].
     ///
-    /// Invalid if the workspace is empty or has dimension 0.
+    /// Invalid if the workspace is empty or has dimension 0, or if there are not as many outcomes
+    /// as the choice requires.
     fn homotopy_expand(&mut self, homotopy: &Expand) -> Result<bool, ProofError> {
+        if homotopy.choice > 0 {
+            let Some((diagram, path)) = self.expansions(homotopy)?.into_iter().nth(homotopy.choice)
+            else {
+                return Ok(false);
+            };
+
+            let ws = self.workspace.as_mut().unwrap();
+            ws.diagram = diagram;
+            ws.path = path;
+            return Ok(true);
+        }
+
+        // The first outcome is the one found without enumerating the others.
         let Some(ws) = &mut self.workspace else {
             return Ok(false);
         };
@@ -614,21 +946,27 @@ impl ProofState {
             let Diagram::DiagramN(diagram) = diagram else {
                 return Ok(false);
             };
-            *diagram = diagram.expand(
-                boundary_path,
-                &mut interior_path,
-                homotopy.point,
-                homotopy.direction,
-                &self.signature,
-            )?;
+            // The boundary is the slice up to and including the last boundary index.
+            let boundary_depth = location.len() - interior_path.len();
+            *diagram = diagram
+                .expand_along(
+                    boundary_path,
+                    &mut interior_path,
+                    &homotopy.point,
+                    homotopy.axis,
+                    homotopy.direction,
+                    &self.signature,
+                )
+                .map_err(|error| ProofError::from(error).in_slice(&location[..boundary_depth]))?;
         } else {
             *diagram = diagram
                 .clone()
                 .identity()
-                .expand(
+                .expand_along(
                     Boundary::Target.into(),
                     &mut interior_path,
-                    homotopy.point,
+                    &homotopy.point,
+                    homotopy.axis,
                     homotopy.direction,
                     &self.signature,
                 )?
@@ -643,6 +981,145 @@ impl ProofState {
         Ok(true)
     }
 
+    /// The distinct outcomes of an expansion in the workspace, each as the expanded diagram
+    /// together with the updated path to the visible slice.
+    fn expansions(
+        &self,
+        homotopy: &Expand,
+    ) -> Result<Vec<(Diagram, Vector<SliceIndex>)>, ProofError> {
+        let Some(ws) = &self.workspace else {
+            return Ok(vec![]);
+        };
+
+        let location = {
+            let mut location: Vec<_> = ws.path.iter().copied().collect();
+            location.extend(&homotopy.location);
+            location
+        };
+
+        let (boundary_path, interior_path) = BoundaryPath::split(&location);
+
+        let outcomes: Vec<(Diagram, Vec<Height>)> = if let Some(boundary_path) = boundary_path {
+            let Diagram::DiagramN(diagram) = &ws.diagram else {
+                return Ok(vec![]);
+            };
+            // The boundary is the slice up to and including the last boundary index.
+            let boundary_depth = location.len() - interior_path.len();
+            diagram
+                .expansions(
+                    boundary_path,
+                    &interior_path,
+                    &homotopy.point,
+                    homotopy.axis,
+                    homotopy.direction,
+                    &self.signature,
+                    homotopy::MAX_OUTCOMES,
+                )
+                .map_err(|error| ProofError::from(error).in_slice(&location[..boundary_depth]))?
+                .into_iter()
+                .map(|(diagram, path)| (diagram.into(), path))
+                .collect()
+        } else {
+            ws.diagram
+                .clone()
+                .identity()
+                .expansions(
+                    Boundary::Target.into(),
+                    &interior_path,
+                    &homotopy.point,
+                    homotopy.axis,
+                    homotopy.direction,
+                    &self.signature,
+                    homotopy::MAX_OUTCOMES,
+                )?
+                .into_iter()
+                .map(|(diagram, path)| (diagram.target(), path))
+                .collect()
+        };
+
+        let offset = boundary_path.map_or(0, |bp| bp.depth() + 1);
+        Ok(outcomes
+            .into_iter()
+            .map(|(diagram, interior_path)| {
+                let mut path = ws.path.clone();
+                for i in offset..path.len() {
+                    path[i] = SliceIndex::Interior(interior_path[i - offset]);
+                }
+                (diagram, path)
+            })
+            .collect())
+    }
+
+    /// The distinct outcomes of a contraction in the workspace under every choice of bias,
+    /// starting with the given one, each as the bias together with the contracted diagram and the
+    /// updated path to the visible slice.
+    fn contractions(
+        &self,
+        homotopy: &Contract,
+    ) -> Vec<(Option<Bias>, Diagram, Vector<SliceIndex>)> {
+        let Some(ws) = &self.workspace else {
+            return vec![];
+        };
+
+        let location = {
+            let mut location: Vec<_> = ws.path.iter().copied().collect();
+            location.extend(&homotopy.location);
+            location
+        };
+
+        let (boundary_path, interior_path) = BoundaryPath::split(&location);
+
+        let outcomes: Vec<(Option<Bias>, Diagram, Vec<Height>)> =
+            if let Some(boundary_path) = boundary_path {
+                let Diagram::DiagramN(diagram) = &ws.diagram else {
+                    return vec![];
+                };
+                diagram
+                    .contractions(
+                        boundary_path,
+                        &interior_path,
+                        homotopy.height,
+                        homotopy.direction,
+                        homotopy.step,
+                        homotopy.bias,
+                        &self.signature,
+                        homotopy::MAX_OUTCOMES,
+                    )
+                    .into_iter()
+                    .map(|(bias, diagram, path)| (bias, diagram.into(), path))
+                    .collect()
+            } else {
+                ws.diagram
+                    .clone()
+                    .identity()
+                    .contractions(
+                        Boundary::Target.into(),
+                        &interior_path,
+                        homotopy.height,
+                        homotopy.direction,
+                        homotopy.step,
+                        homotopy.bias,
+                        &self.signature,
+                        homotopy::MAX_OUTCOMES,
+                    )
+                    .into_iter()
+                    .map(|(bias, diagram, path)| (bias, diagram.target(), path))
+                    .collect()
+            };
+
+        let offset = boundary_path.map_or(0, |bp| bp.depth() + 1);
+        outcomes
+            .into_iter()
+            .map(|(bias, diagram, interior_path)| {
+                let mut path = ws.path.clone();
+                for i in offset..path.len() {
+                    path[i] = SliceIndex::Interior(interior_path[i - offset]);
+                }
+                (bias, diagram, path)
+            })
+            .collect()
+    }
+
     /// Handler for [Action::Homotopy].
     ///
     /// Invalid if the workspace is empty or has dimension 0.
@@ -664,15 +1141,19 @@ impl ProofState {
             let Diagram::DiagramN(diagram) = diagram else {
                 return Ok(false);
             };
-            *diagram = diagram.contract(
-                boundary_path,
-                &mut interior_path,
-                homotopy.height,
-                homotopy.direction,
-                homotopy.step,
-                homotopy.bias,
-                &self.signature,
-            )?;
+            // The boundary is the slice up to and including the last boundary index.
+            let boundary_depth = location.len() - interior_path.len();
+            *diagram = diagram
+                .contract(
+                    boundary_path,
+                    &mut interior_path,
+                    homotopy.height,
+                    homotopy.direction,
+                    homotopy.step,
+                    homotopy.bias,
+                    &self.signature,
+                )
+                .map_err(|error| ProofError::from(error).in_slice(&location[..boundary_depth]))?;
         } else {
             *diagram = diagram
                 .clone()
@@ -799,7 +1280,7 @@ impl ProofState {
             return Ok(false);
         };
 
-        if !ws.path.is_empty() {
+        if !ws.path.is_empty() || ws.diagram.dimension() == 0 {
             return Ok(false);
         }
 
@@ -870,8 +1351,66 @@ impl ProofState {
         Ok(true)
     }
 
+    /// The generator defined by a generator, together with its definition.
+    ///
+    /// This is the case when the source of the generator is a single application of another
+    /// generator, such as a proof made by [Action::Theorem].
+    fn definition(&self, generator: Generator) -> Option<(Generator, DiagramN)> {
+        let Diagram::DiagramN(proof) = &self.signature.generator_info(generator)?.diagram else {
+            return None;
+        };
+        let Diagram::DiagramN(definition) = proof.target() else {
+            return None;
+        };
+        let defined = proof.source().max_generator().generator;
+        (self.signature.generator_info(defined)?.diagram == proof.source())
+            .then_some((defined, definition))
+    }
+
+    /// Handler for [Action::Inline].
+    ///
+    /// Invalid if the generator is not a definition.
+    fn inline(&mut self, generator: Generator) -> Result<bool, ProofError> {
+        let Some((defined, definition)) = self.definition(generator) else {
+            return Ok(false);
+        };
+
+        // The definition keeps its type, so occurrences of it can not be inlined.
+        let signature = self
+            .signature
+            .filter_map(|info| (info.generator != generator).then(|| info.clone()));
+        let cells = retype(defined, &definition, &signature)?;
+        let diagrams = self
+            .diagrams()
+            .map(|diagram| diagram.substitute(defined, &definition, &signature))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (diagram, substituted) in self.diagrams_mut().zip(diagrams) {
+            *diagram = substituted;
+        }
+        self.signature = self.signature.filter_map(|info| {
+            Some(match cells.get(&info.generator) {
+                Some(diagram) => GeneratorInfo {
+                    diagram: diagram.clone().into(),
+                    ..info.clone()
+                },
+                None => info.clone(),
+            })
+        });
+
+        // The slices of the workspace may have moved.
+        if let Some(ws) = &mut self.workspace {
+            ws.path.clear();
+        }
+
+        Ok(true)
+    }
+
     /// Handler for [Action::SuspendSignature].
     fn suspend_signature(&mut self) -> bool {
+        if !self.signature.has_generators() {
+            return false;
+        }
+
         // New generators need to be fresh
         let id = self.signature.next_generator_id();
         let source = Generator::new(id, 0);
@@ -893,18 +1432,11 @@ impl ProofState {
 
     /// Handler for [Action::Suspend].
     fn suspend(&mut self, source: Generator, target: Generator) -> bool {
-        self.signature = self.signature.filter_map(|info| {
-            if info.generator == source || info.generator == target {
-                Some(info.clone())
-            } else {
-                Some(GeneratorInfo {
-                    generator: info.generator.suspended(),
-                    diagram: info.diagram.suspend(source, target).into(),
-                    oriented: false,
-                    ..info.clone()
-                })
-            }
-        });
+        if !self.signature.has_generators() {
+            return false;
+        }
+
+        self.signature = self.signature.suspend(source, target);
 
         for diagram in self.diagrams_mut() {
             *diagram = diagram.suspend(source, target).into();
@@ -930,14 +1462,15 @@ impl ProofState {
             if info.generator == from {
                 None
             } else {
-                let (oriented, invertibility) = if info.generator == to {
+                let (info_oriented, invertibility) = if info.generator == to {
                     (oriented, invertibility)
                 } else {
                     (info.oriented, info.invertibility)
                 };
+                // Occurrences of the merged generator lose their framing wherever they appear.
                 Some(GeneratorInfo {
                     diagram: info.diagram.replace(from, to, oriented),
-                    oriented,
+                    oriented: info_oriented,
                     invertibility,
                     ..info.clone()
                 })
@@ -951,26 +1484,83 @@ impl ProofState {
         Ok(true)
     }
     /// Handler for [Action::ImportProof].
-    fn import_proof(&mut self, data: &SerializedData) -> Result<bool, ProofError> {
-        let ((signature, workspace), metadata) = serialize::deserialize(&data.0)
-            .or_else(|| migration::deserialize(&data.0))
+    ///
+    /// Returns the history saved with the proof, unless some snapshot of it is malformed, in
+    /// which case the history is dropped. Like the proof itself, snapshots are not typechecked.
+    pub fn import_proof(&mut self, data: &SerializedData) -> Result<Option<History>, ProofError> {
+        let (proof, history) = serialize::deserialize_session(&data.0)
+            .or_else(|| {
+                let ((signature, workspace), metadata) = migration::deserialize(&data.0)?;
+                let proof = Self {
+                    signature,
+                    workspace,
+                    metadata,
+                    ..Default::default()
+                };
+                Some((proof, None))
+            })
             .ok_or(ProofError::Import)?;
-        for info in signature.iter() {
-            info.diagram
-                .check(true)
-                .map_err(|_err| ProofError::Import)?;
-        }
-        if let Some(workspace) = workspace.as_ref() {
-            workspace
-                .diagram
-                .check(true)
-                .map_err(|_err| ProofError::Import)?;
+        let diagrams = proof
+            .signature
+            .iter()
+            .map(|info| &info.diagram)
+            .chain(proof.diagrams());
+        for diagram in diagrams {
+            diagram.check(true).map_err(|_err| ProofError::Import)?;
         }
-        self.signature = signature;
-        self.workspace = workspace;
-        self.metadata = metadata;
-        self.boundary = None;
-        self.stash = Vector::new();
+        *self = proof;
+        Ok(history.filter(|history| {
+            history.snapshots().iter().all(|(_, snapshot)| {
+                snapshot
+                    .signature
+                    .iter()
+                    .map(|info| &info.diagram)
+                    .chain(snapshot.diagrams())
+                    .all(|diagram| diagram.check(true).is_ok())
+            })
+        }))
+    }
+
+    /// Transports the workspace, boundary and stash along a signature morphism into the given
+    /// target signature.
+    pub fn transport(
+        &self,
+        morphism: &SignatureMorphism,
+        signature: Signature,
+    ) -> Result<Self, MorphismError> {
+        let transport = |ws: &Workspace| -> Result<Workspace, MorphismError> {
+            Ok(Workspace {
+                view: ws.view,
+                diagram: morphism.apply(&ws.diagram)?,
+                path: Default::default(),
+            })
+        };
+
+        Ok(Self {
+            signature,
+            workspace: self.workspace.as_ref().map(transport).transpose()?,
+            metadata: self.metadata.clone(),
+            boundary: self
+                .boundary
+                .as_ref()
+                .map(|selected| {
+                    Ok::<_, MorphismError>(SelectedBoundary {
+                        boundary: selected.boundary,
+                        diagram: morphism.apply(&selected.diagram)?,
+                    })
+                })
+                .transpose()?,
+            stash: self.stash.iter().map(transport).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Handler for [Action::ApplyMorphism].
+    fn apply_morphism(&mut self, data: &SerializedData) -> Result<bool, ProofError> {
+        let ((signature, _), _) = serialize::deserialize(&data.0)
+            .or_else(|| migration::deserialize(&data.0))
+            .ok_or(ProofError::Import)?;
+        let morphism = self.signature.morphism_by_name(&signature)?;
+        *self = self.transport(&morphism, signature)?;
         Ok(true)
     }
 
@@ -1123,6 +1713,24 @@ pub struct AttachOption {
     pub diagram: DiagramN,
 }
 
+/// An outcome of a homotopy in the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomotopyOption {
+    /// The homotopy which produces the outcome.
+    pub homotopy: Homotopy,
+    /// The workspace after the homotopy.
+    pub workspace: Workspace,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BubbleOption {
+    pub diagram: DiagramN,
+    pub embedding: Vector<usize>,
+    /// Whether to cancel an occurrence of the diagram followed by its inverse, rather than
+    /// insert one.
+    pub cancel: bool,
+}
+
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SerializedData(pub Vec<u8>);
 
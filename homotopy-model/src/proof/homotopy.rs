@@ -4,6 +4,9 @@ use homotopy_core::{
 };
 use serde::{Deserialize, Serialize};
 
+/// The most outcomes of a homotopy which are offered to choose from.
+pub const MAX_OUTCOMES: usize = 8;
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Contract {
     pub height: SingularHeight,
@@ -15,9 +18,16 @@ pub struct Contract {
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Expand {
-    pub point: [Height; 2],
+    /// A point in the projection of the slice at the location, outermost coordinate first.
+    pub point: Vec<Height>,
+    /// The axis of the projection along which the point moves.
+    #[serde(default)]
+    pub axis: usize,
     pub direction: Direction,
     pub location: Vec<SliceIndex>,
+    /// Which of the outcomes of the expansion to take, in the order they are enumerated.
+    #[serde(default)]
+    pub choice: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
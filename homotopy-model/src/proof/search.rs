@@ -0,0 +1,241 @@
+//! Bounded search for homotopies between diagrams.
+//!
+//! Starting from a source diagram, the search explores all single-step contractions and
+//! expansions, in any slice of the diagram, in breadth-first order. When the target diagram is
+//! reached, the sequence of moves is returned as a list of [`Action`]s which can be replayed on a
+//! workspace showing the source diagram.
+
+use std::collections::VecDeque;
+
+use homotopy_common::hash::FastHashMap;
+use homotopy_core::{
+    common::{Boundary, Direction, Height, SliceIndex},
+    contraction::Bias,
+    normalization::components,
+    signature::Signature,
+    Diagram, DiagramN,
+};
+use thiserror::Error;
+
+use super::{
+    homotopy::{Contract, Expand, Homotopy},
+    Action,
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SearchOptions {
+    /// The maximum number of moves in a homotopy.
+    pub max_depth: usize,
+    /// The maximum number of distinct diagrams to visit before giving up.
+    pub max_states: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            max_states: 10_000,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error("the diagrams have different dimensions")]
+    Dimension,
+
+    #[error("the diagrams have different boundaries")]
+    Boundary,
+
+    #[error("no homotopy found within {0} moves")]
+    NotFound(usize),
+
+    #[error("gave up after visiting {0} diagrams")]
+    Exhausted(usize),
+}
+
+/// Searches for a sequence of contractions and expansions turning `source` into `target`.
+///
+/// The returned actions are relative to the visible diagram of a workspace, so replaying them
+/// with [`ProofState::update`](super::ProofState::update) on a workspace whose visible diagram is
+/// `source` makes the visible diagram equal to `target`. Since the search is breadth-first, the
+/// homotopy found is one with the fewest moves.
+pub fn search(
+    source: &Diagram,
+    target: &Diagram,
+    signature: &impl Signature,
+    options: SearchOptions,
+) -> Result<Vec<Action>, SearchError> {
+    if source.dimension() != target.dimension() {
+        return Err(SearchError::Dimension);
+    }
+
+    if let (Diagram::DiagramN(source), Diagram::DiagramN(target)) = (source, target) {
+        // Homotopies fix the boundary.
+        if source.source() != target.source() || source.target() != target.target() {
+            return Err(SearchError::Boundary);
+        }
+    }
+
+    if source == target {
+        return Ok(vec![]);
+    }
+
+    // For every visited diagram, the diagram it was reached from and the move that reached it.
+    let mut visited: FastHashMap<Diagram, Option<(Diagram, Homotopy)>> = FastHashMap::default();
+    visited.insert(source.clone(), None);
+
+    let mut queue = VecDeque::from([(source.clone(), 0)]);
+    while let Some((diagram, depth)) = queue.pop_front() {
+        if depth == options.max_depth {
+            continue;
+        }
+
+        for (homotopy, next) in moves(&diagram, signature) {
+            if visited.contains_key(&next) {
+                continue;
+            }
+            if visited.len() == options.max_states {
+                return Err(SearchError::Exhausted(visited.len()));
+            }
+            visited.insert(next.clone(), Some((diagram.clone(), homotopy)));
+            if &next == target {
+                return Ok(reconstruct(&visited, next));
+            }
+            queue.push_back((next, depth + 1));
+        }
+    }
+
+    Err(SearchError::NotFound(options.max_depth))
+}
+
+fn reconstruct(
+    visited: &FastHashMap<Diagram, Option<(Diagram, Homotopy)>>,
+    mut diagram: Diagram,
+) -> Vec<Action> {
+    let mut actions = Vec::new();
+    while let Some((previous, homotopy)) = &visited[&diagram] {
+        actions.push(Action::Homotopy(homotopy.clone()));
+        diagram = previous.clone();
+    }
+    actions.reverse();
+    actions
+}
+
+/// All single-step homotopies of a diagram, together with their results.
+fn moves(diagram: &Diagram, signature: &impl Signature) -> Vec<(Homotopy, Diagram)> {
+    let mut moves = Vec::new();
+
+    for (location, slice) in locations(diagram) {
+        let Diagram::DiagramN(slice) = slice else {
+            continue;
+        };
+        let location_index: Vec<SliceIndex> = location.iter().copied().map(Into::into).collect();
+
+        for height in 0..slice.size().saturating_sub(1) {
+            for bias in [Bias::Lower, Bias::Higher, Bias::Same] {
+                let homotopy = Contract {
+                    height,
+                    direction: Direction::Forward,
+                    step: 1,
+                    bias: Some(bias),
+                    location: location_index.clone(),
+                };
+                if let Some(result) = contract(diagram, &location, &homotopy, signature) {
+                    moves.push((Homotopy::Contract(homotopy), result));
+                }
+            }
+        }
+
+        if slice.dimension() < 2 {
+            continue;
+        }
+
+        for (height, cospan) in slice.cospans().iter().enumerate() {
+            let components = components(cospan);
+            if components.len() < 2 {
+                continue;
+            }
+            for component in components {
+                for direction in [Direction::Forward, Direction::Backward] {
+                    let homotopy = Expand {
+                        point: vec![Height::Singular(height), Height::Singular(component)],
+                        axis: 0,
+                        direction,
+                        location: location_index.clone(),
+                        choice: 0,
+                    };
+                    if let Some(result) = expand(diagram, &location, &homotopy, signature) {
+                        moves.push((Homotopy::Expand(homotopy), result));
+                    }
+                }
+            }
+        }
+    }
+
+    moves
+}
+
+/// All interior slices of a diagram, including the diagram itself, with their locations.
+fn locations(diagram: &Diagram) -> Vec<(Vec<Height>, Diagram)> {
+    let mut locations = vec![(vec![], diagram.clone())];
+    let mut i = 0;
+    while i < locations.len() {
+        if let Diagram::DiagramN(slice) = &locations[i].1 {
+            // Slices of dimension 0 admit no homotopies.
+            if slice.dimension() > 1 {
+                let slices: Vec<_> = Height::for_size(slice.size()).zip(slice.slices()).collect();
+                for (height, slice) in slices {
+                    let mut location = locations[i].0.clone();
+                    location.push(height);
+                    locations.push((location, slice));
+                }
+            }
+        }
+        i += 1;
+    }
+    locations
+}
+
+fn contract(
+    diagram: &Diagram,
+    location: &[Height],
+    homotopy: &Contract,
+    signature: &impl Signature,
+) -> Option<Diagram> {
+    let result = diagram
+        .clone()
+        .identity()
+        .contract(
+            Boundary::Target.into(),
+            &mut location.to_vec(),
+            homotopy.height,
+            homotopy.direction,
+            homotopy.step,
+            homotopy.bias,
+            signature,
+        )
+        .ok()?;
+    Some(result.target())
+}
+
+fn expand(
+    diagram: &Diagram,
+    location: &[Height],
+    homotopy: &Expand,
+    signature: &impl Signature,
+) -> Option<Diagram> {
+    let result: DiagramN = diagram
+        .clone()
+        .identity()
+        .expand_along(
+            Boundary::Target.into(),
+            &mut location.to_vec(),
+            &homotopy.point,
+            homotopy.axis,
+            homotopy.direction,
+            signature,
+        )
+        .ok()?;
+    Some(result.target())
+}
@@ -1,8 +1,12 @@
 use std::{collections::VecDeque, str::FromStr};
 
-use homotopy_common::tree::{Node, Tree};
+use homotopy_common::{
+    hash::FastHashMap,
+    tree::{Node, Tree},
+};
 use homotopy_core::{
     diagram::NewDiagramError,
+    morphism::{MorphismError, SignatureMorphism},
     signature::{Invertibility, Signature as _},
     typecheck::{typecheck, Mode},
     Diagram, Diagram0, DiagramN, Generator,
@@ -116,6 +120,23 @@ impl Signature {
         }))
     }
 
+    /// Suspends every generator apart from the 0-cells `source` and `target`.
+    #[must_use]
+    pub fn suspend(&self, source: Generator, target: Generator) -> Self {
+        self.filter_map(|info| {
+            if info.generator == source || info.generator == target {
+                Some(info.clone())
+            } else {
+                Some(GeneratorInfo {
+                    generator: info.generator.suspended(),
+                    diagram: info.diagram.suspend(source, target).into(),
+                    oriented: false,
+                    ..info.clone()
+                })
+            }
+        })
+    }
+
     pub(crate) fn insert(
         &mut self,
         generator: Generator,
@@ -190,6 +211,32 @@ impl Signature {
         })
     }
 
+    /// The morphism into another signature which sends each generator to the generator of the
+    /// same name and dimension, if there is exactly one.
+    pub fn morphism_by_name(&self, target: &Self) -> Result<SignatureMorphism, MorphismError> {
+        let mut candidates: FastHashMap<(&str, usize), Vec<&Diagram>> = FastHashMap::default();
+        for info in target.iter() {
+            candidates
+                .entry((&info.name, info.generator.dimension))
+                .or_default()
+                .push(&info.diagram);
+        }
+
+        let images = self
+            .iter()
+            .filter_map(|info| {
+                match candidates
+                    .get(&(info.name.as_str(), info.generator.dimension))?
+                    .as_slice()
+                {
+                    [image] => Some((info.generator, (*image).clone())),
+                    _ => None,
+                }
+            })
+            .collect();
+        SignatureMorphism::new(self, target, images)
+    }
+
     pub fn create_generator_zero(&mut self, name: &str) -> Diagram0 {
         let id = self.next_generator_id();
         let generator = Generator::new(id, 0);
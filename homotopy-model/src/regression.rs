@@ -0,0 +1,224 @@
+//! Regression checks over a corpus of `.hom` files.
+//!
+//! Every file is deserialized (migrating from the legacy format if necessary), all of its
+//! diagrams are deep-checked and typechecked, and the proof is re-serialized to make sure that it
+//! survives a round trip through the current format.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use homotopy_core::{
+    typecheck::{typecheck, Mode},
+    Diagram,
+};
+use instant::Instant;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    migration,
+    proof::{Metadata, Signature, Workspace},
+    serialize,
+};
+
+#[derive(Debug, Clone, Error, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Failure {
+    #[error("could not read file: {message}")]
+    Read { message: String },
+
+    #[error("could not deserialize file in either the current or the legacy format")]
+    Deserialize,
+
+    #[error("{name} is malformed: {message}")]
+    Malformed { name: String, message: String },
+
+    #[error("{name} is ill-typed: {message}")]
+    IllTyped { name: String, message: String },
+
+    #[error("round trip failed: {message}")]
+    RoundTrip { message: String },
+}
+
+/// Time spent in each stage of checking a file, serialized in milliseconds.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Timings {
+    #[serde(serialize_with = "as_millis")]
+    pub deserialize: Duration,
+    #[serde(serialize_with = "as_millis")]
+    pub check: Duration,
+    #[serde(serialize_with = "as_millis")]
+    pub typecheck: Duration,
+    #[serde(serialize_with = "as_millis")]
+    pub round_trip: Duration,
+}
+
+impl Timings {
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.deserialize + self.check + self.typecheck + self.round_trip
+    }
+}
+
+fn as_millis<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReport {
+    pub path: PathBuf,
+    /// Whether the file had to be migrated from the legacy format.
+    pub legacy: bool,
+    /// The number of diagrams checked, including the workspace.
+    pub diagrams: usize,
+    pub timings: Timings,
+    pub failures: Vec<Failure>,
+}
+
+impl FileReport {
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+type Proof = ((Signature, Option<Workspace>), Metadata);
+
+/// Run all regression checks on a single `.hom` file.
+#[must_use]
+pub fn check_file(path: &Path) -> FileReport {
+    let mut report = FileReport {
+        path: path.to_owned(),
+        legacy: false,
+        diagrams: 0,
+        timings: Default::default(),
+        failures: Vec::new(),
+    };
+
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            report.failures.push(Failure::Read {
+                message: e.to_string(),
+            });
+            return report;
+        }
+    };
+
+    let start = Instant::now();
+    let proof = serialize::deserialize(&data).or_else(|| {
+        report.legacy = true;
+        migration::deserialize(&data)
+    });
+    report.timings.deserialize = start.elapsed();
+    let Some(proof) = proof else {
+        report.failures.push(Failure::Deserialize);
+        return report;
+    };
+    let ((signature, workspace), _) = &proof;
+
+    let mut diagrams: Vec<(String, &Diagram)> = signature
+        .iter()
+        .map(|info| (info.name.clone(), &info.diagram))
+        .collect();
+    if let Some(ws) = workspace {
+        diagrams.push(("workspace".to_owned(), &ws.diagram));
+    }
+    report.diagrams = diagrams.len();
+
+    let start = Instant::now();
+    let mut well_formed = Vec::with_capacity(diagrams.len());
+    for (name, diagram) in &diagrams {
+        match diagram.check(true) {
+            Ok(()) => well_formed.push((name, diagram)),
+            Err(errors) => report
+                .failures
+                .extend(errors.iter().map(|e| Failure::Malformed {
+                    name: name.clone(),
+                    message: e.to_string(),
+                })),
+        }
+    }
+    report.timings.check = start.elapsed();
+
+    // Typechecking assumes a well-formed diagram.
+    let start = Instant::now();
+    for (name, diagram) in well_formed {
+        if let Err(e) = typecheck(diagram, signature, Mode::default(), true) {
+            report.failures.push(Failure::IllTyped {
+                name: name.clone(),
+                message: e.to_string(),
+            });
+        }
+    }
+    report.timings.typecheck = start.elapsed();
+
+    let start = Instant::now();
+    if let Err(message) = round_trip(&proof) {
+        report.failures.push(Failure::RoundTrip { message });
+    }
+    report.timings.round_trip = start.elapsed();
+
+    report
+}
+
+/// Run all regression checks on every `.hom` file in a directory, in lexicographic order.
+pub fn check_dir(dir: &Path) -> std::io::Result<Vec<FileReport>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "hom") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(paths.iter().map(|path| check_file(path)).collect())
+}
+
+/// Serialize a proof in the current format, read it back and compare the result with the
+/// original.
+fn round_trip(proof: &Proof) -> Result<(), String> {
+    let ((signature, workspace), metadata) = proof;
+    let data = serialize::serialize(signature.clone(), workspace.clone(), metadata.clone());
+    let ((new_signature, new_workspace), new_metadata) =
+        serialize::deserialize(&data).ok_or("could not deserialize re-serialized file")?;
+
+    if new_metadata != *metadata {
+        return Err("metadata differs".to_owned());
+    }
+
+    let old: Vec<_> = signature.iter().collect();
+    let new: Vec<_> = new_signature.iter().collect();
+    if old.len() != new.len() {
+        return Err(format!(
+            "expected {} generators, found {}",
+            old.len(),
+            new.len()
+        ));
+    }
+    for (old, new) in old.into_iter().zip(new) {
+        if old.generator != new.generator
+            || old.name != new.name
+            || old.color != new.color
+            || old.oriented != new.oriented
+            || old.invertibility.is_invertible() != new.invertibility.is_invertible()
+            || old.diagram != new.diagram
+        {
+            return Err(format!("generator {} differs", old.name));
+        }
+    }
+
+    match (workspace, new_workspace) {
+        (None, None) => Ok(()),
+        (Some(old), Some(new))
+            if old.diagram == new.diagram && old.path == new.path && old.view == new.view =>
+        {
+            Ok(())
+        }
+        _ => Err("workspace differs".to_owned()),
+    }
+}
@@ -1,6 +1,6 @@
-use homotopy_common::tree::Tree;
+use homotopy_common::tree::{Node, Tree};
 use homotopy_core::{
-    common::{Generator, SliceIndex},
+    common::{Boundary, Generator, SliceIndex},
     serialize::{Key, Store},
     Diagram,
 };
@@ -8,8 +8,12 @@ use homotopy_graphics::style::Color;
 use im::Vector;
 use obake::AnyVersion;
 
-use super::proof::{
-    generators::GeneratorInfo, FolderInfo, Metadata, Signature, SignatureItem, View, Workspace,
+use super::{
+    history::{History, Snapshot},
+    proof::{
+        generators::GeneratorInfo, Action, FolderInfo, Metadata, ProofState, SelectedBoundary,
+        Signature, SignatureItem, View, Workspace,
+    },
 };
 
 #[obake::versioned]
@@ -25,6 +29,7 @@ struct WorkspaceData {
 
 #[obake::versioned]
 #[obake(version("0.1.3"))]
+#[obake(version("0.1.4"))]
 #[obake(derive(serde::Serialize, serde::Deserialize))]
 #[obake(serde(tag = "version"))]
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -33,6 +38,21 @@ struct Data {
     signature: Tree<SignatureData>,
     workspace: Option<WorkspaceData>,
     metadata: Metadata,
+    #[obake(cfg(">=0.1.4"))]
+    #[serde(default)]
+    session: Option<SessionData>,
+}
+
+impl From<Data!["0.1.3"]> for Data!["0.1.4"] {
+    fn from(data: Data!["0.1.3"]) -> Self {
+        Self {
+            store: data.store,
+            signature: data.signature,
+            workspace: data.workspace,
+            metadata: data.metadata,
+            session: None,
+        }
+    }
 }
 
 impl std::fmt::Debug for Data {
@@ -41,6 +61,41 @@ impl std::fmt::Debug for Data {
     }
 }
 
+/// The state of the session in which a proof was saved, beyond the proof itself.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct SessionData {
+    boundary: Option<BoundaryData>,
+    stash: Vector<WorkspaceData>,
+    history: Option<HistoryData>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct BoundaryData {
+    boundary: Boundary,
+    diagram: Key<Diagram>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct HistoryData {
+    /// The distinct signatures of the snapshots, which are mostly shared between them.
+    signatures: Vec<Tree<SignatureData>>,
+    snapshots: Tree<SnapshotData>,
+    current: Node,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct SnapshotData {
+    action: Option<Action>,
+    navigation: Vec<Action>,
+    origin: Option<WorkspaceData>,
+    /// The index of the signature in [HistoryData::signatures].
+    signature: usize,
+    workspace: Option<WorkspaceData>,
+    metadata: Metadata,
+    boundary: Option<BoundaryData>,
+    stash: Vector<WorkspaceData>,
+}
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 enum SignatureData {
     Folder(String, bool),
@@ -69,44 +124,90 @@ pub fn serialize(
     workspace: Option<Workspace>,
     metadata: Metadata,
 ) -> Vec<u8> {
-    let mut data = Data {
-        store: Default::default(),
-        signature: Default::default(),
-        workspace: Default::default(),
+    let mut store = Store::default();
+    let data = Data {
+        signature: pack_signature(&mut store, signature),
+        workspace: workspace.map(|workspace| pack_workspace(&mut store, &workspace)),
         metadata,
+        session: None,
+        store,
     };
+    encode(data)
+}
 
-    let mut signature = signature.into_tree();
-    // Remove noise from signature tree
-    signature.clean_up();
-    // Pack signature data
-    data.signature = signature.map(|item| match item {
-        SignatureItem::Folder(info) => SignatureData::Folder(info.name, info.open),
-        SignatureItem::Item(info) => SignatureData::Item(GeneratorData {
-            generator: info.generator,
-            diagram: data.store.pack_diagram(&info.diagram),
-            name: info.name,
-            color: info.color,
-            oriented: info.oriented,
-            invertible: info.invertibility.is_invertible(),
-        }),
-    });
+/// Serializes a proof together with its selected boundary and stash, and optionally the history
+/// which led to it, so that the session can be restored when the file is reopened.
+#[must_use]
+pub fn serialize_session(proof: &ProofState, history: Option<&History>) -> Vec<u8> {
+    let mut store = Store::default();
+    let session = SessionData {
+        boundary: proof
+            .boundary
+            .as_ref()
+            .map(|boundary| pack_boundary(&mut store, boundary)),
+        stash: pack_stash(&mut store, &proof.stash),
+        history: history.map(|history| pack_history(&mut store, history)),
+    };
+    let data = Data {
+        signature: pack_signature(&mut store, proof.signature.clone()),
+        workspace: proof
+            .workspace
+            .as_ref()
+            .map(|workspace| pack_workspace(&mut store, workspace)),
+        metadata: proof.metadata.clone(),
+        session: Some(session),
+        store,
+    };
+    encode(data)
+}
 
-    if let Some(workspace) = workspace {
-        data.workspace = Some(WorkspaceData {
-            diagram: data.store.pack_diagram(&workspace.diagram),
-            path: workspace.path,
-            view: workspace.view,
-        });
-    }
+pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metadata)> {
+    let data = decode(data)?;
+    let mut store = data.store;
+
+    let signature = unpack_signature(&mut store, data.signature)?;
+    let workspace = unpack_optional(data.workspace, |workspace| {
+        unpack_workspace(&mut store, workspace)
+    })?;
+    Some(((signature, workspace), data.metadata))
+}
+
+/// Deserializes a proof together with the selected boundary and stash it was saved with, and
+/// the history which led to it if that was saved too.
+pub fn deserialize_session(data: &[u8]) -> Option<(ProofState, Option<History>)> {
+    let data = decode(data)?;
+    let mut store = data.store;
+
+    let mut proof = ProofState {
+        signature: unpack_signature(&mut store, data.signature)?,
+        workspace: unpack_optional(data.workspace, |workspace| {
+            unpack_workspace(&mut store, workspace)
+        })?,
+        metadata: data.metadata,
+        ..Default::default()
+    };
+    let Some(session) = data.session else {
+        return Some((proof, None));
+    };
+    proof.boundary = unpack_optional(session.boundary, |boundary| {
+        unpack_boundary(&mut store, boundary)
+    })?;
+    proof.stash = unpack_stash(&mut store, session.stash)?;
 
+    let history = unpack_optional(session.history, |history| {
+        unpack_history(&mut store, history)
+    })?;
+    Some((proof, history))
+}
+
+fn encode(data: Data) -> Vec<u8> {
     // Tag data with version
     let data: AnyVersion<Data> = data.into();
     // Serialize
     rmp_serde::encode::to_vec_named(&data).unwrap()
 }
 
-pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metadata)> {
+fn decode(data: &[u8]) -> Option<Data> {
     // Deserialize with version tag
     let data: AnyVersion<Data> = match rmp_serde::decode::from_slice(data) {
         Err(error) => {
@@ -116,46 +217,188 @@ pub fn deserialize(data: &[u8]) -> Option<((Signature, Option<Workspace>), Metad
         Ok(data) => Some(data),
     }?;
     // Migrate to current version
-    let data: Data = data.into();
-    let mut store = data.store;
+    Some(data.into())
+}
+
+/// Unpacks an optional piece of data, failing only if it is present but can not be unpacked.
+fn unpack_optional<T, U>(
+    data: Option<T>,
+    unpack: impl FnOnce(T) -> Option<U>,
+) -> Option<Option<U>> {
+    data.map_or(Some(None), |data| unpack(data).map(Some))
+}
+
+fn pack_signature(store: &mut Store, signature: Signature) -> Tree<SignatureData> {
+    let mut signature = signature.into_tree();
+    // Remove noise from signature tree
+    signature.clean_up();
+    // Pack signature data
+    signature.map(|item| match item {
+        SignatureItem::Folder(info) => SignatureData::Folder(info.name, info.open),
+        SignatureItem::Item(info) => SignatureData::Item(GeneratorData {
+            generator: info.generator,
+            diagram: store.pack_diagram(&info.diagram),
+            name: info.name,
+            color: info.color,
+            oriented: info.oriented,
+            invertible: info.invertibility.is_invertible(),
+        }),
+    })
+}
 
+fn unpack_signature(store: &mut Store, signature: Tree<SignatureData>) -> Option<Signature> {
     let mut folder_index = 0;
-    let signature = data
-        .signature
-        .map(|s| {
-            Some(match s {
-                SignatureData::Folder(name, open) => {
-                    folder_index += 1;
-                    SignatureItem::Folder(FolderInfo {
-                        id: folder_index,
-                        name,
-                        open: open.to_owned(),
-                    })
-                }
-                SignatureData::Item(gd) => SignatureItem::Item(GeneratorInfo {
-                    generator: gd.generator,
-                    name: gd.name,
-                    color: gd.color,
-                    shape: Default::default(),
-                    diagram: store.unpack_diagram(gd.diagram)?,
-                    oriented: gd.oriented,
-                    // TODO: `invertibility` should be properly serialized
-                    invertibility: gd.invertible.into(),
-                    // TODO: `single_preview` should be properly serialized
-                    single_preview: true,
-                }),
+    Some(
+        signature
+            .map(|s| {
+                Some(match s {
+                    SignatureData::Folder(name, open) => {
+                        folder_index += 1;
+                        SignatureItem::Folder(FolderInfo {
+                            id: folder_index,
+                            name,
+                            open: open.to_owned(),
+                        })
+                    }
+                    SignatureData::Item(gd) => SignatureItem::Item(GeneratorInfo {
+                        generator: gd.generator,
+                        name: gd.name,
+                        color: gd.color,
+                        shape: Default::default(),
+                        diagram: store.unpack_diagram(gd.diagram)?,
+                        oriented: gd.oriented,
+                        // TODO: `invertibility` should be properly serialized
+                        invertibility: gd.invertible.into(),
+                        // TODO: `single_preview` should be properly serialized
+                        single_preview: true,
+                    }),
+                })
             })
-        })
-        .transpose()?
-        .into();
-
-    let mut workspace = None;
-    if let Some(workspace_data) = data.workspace {
-        workspace = Some(Workspace {
-            diagram: store.unpack_diagram(workspace_data.diagram)?,
-            path: workspace_data.path,
-            view: workspace_data.view,
-        });
+            .transpose()?
+            .into(),
+    )
+}
+
+fn pack_workspace(store: &mut Store, workspace: &Workspace) -> WorkspaceData {
+    WorkspaceData {
+        diagram: store.pack_diagram(&workspace.diagram),
+        path: workspace.path.clone(),
+        view: workspace.view,
     }
-    Some(((signature, workspace), data.metadata))
+}
+
+fn unpack_workspace(store: &mut Store, workspace: WorkspaceData) -> Option<Workspace> {
+    Some(Workspace {
+        diagram: store.unpack_diagram(workspace.diagram)?,
+        path: workspace.path,
+        view: workspace.view,
+    })
+}
+
+fn pack_stash(store: &mut Store, stash: &Vector<Workspace>) -> Vector<WorkspaceData> {
+    stash
+        .iter()
+        .map(|workspace| pack_workspace(store, workspace))
+        .collect()
+}
+
+fn unpack_stash(store: &mut Store, stash: Vector<WorkspaceData>) -> Option<Vector<Workspace>> {
+    stash
+        .into_iter()
+        .map(|workspace| unpack_workspace(store, workspace))
+        .collect()
+}
+
+fn pack_boundary(store: &mut Store, boundary: &SelectedBoundary) -> BoundaryData {
+    BoundaryData {
+        boundary: boundary.boundary,
+        diagram: store.pack_diagram(&boundary.diagram),
+    }
+}
+
+fn unpack_boundary(store: &mut Store, boundary: BoundaryData) -> Option<SelectedBoundary> {
+    Some(SelectedBoundary {
+        boundary: boundary.boundary,
+        diagram: store.unpack_diagram(boundary.diagram)?,
+    })
+}
+
+fn pack_history(store: &mut Store, history: &History) -> HistoryData {
+    let mut distinct: Vec<Signature> = vec![];
+    let mut signatures = vec![];
+    let snapshots = history.snapshots().map_ref(|snapshot| {
+        let signature = distinct
+            .iter()
+            .position(|signature| *signature == snapshot.signature)
+            .unwrap_or_else(|| {
+                distinct.push(snapshot.signature.clone());
+                signatures.push(pack_signature(store, snapshot.signature.clone()));
+                signatures.len() - 1
+            });
+        pack_snapshot(store, snapshot, signature)
+    });
+    HistoryData {
+        signatures,
+        snapshots,
+        current: history.current(),
+    }
+}
+
+fn unpack_history(store: &mut Store, history: HistoryData) -> Option<History> {
+    let signatures = history
+        .signatures
+        .into_iter()
+        .map(|signature| unpack_signature(store, signature))
+        .collect::<Option<Vec<_>>>()?;
+    let snapshots = history
+        .snapshots
+        .map(|snapshot| unpack_snapshot(store, &signatures, snapshot))
+        .transpose()?;
+    History::from_snapshots(snapshots, history.current)
+}
+
+fn pack_snapshot(store: &mut Store, proof: &Snapshot, signature: usize) -> SnapshotData {
+    SnapshotData {
+        action: proof.action().cloned(),
+        navigation: proof.navigation.clone(),
+        origin: proof
+            .origin
+            .as_ref()
+            .map(|workspace| pack_workspace(store, workspace)),
+        signature,
+        workspace: proof
+            .workspace
+            .as_ref()
+            .map(|workspace| pack_workspace(store, workspace)),
+        metadata: proof.metadata.clone(),
+        boundary: proof
+            .boundary
+            .as_ref()
+            .map(|boundary| pack_boundary(store, boundary)),
+        stash: pack_stash(store, &proof.stash),
+    }
+}
+
+fn unpack_snapshot(
+    store: &mut Store,
+    signatures: &[Signature],
+    snapshot: SnapshotData,
+) -> Option<Snapshot> {
+    let proof = ProofState {
+        signature: signatures.get(snapshot.signature)?.clone(),
+        workspace: unpack_optional(snapshot.workspace, |workspace| {
+            unpack_workspace(store, workspace)
+        })?,
+        metadata: snapshot.metadata,
+        boundary: unpack_optional(snapshot.boundary, |boundary| {
+            unpack_boundary(store, boundary)
+        })?,
+        stash: unpack_stash(store, snapshot.stash)?,
+    };
+    let mut result = Snapshot::new(snapshot.action, proof);
+    result.navigation = snapshot.navigation;
+    result.origin = unpack_optional(snapshot.origin, |workspace| {
+        unpack_workspace(store, workspace)
+    })?;
+    Some(result)
 }
@@ -0,0 +1,767 @@
+//! A plain-text syntax for signatures and diagrams.
+//!
+//! A document is a sequence of statements, which declare generators, bind diagrams to names, or
+//! suspend everything declared so far:
+//!
+//! ```text
+//! # Comments run to the end of the line.
+//! generator x
+//! generator f : x -> x
+//! generator m : f ; f -> f
+//! generator u : id(x) -> f invertible
+//! let unit = u ; f ; m @ 0
+//! generator "base source"
+//! generator "base target"
+//! suspend "base source" "base target"
+//! ```
+//!
+//! Names are either identifiers or double-quoted strings. Expressions are built from names and
+//! the following operations:
+//!
+//! - `id(e)` is the identity on `e`;
+//! - `inv(e)` is the inverse of `e`;
+//! - `e ; e'` attaches `e'` to the target of `e`, and `e <; e'` attaches `e'` to its source.
+//!   Either can be followed by `@` and the embedding of `e'` into the boundary, which defaults to
+//!   all zeroes.
+//!
+//! Generators can be marked as `invertible`, `dualisable <n>` or `oriented`. The statement
+//! `suspend s t` suspends all generators and bound diagrams, using the 0-cells `s` and `t` as the
+//! new base.
+//!
+//! The printer emits a generator declaration for every generator, in order of dimension, and
+//! expresses every diagram as a composite of whiskered generators. This covers every diagram
+//! which was built by attachment, but not diagrams which have been modified by homotopies. The
+//! parser numbers generators in order of declaration, so printing and parsing again preserves
+//! diagrams up to a renumbering of their generators.
+
+use std::{fmt::Write as _, str::FromStr};
+
+use homotopy_common::hash::{FastHashMap, FastHashSet};
+use homotopy_core::{
+    diagram::{AttachmentError, NewDiagramError},
+    signature::Invertibility,
+    Boundary, Diagram, Diagram0, DiagramN, Generator, Orientation,
+};
+use homotopy_graphics::style::Color;
+use thiserror::Error;
+
+use crate::proof::{generators::GeneratorInfo, Signature, SignatureItem, COLORS};
+
+const KEYWORDS: &[&str] = &[
+    "generator",
+    "let",
+    "suspend",
+    "id",
+    "inv",
+    "invertible",
+    "dualisable",
+    "oriented",
+];
+
+/// A signature together with a sequence of named diagrams.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    pub signature: Signature,
+    pub diagrams: Vec<(String, Diagram)>,
+}
+
+impl Document {
+    /// The diagram bound to a name.
+    #[must_use]
+    pub fn diagram(&self, name: &str) -> Option<&Diagram> {
+        self.diagrams
+            .iter()
+            .find_map(|(n, diagram)| (n == name).then_some(diagram))
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{line}:{column}: {kind}")]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Error)]
+pub enum ParseErrorKind {
+    #[error("unexpected character {0:?}")]
+    UnexpectedCharacter(char),
+
+    #[error("unterminated string")]
+    UnterminatedString,
+
+    #[error("number is too large")]
+    NumberTooLarge,
+
+    #[error("expected {expected}, found {found}")]
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+
+    #[error("{0} is not defined")]
+    Undefined(String),
+
+    #[error("{0} is already defined")]
+    Redefined(String),
+
+    #[error("{0} is not a 0-cell generator")]
+    NotZeroCell(String),
+
+    #[error("cannot take the inverse of a 0-cell")]
+    Inverse,
+
+    #[error(transparent)]
+    Generator(#[from] NewDiagramError),
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+}
+
+#[derive(Debug, Error)]
+pub enum PrintError {
+    #[error("generator {0:?} is not in the signature")]
+    UnknownGenerator(Generator),
+
+    #[error("cannot print 0-cells with a non-positive orientation")]
+    Orientation,
+
+    #[error("level {height} of a {dimension}-diagram is not a whiskered generator")]
+    Level { dimension: usize, height: usize },
+}
+
+/// Parses a document.
+pub fn parse(input: &str) -> Result<Document, ParseError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        document: Document::default(),
+    };
+    while !parser.at(&Token::End) {
+        parser.statement()?;
+    }
+    Ok(parser.document)
+}
+
+/// Prints a document.
+pub fn print(document: &Document) -> Result<String, PrintError> {
+    let mut names = Names::new(&document.signature);
+
+    let mut generators: Vec<_> = document.signature.iter().collect();
+    generators.sort_by_key(|info| (info.generator.dimension, info.generator.id));
+
+    let mut output = String::new();
+    for info in generators {
+        write!(output, "generator {}", names.get(info.generator)?).unwrap();
+        if let Diagram::DiagramN(diagram) = &info.diagram {
+            write!(
+                output,
+                " : {} -> {}",
+                names.expression(&diagram.source(), &document.signature)?,
+                names.expression(&diagram.target(), &document.signature)?
+            )
+            .unwrap();
+        }
+        match info.invertibility {
+            Invertibility::Directed => {}
+            Invertibility::Invertible => output.push_str(" invertible"),
+            Invertibility::Dualisable(n) => write!(output, " dualisable {n}").unwrap(),
+        }
+        if info.oriented {
+            output.push_str(" oriented");
+        }
+        output.push('\n');
+    }
+
+    for (name, diagram) in &document.diagrams {
+        let name = names.fresh(name);
+        writeln!(
+            output,
+            "let {name} = {}",
+            names.expression(diagram, &document.signature)?
+        )
+        .unwrap();
+    }
+
+    Ok(output)
+}
+
+/// Prints a diagram as an expression in terms of the generators of a signature.
+pub fn print_diagram(diagram: &Diagram, signature: &Signature) -> Result<String, PrintError> {
+    Names::new(signature).expression(diagram, signature)
+}
+
+// Lexing
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Name(String),
+    Keyword(&'static str),
+    Number(usize),
+    Colon,
+    Arrow,
+    Semicolon,
+    SourceSemicolon,
+    At,
+    Equals,
+    LeftParen,
+    RightParen,
+    End,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{}", quote(name)),
+            Self::Keyword(keyword) => write!(f, "`{keyword}`"),
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Colon => f.write_str("`:`"),
+            Self::Arrow => f.write_str("`->`"),
+            Self::Semicolon => f.write_str("`;`"),
+            Self::SourceSemicolon => f.write_str("`<;`"),
+            Self::At => f.write_str("`@`"),
+            Self::Equals => f.write_str("`=`"),
+            Self::LeftParen => f.write_str("`(`"),
+            Self::RightParen => f.write_str("`)`"),
+            Self::End => f.write_str("end of input"),
+        }
+    }
+}
+
+struct Spanned {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_identifier(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '\''
+}
+
+fn tokenize(input: &str) -> Result<Vec<Spanned>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    macro_rules! bump {
+        () => {{
+            let c = chars.next();
+            if c == Some('\n') {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+            c
+        }};
+    }
+
+    while let Some(&c) = chars.peek() {
+        let (start_line, start_column) = (line, column);
+        let error = |kind| ParseError {
+            line: start_line,
+            column: start_column,
+            kind,
+        };
+
+        let token = match c {
+            c if c.is_whitespace() => {
+                bump!();
+                continue;
+            }
+            '#' => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    bump!();
+                }
+                continue;
+            }
+            '"' => {
+                bump!();
+                let mut name = String::new();
+                loop {
+                    match bump!() {
+                        None => return Err(error(ParseErrorKind::UnterminatedString)),
+                        Some('"') => break,
+                        Some('\\') => match bump!() {
+                            None => return Err(error(ParseErrorKind::UnterminatedString)),
+                            Some(c) => name.push(c),
+                        },
+                        Some(c) => name.push(c),
+                    }
+                }
+                Token::Name(name)
+            }
+            c if c.is_ascii_digit() => {
+                let mut n = 0usize;
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                    n = n
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(digit as usize))
+                        .ok_or_else(|| error(ParseErrorKind::NumberTooLarge))?;
+                    bump!();
+                }
+                Token::Number(n)
+            }
+            c if is_identifier_start(c) => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|&&c| is_identifier(c)) {
+                    name.push(c);
+                    bump!();
+                }
+                match KEYWORDS.iter().find(|&&keyword| keyword == name) {
+                    Some(keyword) => Token::Keyword(keyword),
+                    None => Token::Name(name),
+                }
+            }
+            _ => {
+                bump!();
+                match c {
+                    ':' => Token::Colon,
+                    ';' => Token::Semicolon,
+                    '@' => Token::At,
+                    '=' => Token::Equals,
+                    '(' => Token::LeftParen,
+                    ')' => Token::RightParen,
+                    '-' if chars.peek() == Some(&'>') => {
+                        bump!();
+                        Token::Arrow
+                    }
+                    '<' if chars.peek() == Some(&';') => {
+                        bump!();
+                        Token::SourceSemicolon
+                    }
+                    _ => return Err(error(ParseErrorKind::UnexpectedCharacter(c))),
+                }
+            }
+        };
+
+        tokens.push(Spanned {
+            token,
+            line: start_line,
+            column: start_column,
+        });
+    }
+
+    tokens.push(Spanned {
+        token: Token::End,
+        line,
+        column,
+    });
+    Ok(tokens)
+}
+
+// Parsing
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    position: usize,
+    document: Document,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].token
+    }
+
+    fn at(&self, token: &Token) -> bool {
+        self.peek() == token
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        let Spanned { line, column, .. } = self.tokens[self.position];
+        ParseError { line, column, kind }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        self.error(ParseErrorKind::Unexpected {
+            expected,
+            found: self.peek().to_string(),
+        })
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.position].token.clone();
+        if token != Token::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.at(token) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token, expected: &'static str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Token::Name(_) => match self.advance() {
+                Token::Name(name) => Ok(name),
+                _ => unreachable!(),
+            },
+            _ => Err(self.unexpected("a name")),
+        }
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        match self.peek() {
+            &Token::Number(n) => {
+                self.advance();
+                Some(n)
+            }
+            _ => None,
+        }
+    }
+
+    fn generator(&self, name: &str) -> Option<&GeneratorInfo> {
+        self.document
+            .signature
+            .iter()
+            .find(|info| info.name == name)
+    }
+
+    fn is_defined(&self, name: &str) -> bool {
+        self.generator(name).is_some() || self.document.diagram(name).is_some()
+    }
+
+    fn statement(&mut self) -> Result<(), ParseError> {
+        let statement = match self.peek() {
+            Token::Keyword("generator") => Self::generator_statement,
+            Token::Keyword("let") => Self::let_statement,
+            Token::Keyword("suspend") => Self::suspend_statement,
+            _ => return Err(self.unexpected("`generator`, `let` or `suspend`")),
+        };
+        self.advance();
+        statement(self)
+    }
+
+    fn generator_statement(&mut self) -> Result<(), ParseError> {
+        let name = self.name()?;
+        if self.is_defined(&name) {
+            self.position -= 1;
+            return Err(self.error(ParseErrorKind::Redefined(name)));
+        }
+
+        let id = self.document.signature.next_generator_id();
+        let (generator, diagram): (Generator, Diagram) = if self.eat(&Token::Colon) {
+            let source = self.expression()?;
+            self.expect(&Token::Arrow, "`->`")?;
+            let position = self.position;
+            let target = self.expression()?;
+            let generator = Generator::new(id, source.dimension() + 1);
+            let diagram = DiagramN::from_generator(generator, source, target).map_err(|e| {
+                self.position = position;
+                self.error(e.into())
+            })?;
+            (generator, diagram.into())
+        } else {
+            let generator = Generator::new(id, 0);
+            (generator, Diagram0::from(generator).into())
+        };
+
+        let mut invertibility = Invertibility::Directed;
+        let mut oriented = false;
+        loop {
+            match self.peek() {
+                Token::Keyword("invertible") => invertibility = Invertibility::Invertible,
+                Token::Keyword("dualisable") => {
+                    self.advance();
+                    let n = self.number().ok_or_else(|| self.unexpected("a number"))?;
+                    invertibility = Invertibility::Dualisable(n);
+                    continue;
+                }
+                Token::Keyword("oriented") => oriented = true,
+                _ => break,
+            }
+            self.advance();
+        }
+
+        self.document
+            .signature
+            .insert_item(SignatureItem::Item(GeneratorInfo {
+                generator,
+                oriented,
+                invertibility,
+                single_preview: true,
+                color: Color::from_str(COLORS[id % COLORS.len()]).unwrap(),
+                shape: Default::default(),
+                diagram,
+                name,
+            }));
+        Ok(())
+    }
+
+    fn let_statement(&mut self) -> Result<(), ParseError> {
+        let name = self.name()?;
+        if self.is_defined(&name) {
+            self.position -= 1;
+            return Err(self.error(ParseErrorKind::Redefined(name)));
+        }
+        self.expect(&Token::Equals, "`=`")?;
+        let diagram = self.expression()?;
+        self.document.diagrams.push((name, diagram));
+        Ok(())
+    }
+
+    fn suspend_statement(&mut self) -> Result<(), ParseError> {
+        let mut base = [Generator::new(0, 0); 2];
+        for generator in &mut base {
+            let name = self.name()?;
+            match self.generator(&name) {
+                Some(info) if info.generator.dimension == 0 => *generator = info.generator,
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error(ParseErrorKind::NotZeroCell(name)));
+                }
+            }
+        }
+
+        let [source, target] = base;
+        self.document.signature = self.document.signature.suspend(source, target);
+        for (_, diagram) in &mut self.document.diagrams {
+            *diagram = diagram.suspend(source, target).into();
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self) -> Result<Diagram, ParseError> {
+        let mut diagram = self.term()?;
+        loop {
+            let boundary = match self.peek() {
+                Token::Semicolon => Boundary::Target,
+                Token::SourceSemicolon => Boundary::Source,
+                _ => return Ok(diagram),
+            };
+            let position = self.position;
+            self.advance();
+            let attached = self.term()?;
+
+            let mut embedding = vec![];
+            if self.eat(&Token::At) {
+                while let Some(n) = self.number() {
+                    embedding.push(n);
+                }
+            }
+
+            let result = match (&diagram, attached) {
+                (Diagram::DiagramN(diagram), Diagram::DiagramN(attached)) => diagram
+                    .attach(&attached, boundary, &embedding)
+                    .map_err(ParseErrorKind::from),
+                _ => Err(ParseErrorKind::Attachment(
+                    AttachmentError::IncompatibleAttachment,
+                )),
+            };
+            diagram = result
+                .map_err(|kind| {
+                    self.position = position;
+                    self.error(kind)
+                })?
+                .into();
+        }
+    }
+
+    fn term(&mut self) -> Result<Diagram, ParseError> {
+        match self.peek().clone() {
+            Token::Name(name) => {
+                let diagram = self
+                    .generator(&name)
+                    .map(|info| info.diagram.clone())
+                    .or_else(|| self.document.diagram(&name).cloned())
+                    .ok_or_else(|| self.error(ParseErrorKind::Undefined(name)))?;
+                self.advance();
+                Ok(diagram)
+            }
+            Token::Keyword(keyword @ ("id" | "inv")) => {
+                let position = self.position;
+                self.advance();
+                self.expect(&Token::LeftParen, "`(`")?;
+                let diagram = self.expression()?;
+                self.expect(&Token::RightParen, "`)`")?;
+                if keyword == "id" {
+                    Ok(diagram.identity().into())
+                } else {
+                    match diagram {
+                        Diagram::DiagramN(diagram) => Ok(diagram.inverse().into()),
+                        Diagram::Diagram0(_) => {
+                            self.position = position;
+                            Err(self.error(ParseErrorKind::Inverse))
+                        }
+                    }
+                }
+            }
+            Token::LeftParen => {
+                self.advance();
+                let diagram = self.expression()?;
+                self.expect(&Token::RightParen, "`)`")?;
+                Ok(diagram)
+            }
+            _ => Err(self.unexpected("an expression")),
+        }
+    }
+}
+
+// Printing
+
+fn quote(name: &str) -> String {
+    let is_identifier = name.starts_with(is_identifier_start)
+        && name.chars().all(is_identifier)
+        && !KEYWORDS.contains(&name);
+    if is_identifier {
+        name.to_owned()
+    } else {
+        let mut quoted = String::from('"');
+        for c in name.chars() {
+            if matches!(c, '"' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    }
+}
+
+/// Unique printable names for the generators of a signature.
+struct Names {
+    names: FastHashMap<Generator, String>,
+    used: FastHashSet<String>,
+}
+
+impl Names {
+    fn new(signature: &Signature) -> Self {
+        let mut names = Self {
+            names: FastHashMap::default(),
+            used: FastHashSet::default(),
+        };
+        for info in signature.iter() {
+            let name = names.fresh(&info.name);
+            names.names.insert(info.generator, name);
+        }
+        names
+    }
+
+    /// Quotes a name, disambiguating it from the names handed out so far.
+    fn fresh(&mut self, name: &str) -> String {
+        let mut fresh = name.to_owned();
+        let mut i = 1;
+        while self.used.contains(&fresh) {
+            i += 1;
+            fresh = format!("{name} {i}");
+        }
+        self.used.insert(fresh.clone());
+        quote(&fresh)
+    }
+
+    fn get(&self, generator: Generator) -> Result<&str, PrintError> {
+        self.names
+            .get(&generator)
+            .map(String::as_str)
+            .ok_or(PrintError::UnknownGenerator(generator))
+    }
+
+    fn expression(&self, diagram: &Diagram, signature: &Signature) -> Result<String, PrintError> {
+        let diagram = match diagram {
+            Diagram::Diagram0(d) if d.orientation == Orientation::Positive => {
+                return self.get(d.generator).map(ToOwned::to_owned);
+            }
+            Diagram::Diagram0(_) => return Err(PrintError::Orientation),
+            Diagram::DiagramN(diagram) => diagram,
+        };
+
+        if diagram.size() == 0 {
+            return Ok(format!(
+                "id({})",
+                self.expression(&diagram.source(), signature)?
+            ));
+        }
+
+        let mut expression = String::new();
+        for (height, (source, cospan)) in
+            diagram.regular_slices().zip(diagram.cospans()).enumerate()
+        {
+            let level = DiagramN::new(source.clone(), vec![cospan.clone()]);
+            let (term, attached, embedding) =
+                self.level(&level, signature)?.ok_or(PrintError::Level {
+                    dimension: diagram.dimension(),
+                    height,
+                })?;
+
+            if height == 0 {
+                // Diagrams which start with a whole generator do not need an identity.
+                if attached.source() == source {
+                    expression = term;
+                    continue;
+                }
+                expression = format!("id({})", self.expression(&source, signature)?);
+            }
+
+            write!(expression, " ; {term}").unwrap();
+            if embedding.iter().any(|&i| i != 0) {
+                expression.push_str(" @");
+                for i in embedding {
+                    write!(expression, " {i}").unwrap();
+                }
+            }
+        }
+
+        Ok(expression)
+    }
+
+    /// Finds the generator, or inverse of a generator, which is attached in a diagram of size one,
+    /// together with the embedding of its source.
+    fn level(
+        &self,
+        level: &DiagramN,
+        signature: &Signature,
+    ) -> Result<Option<(String, DiagramN, Vec<usize>)>, PrintError> {
+        let generator = level.max_generator().generator;
+        if generator.dimension != level.dimension() {
+            return Ok(None);
+        }
+
+        let name = self.get(generator)?;
+        let Some(Diagram::DiagramN(diagram)) = signature
+            .iter()
+            .find(|info| info.generator == generator)
+            .map(|info| &info.diagram)
+        else {
+            return Err(PrintError::UnknownGenerator(generator));
+        };
+
+        let source = level.source();
+        for (term, attached) in [
+            (name.to_owned(), diagram.clone()),
+            (format!("inv({name})"), diagram.inverse()),
+        ] {
+            for embedding in source.embeddings(&attached.source()) {
+                let candidate =
+                    source
+                        .clone()
+                        .identity()
+                        .attach(&attached, Boundary::Target, &embedding);
+                if candidate.ok().as_ref() == Some(level) {
+                    return Ok(Some((term, attached, embedding)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,102 @@
+use homotopy_core::{signature::Invertibility, Boundary, Diagram, DiagramN, SliceIndex};
+use homotopy_model::proof::{Action, BubbleOption, ProofError, ProofState, Signature, Workspace};
+
+// A wire k from w to x next to the composite of 2-cells f => g => h between wires from x to y,
+// where the second 2-cell has the given invertibility.
+fn setup(invertibility: Invertibility) -> (ProofState, DiagramN, [DiagramN; 2]) {
+    let mut sig = Signature::default();
+    let [w, x, y] = ["w", "x", "y"].map(|name| sig.create_generator_zero(name));
+    let [f, g, h] = ["f", "g", "h"].map(|name| {
+        sig.create_generator(x.into(), y.into(), name, Invertibility::Directed)
+            .unwrap()
+    });
+    let k = sig
+        .create_generator(w.into(), x.into(), "k", Invertibility::Directed)
+        .unwrap();
+    let alpha = sig
+        .create_generator(
+            f.into(),
+            g.clone().into(),
+            "alpha",
+            Invertibility::Invertible,
+        )
+        .unwrap();
+    let beta = sig
+        .create_generator(g.into(), h.into(), "beta", invertibility)
+        .unwrap();
+
+    let host = alpha
+        .attach(&beta, Boundary::Target, &[])
+        .unwrap()
+        .attach(&k, Boundary::Source, &[])
+        .unwrap();
+    let proof = ProofState {
+        signature: sig,
+        workspace: Some(Workspace::new(host.clone().into())),
+        ..Default::default()
+    };
+    (proof, k, [alpha, beta])
+}
+
+fn bubble(diagram: &DiagramN, cancel: bool) -> Action {
+    Action::Bubble(BubbleOption {
+        diagram: diagram.clone(),
+        embedding: [1, 1].into_iter().collect(),
+        cancel,
+    })
+}
+
+// The host with the second 2-cell followed by its inverse and itself again.
+fn bubbled(k: &DiagramN, [alpha, beta]: &[DiagramN; 2]) -> Diagram {
+    let cells = [beta, &beta.inverse(), beta];
+    cells
+        .into_iter()
+        .fold(alpha.clone(), |composite, cell| {
+            composite.attach(cell, Boundary::Target, &[]).unwrap()
+        })
+        .attach(k, Boundary::Source, &[])
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn pairs_are_introduced_and_cancelled() {
+    let (mut proof, k, cells) = setup(Invertibility::Invertible);
+    let host = proof.workspace.clone().unwrap().diagram;
+
+    assert!(bubble(&cells[1], false).is_valid(&proof));
+    assert!(proof.update(&bubble(&cells[1], false)).unwrap());
+    assert_eq!(
+        proof.workspace.as_ref().unwrap().diagram,
+        bubbled(&k, &cells)
+    );
+
+    assert!(proof.update(&bubble(&cells[1], true)).unwrap());
+    assert_eq!(proof.workspace.unwrap().diagram, host);
+}
+
+#[test]
+fn pairs_are_introduced_in_the_boundary() {
+    let (mut proof, k, cells) = setup(Invertibility::Invertible);
+    let ws = proof.workspace.as_mut().unwrap();
+    ws.diagram = DiagramN::try_from(ws.diagram.clone())
+        .unwrap()
+        .identity()
+        .into();
+    ws.path.push_back(SliceIndex::Boundary(Boundary::Target));
+
+    assert!(proof.update(&bubble(&cells[1], false)).unwrap());
+    let diagram = DiagramN::try_from(proof.workspace.unwrap().diagram).unwrap();
+    assert_eq!(diagram.size(), 1);
+    assert_eq!(diagram.target(), bubbled(&k, &cells));
+}
+
+#[test]
+fn directed_generators_are_not_bubbled() {
+    let (mut proof, _, cells) = setup(Invertibility::Directed);
+
+    assert!(matches!(
+        proof.update(&bubble(&cells[1], false)),
+        Err(ProofError::NotInvertible)
+    ));
+}
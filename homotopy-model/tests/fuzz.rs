@@ -0,0 +1,370 @@
+use homotopy_core::{
+    examples,
+    signature::{GeneratorInfo as _, Invertibility, Signature as _, SignatureBuilder},
+    typecheck::{typecheck, Mode},
+    Boundary, Diagram, Direction, Height, SliceIndex,
+};
+use homotopy_model::{
+    proof::{
+        homotopy::{Contract, Expand, Homotopy},
+        Action, AttachOption, BubbleOption, ProofState, SerializedData, Signature, SignatureEdit,
+        SignatureItem, SignatureItemEdit, Workspace,
+    },
+    serialize,
+};
+use proptest::{prelude::*, sample::Index};
+
+const MAX_ACTIONS: usize = 12;
+
+// Examples small enough to typecheck after every action.
+const EXAMPLES: usize = 6;
+
+fn example(index: usize) -> ProofState {
+    let (sig, diagram): (_, Diagram) = match index {
+        0 => {
+            let (sig, diagram) = examples::one_zero_cell();
+            (sig, diagram.into())
+        }
+        1 => map(examples::scalar()),
+        2 => map(examples::two_endomorphism()),
+        3 => map(examples::two_monoid()),
+        4 => map(examples::two_scalars()),
+        _ => map(examples::associator()),
+    };
+    ProofState {
+        signature: signature(&sig),
+        workspace: Some(Workspace::new(diagram)),
+        ..Default::default()
+    }
+}
+
+fn map<D: Into<Diagram>>((sig, diagram): (SignatureBuilder, D)) -> (SignatureBuilder, Diagram) {
+    (sig, diagram.into())
+}
+
+// Rebuilds an example signature, keeping the ids of its generators.
+fn signature(builder: &SignatureBuilder) -> Signature {
+    let mut sig = Signature::default();
+    for generator in builder.generators() {
+        let info = builder.generator_info(generator).unwrap();
+        let name = format!("g{}", generator.id);
+        let diagram: Diagram = match info.diagram() {
+            Diagram::Diagram0(_) => sig.create_generator_zero(&name).into(),
+            Diagram::DiagramN(d) => sig
+                .create_generator(d.source(), d.target(), &name, info.invertibility())
+                .unwrap()
+                .into(),
+        };
+        assert_eq!(&diagram, info.diagram());
+    }
+    sig
+}
+
+// The kinds of action the fuzzer chooses from.
+const KINDS: usize = 34;
+
+// The actions of a given kind which make sense to try in the current state, including ones which
+// are expected to be invalid or to fail.
+fn candidates(proof: &ProofState, kind: usize) -> Vec<Action> {
+    let generators: Vec<_> = proof.signature.iter().map(|info| info.generator).collect();
+    let ws = proof.workspace.as_ref();
+    let size = ws.and_then(|ws| ws.visible_diagram().size()).unwrap_or(0);
+    let location: Vec<_> = ws
+        .map(|ws| ws.path.iter().copied().collect())
+        .unwrap_or_default();
+
+    match kind {
+        0 => vec![Action::CreateGeneratorZero],
+        1 => vec![
+            Action::SetBoundary(Boundary::Source),
+            Action::SetBoundary(Boundary::Target),
+        ],
+        2 => vec![Action::TakeIdentityDiagram],
+        3 => vec![Action::ClearWorkspace],
+        4 => vec![Action::ClearBoundary],
+        5 => generators
+            .into_iter()
+            .map(Action::SelectGenerator)
+            .collect(),
+        6 => vec![Action::AscendSlice(1)],
+        7 => {
+            let mut slices = vec![Boundary::Source.into(), Boundary::Target.into()];
+            slices.extend((0..=size).map(|i| SliceIndex::Interior(Height::Regular(i))));
+            slices.extend((0..size).map(|i| SliceIndex::Interior(Height::Singular(i))));
+            slices.into_iter().map(Action::DescendSlice).collect()
+        }
+        8 => vec![
+            Action::SwitchSlice(Direction::Forward),
+            Action::SwitchSlice(Direction::Backward),
+        ],
+        9 => vec![Action::IncreaseView(1)],
+        10 => vec![Action::DecreaseView(1)],
+        11 => attach_options(proof)
+            .into_iter()
+            .map(Action::Attach)
+            .collect(),
+        12 => (0..size)
+            .flat_map(|height| {
+                [Direction::Forward, Direction::Backward].map(|direction| {
+                    Action::Homotopy(Homotopy::Contract(Contract {
+                        height,
+                        direction,
+                        step: 1,
+                        bias: None,
+                        location: location.clone(),
+                    }))
+                })
+            })
+            .collect(),
+        13 => {
+            let mut actions = vec![];
+            for y in 0..size {
+                for x in 0..2 {
+                    for direction in [Direction::Forward, Direction::Backward] {
+                        actions.push(Action::Homotopy(Homotopy::Expand(Expand {
+                            point: vec![Height::Singular(y), Height::Singular(x)],
+                            axis: 0,
+                            direction,
+                            location: location.clone(),
+                            choice: 0,
+                        })));
+                    }
+                }
+            }
+            actions
+        }
+        14 => vec![Action::Squash],
+        15 => vec![Action::Behead],
+        16 => vec![Action::Befoot],
+        17 => vec![Action::Invert],
+        18 => vec![Action::Restrict],
+        19 => vec![Action::Theorem],
+        20 => vec![Action::FlipBoundary],
+        21 => vec![Action::RecoverBoundary],
+        22 => vec![Action::Stash],
+        23 => vec![Action::StashDrop],
+        24 => vec![Action::StashPop],
+        25 => vec![Action::StashApply],
+        26 => generators.into_iter().map(Action::RewriteAll).collect(),
+        27 => bubble_options(proof)
+            .into_iter()
+            .map(Action::Bubble)
+            .collect(),
+        28 => generators.into_iter().map(Action::Inline).collect(),
+        29 => {
+            let mut actions = vec![Action::SuspendSignature];
+            actions.extend(
+                generators
+                    .into_iter()
+                    .filter(|generator| generator.dimension == 0)
+                    .map(|generator| Action::Suspend(generator, generator)),
+            );
+            actions
+        }
+        30 => generators
+            .into_iter()
+            .flat_map(|from| {
+                proof
+                    .signature
+                    .globular_pairs(from)
+                    .into_iter()
+                    .map(move |to| Action::Merge(from, to))
+            })
+            .collect(),
+        31 => signature_edits(proof)
+            .into_iter()
+            .map(Action::EditSignature)
+            .collect(),
+        32 => proofs(proof).map(Action::ImportProof).collect(),
+        _ => proofs(proof).map(Action::ApplyMorphism).collect(),
+    }
+}
+
+// Attaches a generator to the target of the workspace at every place its source occurs.
+fn attach_options(proof: &ProofState) -> Vec<AttachOption> {
+    let Some(ws) = proof.workspace.as_ref() else {
+        return vec![];
+    };
+    let mut options = vec![];
+    for info in proof.signature.iter() {
+        let Diagram::DiagramN(diagram) = &info.diagram else {
+            continue;
+        };
+        if diagram.dimension() != ws.diagram.dimension() + 1 {
+            continue;
+        }
+        let needle = diagram.source();
+        options.extend(
+            ws.diagram
+                .embeddings(&needle)
+                .map(|embedding| AttachOption {
+                    generator: info.generator,
+                    boundary_path: None,
+                    embedding: embedding.into_iter().collect(),
+                    tag: None,
+                    diagram: diagram.clone(),
+                }),
+        );
+    }
+    options
+}
+
+// Introduces a generator or its inverse followed by its inverse at every place its source occurs
+// in the visible diagram, and cancels every such pair.
+fn bubble_options(proof: &ProofState) -> Vec<BubbleOption> {
+    let Some(ws) = proof.workspace.as_ref() else {
+        return vec![];
+    };
+    let visible = ws.visible_diagram();
+    let mut options = vec![];
+    for info in proof.signature.iter() {
+        let Diagram::DiagramN(diagram) = &info.diagram else {
+            continue;
+        };
+        if diagram.dimension() != visible.dimension() {
+            continue;
+        }
+        for diagram in [diagram.clone(), diagram.inverse()] {
+            let pair = diagram
+                .attach(&diagram.inverse(), Boundary::Target, &[])
+                .unwrap();
+            for (needle, cancel) in [(diagram.source(), false), (pair.into(), true)] {
+                options.extend(visible.embeddings(&needle).map(|embedding| BubbleOption {
+                    diagram: diagram.clone(),
+                    embedding: embedding.into_iter().collect(),
+                    cancel,
+                }));
+            }
+        }
+    }
+    options
+}
+
+// Edits every item of the signature, and moves it before any other item or into any folder.
+fn signature_edits(proof: &ProofState) -> Vec<SignatureEdit> {
+    let tree = proof.signature.as_tree();
+    let root = tree.root();
+    let nodes: Vec<_> = tree
+        .iter()
+        .map(|(node, _)| node)
+        .filter(|&node| node != root)
+        .collect();
+    let folders: Vec<_> = tree
+        .iter()
+        .filter(|(_, item)| matches!(item.inner(), SignatureItem::Folder(_)))
+        .map(|(node, _)| node)
+        .collect();
+
+    let mut edits: Vec<_> = folders
+        .iter()
+        .flat_map(|&folder| {
+            [
+                SignatureEdit::NewFolder(folder),
+                SignatureEdit::ToggleFolder(folder),
+            ]
+        })
+        .collect();
+    for &node in &nodes {
+        edits.extend(
+            [
+                SignatureItemEdit::Rename("renamed".to_owned()),
+                SignatureItemEdit::MakeOriented(true),
+                SignatureItemEdit::MakeInvertible(Invertibility::Directed),
+                SignatureItemEdit::MakeInvertible(Invertibility::Invertible),
+                SignatureItemEdit::ShowSourceTarget(true),
+            ]
+            .map(|edit| SignatureEdit::Edit(node, edit)),
+        );
+        edits.push(SignatureEdit::Remove(node));
+        edits.extend(nodes.iter().map(|&to| SignatureEdit::MoveBefore(node, to)));
+        edits.extend(folders.iter().map(|&to| SignatureEdit::MoveInto(node, to)));
+    }
+    edits
+}
+
+// The current proof and every example, serialized, together with data which is not a proof.
+fn proofs(proof: &ProofState) -> impl Iterator<Item = SerializedData> {
+    let data = serialize::serialize_session(proof, None);
+    std::iter::once(data)
+        .chain((0..EXAMPLES).map(|index| serialize::serialize_session(&example(index), None)))
+        .chain([vec![]])
+        .map(SerializedData)
+}
+
+fn choose(proof: &ProofState, kind: usize, index: &Index) -> Action {
+    let candidates = candidates(proof, kind);
+    if candidates.is_empty() {
+        Action::Nothing
+    } else {
+        candidates[index.index(candidates.len())].clone()
+    }
+}
+
+// Every diagram of the proof is well-formed and well-typed, and the proof survives a round trip
+// through the current format.
+fn check(proof: &ProofState) -> Result<(), TestCaseError> {
+    let diagrams = proof
+        .signature
+        .iter()
+        .map(|info| &info.diagram)
+        .chain(proof.workspace.iter().map(|ws| &ws.diagram))
+        .chain(proof.boundary.iter().map(|selected| &selected.diagram))
+        .chain(proof.stash.iter().map(|ws| &ws.diagram));
+    for diagram in diagrams {
+        prop_assert!(diagram.check(true).is_ok(), "malformed diagram");
+        prop_assert!(
+            typecheck(diagram, &proof.signature, Mode::default(), true).is_ok(),
+            "ill-typed diagram"
+        );
+    }
+
+    let data = serialize::serialize_session(proof, None);
+    let (restored, history) = serialize::deserialize_session(&data).unwrap();
+    prop_assert!(history.is_none());
+    let generators = |proof: &ProofState| {
+        proof
+            .signature
+            .iter()
+            .map(|info| (info.generator, info.diagram.clone()))
+            .collect::<Vec<_>>()
+    };
+    prop_assert_eq!(generators(&restored), generators(proof));
+    prop_assert_eq!(&restored.workspace, &proof.workspace);
+    prop_assert_eq!(&restored.boundary, &proof.boundary);
+    prop_assert_eq!(&restored.stash, &proof.stash);
+    prop_assert_eq!(&restored.metadata, &proof.metadata);
+    Ok(())
+}
+
+// Set PROPTEST_CASES to fuzz for longer.
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn updates_agree_with_validity(
+        start in 0..EXAMPLES,
+        choices in prop::collection::vec((0..KINDS, any::<Index>()), 1..MAX_ACTIONS),
+    ) {
+        let mut proof = example(start);
+        for (kind, index) in choices {
+            let action = choose(&proof, kind, &index);
+            let valid = action.is_valid(&proof);
+
+            // Failed actions are discarded, as they are in the app.
+            let mut updated = proof.clone();
+            let result = updated.update(&action);
+            prop_assert_eq!(
+                valid,
+                !matches!(result, Ok(false)),
+                "{:?} is valid: {}, but update returned {:?}",
+                action,
+                valid,
+                result
+            );
+            if matches!(result, Ok(true)) {
+                check(&updated)?;
+                proof = updated;
+            }
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use homotopy_core::{
+    common::Generator,
+    signature::Invertibility,
+    Boundary, Diagram,
+    Direction::{Backward, Forward},
+    Height, SliceIndex,
+};
+use homotopy_model::{
+    history::{History, HistoryError},
+    proof::{Action, ProofState},
+    serialize,
+};
+
+fn perform(history: &mut History, action: Action) {
+    let mut proof = history.proof().clone();
+    assert!(proof.update(&action).unwrap());
+    history.add(action, proof);
+}
+
+fn generator_diagrams(proof: &ProofState) -> Vec<Diagram> {
+    proof
+        .signature
+        .iter()
+        .map(|info| info.diagram.clone())
+        .collect()
+}
+
+// Compares proofs up to the details of the signature which are not saved.
+fn assert_same(left: &ProofState, right: &ProofState) {
+    assert_eq!(generator_diagrams(left), generator_diagrams(right));
+    assert_eq!(left.workspace, right.workspace);
+    assert_eq!(left.metadata, right.metadata);
+    assert_eq!(left.boundary, right.boundary);
+    assert_eq!(left.stash, right.stash);
+}
+
+fn generators(history: &History) -> usize {
+    history.proof().signature.iter().count()
+}
+
+// A history which adds one 0-cell, then two 0-cells on one branch and three on another.
+fn setup() -> History {
+    let mut history = History::default();
+    perform(&mut history, Action::CreateGeneratorZero);
+    perform(&mut history, Action::CreateGeneratorZero);
+    history.undo().unwrap();
+    perform(&mut history, Action::CreateGeneratorZero);
+    perform(&mut history, Action::CreateGeneratorZero);
+    history
+}
+
+#[test]
+fn branches_are_reachable() {
+    let mut history = setup();
+    history.undo().unwrap();
+    assert_eq!(generators(&history), 2);
+
+    history.move_to_sibling(Backward).unwrap();
+    assert_eq!(generators(&history), 2);
+    assert!(history.proof().is_empty());
+    assert!(matches!(
+        history.move_to_sibling(Backward),
+        Err(HistoryError::Branch)
+    ));
+
+    // Redo follows the branch which was visited last.
+    history.undo().unwrap();
+    history.redo().unwrap();
+    assert!(history.proof().is_empty());
+
+    history.move_to_sibling(Forward).unwrap();
+    history.redo().unwrap();
+    assert_eq!(generators(&history), 3);
+}
+
+#[test]
+fn jumps_are_remembered_by_redo() {
+    let mut history = setup();
+    let outline = history.outline();
+    let leaves: Vec<_> = outline
+        .actions
+        .iter()
+        .filter(|(_, data)| data.is_empty())
+        .map(|(node, _)| node)
+        .collect();
+    assert_eq!(leaves.len(), 2);
+
+    history.jump(leaves[0]).unwrap();
+    assert_eq!(generators(&history), 2);
+    history.undo().unwrap();
+    history.undo().unwrap();
+    history.redo().unwrap();
+    history.redo().unwrap();
+    assert_eq!(history.current(), leaves[0]);
+}
+
+#[test]
+fn pruning_keeps_the_current_line() {
+    let mut history = setup();
+    let actions = history.get_actions();
+    let outline = history.outline();
+    let first = outline.actions[outline.actions.root()].last().unwrap();
+    let branches: Vec<_> = outline.actions[first].children().collect();
+
+    assert!(!history.can_prune(outline.actions.root()));
+    assert!(!history.can_prune(branches[1]));
+    assert!(matches!(
+        history.prune(branches[1]),
+        Err(HistoryError::Prune)
+    ));
+
+    history.prune(branches[0]).unwrap();
+    assert_eq!(history.outline().actions.iter().count(), 4);
+    assert_eq!(history.get_actions(), actions);
+    assert_eq!(generators(&history), 3);
+
+    history.undo().unwrap();
+    history.undo().unwrap();
+    assert!(matches!(
+        history.move_to_sibling(Forward),
+        Err(HistoryError::Branch)
+    ));
+    history.redo().unwrap();
+    history.redo().unwrap();
+    assert_eq!(generators(&history), 3);
+}
+
+#[test]
+fn sessions_are_restored() {
+    let mut history = setup();
+    perform(&mut history, Action::SelectGenerator(Generator::new(0, 0)));
+    perform(&mut history, Action::Stash);
+    perform(&mut history, Action::SelectGenerator(Generator::new(1, 0)));
+    perform(&mut history, Action::SetBoundary(Boundary::Source));
+    history.undo().unwrap();
+
+    let data = serialize::serialize_session(history.proof(), Some(&history));
+    let (proof, restored) = serialize::deserialize_session(&data).unwrap();
+    let restored = restored.unwrap();
+    assert_same(&proof, history.proof());
+    assert_eq!(proof.stash.len(), 1);
+    assert_eq!(restored.outline(), history.outline());
+    assert_eq!(restored.get_actions(), history.get_actions());
+
+    // Redo still follows the branch which was visited last.
+    let mut restored = restored;
+    restored.redo().unwrap();
+    assert!(restored.proof().boundary.is_some());
+
+    // Importing the proof restores its stash and boundary, and returns its history.
+    let mut imported = ProofState::default();
+    let saved = imported
+        .import_proof(&data.clone().into())
+        .unwrap()
+        .unwrap();
+    assert_same(&imported, history.proof());
+    assert_eq!(saved.outline(), history.outline());
+
+    // The proof alone is imported by the action.
+    let mut imported = ProofState::default();
+    assert!(imported.update(&Action::ImportProof(data.into())).unwrap());
+    assert_same(&imported, history.proof());
+}
+
+#[test]
+fn restored_histories_are_grafted() {
+    let saved = setup();
+    let data = serialize::serialize_session(saved.proof(), Some(&saved));
+
+    let mut history = History::default();
+    perform(&mut history, Action::CreateGeneratorZero);
+    let mut proof = history.proof().clone();
+    let restored = proof.import_proof(&data.clone().into()).unwrap().unwrap();
+    history.add(Action::ImportProof(data.into()), proof);
+    history.graft(restored);
+
+    // The restored history continues from the import, and the earlier history is kept.
+    assert_eq!(generators(&history), 3);
+    assert_eq!(history.get_actions(), saved.get_actions());
+    history.undo().unwrap();
+    history.move_to_sibling(Backward).unwrap();
+    assert_eq!(generators(&history), 2);
+    for _ in 0..3 {
+        history.undo().unwrap();
+    }
+    assert!(matches!(
+        history.last_action(),
+        Some(Action::ImportProof(_))
+    ));
+    history.undo().unwrap();
+    assert_eq!(generators(&history), 1);
+}
+
+#[test]
+fn proofs_are_saved_without_a_session() {
+    let mut history = setup();
+    perform(&mut history, Action::SelectGenerator(Generator::new(0, 0)));
+    perform(&mut history, Action::Stash);
+
+    let proof = history.proof();
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.workspace.clone(),
+        proof.metadata.clone(),
+    );
+    let (restored, restored_history) = serialize::deserialize_session(&data).unwrap();
+    assert!(restored_history.is_none());
+    assert!(restored.stash.is_empty());
+    assert_eq!(generator_diagrams(&restored), generator_diagrams(proof));
+}
+
+// A history in which a 2-cell is selected, then navigated around and stashed.
+fn navigated() -> History {
+    let mut history = History::default();
+    let mut proof = history.proof().clone();
+    let x = proof.signature.create_generator_zero("x");
+    let f = proof
+        .signature
+        .create_generator(x.into(), x.into(), "f", Invertibility::Directed)
+        .unwrap();
+    let alpha = proof
+        .signature
+        .create_generator(f.clone().into(), f.into(), "alpha", Invertibility::Directed)
+        .unwrap();
+    history.add(Action::CreateGeneratorZero, proof);
+
+    let navigation = [
+        Action::DescendSlice(SliceIndex::Interior(Height::Singular(0))),
+        Action::SwitchSlice(Forward),
+        Action::SwitchSlice(Backward),
+        Action::DecreaseView(1),
+    ];
+    perform(
+        &mut history,
+        Action::SelectGenerator(alpha.max_generator().generator),
+    );
+    for action in navigation {
+        perform(&mut history, action);
+    }
+    perform(&mut history, Action::Stash);
+    history
+}
+
+#[test]
+fn navigation_is_not_an_undo_step() {
+    let mut history = navigated();
+    assert_eq!(history.outline().actions.iter().count(), 4);
+    let stashed = history.proof().stash[0].clone();
+    assert_eq!(stashed.path.len(), 1);
+    assert_eq!(stashed.view.dimension(), 0);
+
+    // Undo returns to where the user was before stashing.
+    history.undo().unwrap();
+    assert_eq!(history.proof().workspace.as_ref(), Some(&stashed));
+}
+
+#[test]
+fn navigation_is_logged_before_changes() {
+    let history = navigated();
+    let actions = history.get_actions();
+    assert_eq!(
+        actions[2..],
+        [
+            Action::DescendSlice(SliceIndex::Interior(Height::Singular(0))),
+            Action::DecreaseView(1),
+            Action::Stash,
+        ]
+    );
+}
+
+#[test]
+fn redo_requires_the_same_navigation() {
+    let mut history = navigated();
+    let stashed = history.outline().current;
+
+    history.undo().unwrap();
+    history.try_redo(&Action::Stash).unwrap();
+    assert_eq!(history.current(), stashed);
+
+    // Stashing after navigating elsewhere stashes another workspace.
+    history.undo().unwrap();
+    perform(&mut history, Action::AscendSlice(1));
+    assert!(matches!(
+        history.try_redo(&Action::Stash),
+        Err(HistoryError::Redo)
+    ));
+    perform(&mut history, Action::Stash);
+    assert_ne!(history.current(), stashed);
+    assert!(history.proof().stash[0].path.is_empty());
+}
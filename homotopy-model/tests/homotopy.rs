@@ -0,0 +1,103 @@
+use homotopy_core::{
+    signature::Invertibility, Bias, Boundary, Diagram, DiagramN, Direction, Height,
+};
+use homotopy_model::proof::{
+    homotopy::{Contract, Expand, Homotopy},
+    Action, ProofState, Signature, Workspace,
+};
+
+// Two distinct scalars one after the other.
+fn setup() -> ProofState {
+    let mut sig = Signature::default();
+    let x = sig.create_generator_zero("x");
+    let identity = Diagram::from(x).identity();
+    let [s, t]: [DiagramN; 2] = ["s", "t"].map(|name| {
+        sig.create_generator(
+            identity.clone().into(),
+            identity.clone().into(),
+            name,
+            Invertibility::Directed,
+        )
+        .unwrap()
+    });
+    let two = s.attach(&t, Boundary::Target, &[]).unwrap();
+    ProofState {
+        signature: sig,
+        workspace: Some(Workspace::new(two.into())),
+        ..Default::default()
+    }
+}
+
+fn contract(bias: Option<Bias>) -> Homotopy {
+    Homotopy::Contract(Contract {
+        height: 0,
+        direction: Direction::Forward,
+        step: 1,
+        bias,
+        location: vec![],
+    })
+}
+
+#[test]
+fn contractions_are_offered_for_every_bias() {
+    let proof = setup();
+
+    let options = proof.homotopy_options(&contract(Some(Bias::Higher)));
+    assert_eq!(
+        options
+            .iter()
+            .map(|option| option.homotopy.clone())
+            .collect::<Vec<_>>(),
+        vec![contract(Some(Bias::Higher)), contract(Some(Bias::Lower))]
+    );
+
+    for option in options {
+        let mut proof = proof.clone();
+        assert!(proof.update(&Action::Homotopy(option.homotopy)).unwrap());
+        assert_eq!(proof.workspace.unwrap(), option.workspace);
+    }
+}
+
+#[test]
+fn expansions_are_chosen_by_index() {
+    let mut proof = setup();
+    proof
+        .update(&Action::Homotopy(contract(Some(Bias::Lower))))
+        .unwrap();
+
+    let expand = |choice| Expand {
+        point: vec![Height::Singular(0), Height::Singular(0)],
+        axis: 0,
+        direction: Direction::Forward,
+        location: vec![],
+        choice,
+    };
+    let options = proof.homotopy_options(&Homotopy::Expand(expand(0)));
+    assert_eq!(options.len(), 1);
+    assert_eq!(options[0].homotopy, Homotopy::Expand(expand(0)));
+
+    assert!(!proof
+        .clone()
+        .update(&Action::Homotopy(Homotopy::Expand(expand(1))))
+        .unwrap());
+    assert!(proof
+        .update(&Action::Homotopy(Homotopy::Expand(expand(0))))
+        .unwrap());
+    assert_eq!(proof.workspace.unwrap(), options[0].workspace);
+}
+
+#[test]
+fn failed_expansions_report_errors() {
+    let mut proof = setup();
+
+    let expand = Expand {
+        point: vec![Height::Singular(5), Height::Singular(0)],
+        axis: 0,
+        direction: Direction::Forward,
+        location: vec![],
+        choice: 0,
+    };
+    assert!(proof
+        .update(&Action::Homotopy(Homotopy::Expand(expand)))
+        .is_err());
+}
@@ -0,0 +1,104 @@
+use homotopy_core::{
+    examples::stack_scalars as stack,
+    signature::{Invertibility, Signature as _},
+    typecheck::{typecheck, Mode},
+    Diagram, DiagramN, Generator,
+};
+use homotopy_model::proof::{Action, ProofError, ProofState, Signature, Workspace};
+
+// Turns the scalars a, b into a theorem, and returns the proof state with the scalars a, b, c in
+// its signature together with the theorem and its proof.
+fn setup() -> (ProofState, [DiagramN; 3], Generator, Generator) {
+    let mut sig = Signature::default();
+    let x = sig.create_generator_zero("x");
+    let [a, b, c] = ["a", "b", "c"].map(|name| {
+        sig.create_generator(
+            x.identity().into(),
+            x.identity().into(),
+            name,
+            Invertibility::Directed,
+        )
+        .unwrap()
+    });
+
+    let mut proof = ProofState {
+        signature: sig,
+        workspace: Some(Workspace::new(stack(&[&a, &b]).into())),
+        ..Default::default()
+    };
+    assert!(proof.update(&Action::Theorem).unwrap());
+    let theorem = Generator::new(4, 2);
+    let definition = Generator::new(5, 3);
+    (proof, [a, b, c], theorem, definition)
+}
+
+fn diagram(proof: &ProofState, generator: Generator) -> DiagramN {
+    proof
+        .signature
+        .generator_info(generator)
+        .unwrap()
+        .diagram
+        .clone()
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn theorems_are_inlined() {
+    let (mut proof, [a, b, c], theorem, definition) = setup();
+    let theorem = diagram(&proof, theorem);
+
+    proof.workspace = Some(Workspace::new(stack(&[&theorem, &c, &theorem]).into()));
+    assert!(Action::Inline(definition).is_valid(&proof));
+    assert!(proof.update(&Action::Inline(definition)).unwrap());
+
+    let expected: Diagram = stack(&[&a, &b, &c, &a, &b]).into();
+    assert_eq!(proof.workspace.unwrap().diagram, expected);
+}
+
+#[test]
+fn cells_are_retyped() {
+    let (mut proof, [a, b, c], theorem, definition) = setup();
+    let beta = proof
+        .signature
+        .create_generator(
+            diagram(&proof, theorem).into(),
+            c.clone().into(),
+            "beta",
+            Invertibility::Directed,
+        )
+        .unwrap();
+    let generator = beta.max_generator().generator;
+
+    proof.workspace = Some(Workspace::new(beta.into()));
+    assert!(proof.update(&Action::Inline(definition)).unwrap());
+
+    let beta = diagram(&proof, generator);
+    assert_eq!(beta.source(), stack(&[&a, &b]).into());
+    assert_eq!(beta.target(), c.into());
+    let workspace = proof.workspace.unwrap().diagram;
+    assert_eq!(workspace, beta.into());
+    assert!(typecheck(&workspace, &proof.signature, Mode::default(), true).is_ok());
+}
+
+#[test]
+fn only_definitions_are_inlined() {
+    let (mut proof, _, theorem, _) = setup();
+
+    proof.workspace = Some(Workspace::new(diagram(&proof, theorem).into()));
+    assert!(!Action::Inline(theorem).is_valid(&proof));
+    assert!(!proof.update(&Action::Inline(theorem)).unwrap());
+}
+
+#[test]
+fn proofs_cannot_be_inlined_into_themselves() {
+    let (mut proof, _, theorem, definition) = setup();
+
+    proof.workspace = Some(Workspace::new(diagram(&proof, definition).into()));
+    assert!(matches!(
+        proof.update(&Action::Inline(definition)),
+        Err(ProofError::SubstitutionError(_))
+    ));
+    let workspace = proof.workspace.unwrap().diagram;
+    assert!(workspace.generators().contains_key(&theorem));
+}
@@ -0,0 +1,64 @@
+use homotopy_model::{
+    history::Proof,
+    json::{export, import, ImportError},
+    proof::Action,
+};
+
+fn replay(json: &str) -> Proof {
+    let (_safe, actions): (bool, Vec<Action>) = serde_json::from_str(json).unwrap();
+    let mut proof: Proof = Default::default();
+    for a in &actions {
+        proof.update(a).unwrap();
+    }
+    proof
+}
+
+#[test]
+fn round_trip() {
+    for dump in [
+        include_str!("examples/associator.json"),
+        include_str!("examples/braiding_half_bubbles.json"),
+    ] {
+        let proof = replay(dump);
+
+        let json = export(&proof.signature, proof.workspace.as_ref(), &proof.metadata);
+        let ((signature, workspace), metadata) = import(&json).unwrap();
+
+        assert_eq!(signature, proof.signature);
+        assert_eq!(workspace, proof.workspace);
+        assert_eq!(metadata, proof.metadata);
+    }
+}
+
+#[test]
+fn unsupported_version() {
+    let json = r#"{
+        "version": 0,
+        "signature": [],
+        "workspace": null,
+        "metadata": { "title": null, "author": null, "abstr": null },
+        "tables": { "diagrams": [], "rewrites": [], "cones": [] }
+    }"#;
+
+    assert!(matches!(import(json), Err(ImportError::Version(0))));
+}
+
+#[test]
+fn mismatched_dimension() {
+    let proof = replay(include_str!("examples/associator.json"));
+    let json = export(&proof.signature, proof.workspace.as_ref(), &proof.metadata);
+
+    // Claim that the first generator, a 0-cell, is a 1-cell.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["signature"][0]["generator"]["dimension"] = 1.into();
+    let json = serde_json::to_string(&value).unwrap();
+
+    assert!(matches!(
+        import(&json),
+        Err(ImportError::Dimension {
+            expected: 1,
+            found: 0,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,39 @@
+use std::{fs, path::Path};
+
+use homotopy_core::{
+    knot::{link_invariants, KnotError, Laurent},
+    DiagramN,
+};
+use homotopy_model::serialize;
+
+fn workspace_diagram(name: &str) -> DiagramN {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../examples")
+        .join(name);
+    let data = fs::read(path).unwrap();
+    let ((_, workspace), _) = serialize::deserialize(&data).unwrap();
+    workspace.unwrap().diagram.try_into().unwrap()
+}
+
+#[test]
+fn figure_8_isotopy_preserves_knot_type() {
+    let isotopy = workspace_diagram("figure_8_isotopy.hom");
+    assert_eq!(isotopy.dimension(), 4);
+
+    let invariants: Vec<_> = isotopy
+        .regular_slices()
+        .map(|slice| link_invariants(&slice.try_into().unwrap()).unwrap())
+        .collect();
+    // The figure 8 is an unknot with a single crossing.
+    let source = &invariants[0];
+    assert_eq!(source.components, 1);
+    assert_eq!(source.writhe.abs(), 1);
+    assert_eq!(source.jones_polynomial, Laurent::monomial(0, 1));
+    assert!(invariants.iter().all(|i| i == source));
+}
+
+#[test]
+fn open_braids_are_rejected() {
+    let braid = workspace_diagram("double_braid.hom");
+    assert!(matches!(link_invariants(&braid), Err(KnotError::Open)));
+}
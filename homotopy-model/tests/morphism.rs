@@ -0,0 +1,81 @@
+use homotopy_core::{
+    examples, morphism::MorphismError, signature::Invertibility, Boundary, DiagramN,
+};
+use homotopy_model::{
+    proof::{
+        generators::GeneratorInfo, Action, Metadata, ProofError, ProofState, Signature, Workspace,
+    },
+    serialize,
+};
+
+// A signature with a point, a wire and a binary operation on the wire, after any extra
+// generators, returning the wire and the operation.
+fn magma(sig: &mut Signature, names: [&str; 3]) -> (DiagramN, DiagramN) {
+    let [point, wire, operation] = names;
+    let x = sig.create_generator_zero(point);
+    let f = sig
+        .create_generator(x.into(), x.into(), wire, Invertibility::Directed)
+        .unwrap();
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let m = sig
+        .create_generator(
+            ff.into(),
+            f.clone().into(),
+            operation,
+            Invertibility::Directed,
+        )
+        .unwrap();
+    (f, m)
+}
+
+// Drops the identifiers which are appended to the names of new generators.
+fn strip_ids(sig: &Signature) -> Signature {
+    sig.filter_map(|info| {
+        Some(GeneratorInfo {
+            name: info.name.split(' ').next().unwrap().to_owned(),
+            ..info.clone()
+        })
+    })
+}
+
+fn apply_morphism(target: Signature) -> (Result<bool, ProofError>, ProofState) {
+    let mut sig = Signature::default();
+    let (f, m) = magma(&mut sig, ["x", "f", "m"]);
+    let mut proof = ProofState {
+        signature: strip_ids(&sig),
+        workspace: Some(Workspace::new(examples::multiply_thrice(&f, &m).into())),
+        ..Default::default()
+    };
+
+    let data = serialize::serialize(strip_ids(&target), None, Metadata::default());
+    let result = proof.update(&Action::ApplyMorphism(data.into()));
+    (result, proof)
+}
+
+#[test]
+fn generators_are_matched_by_name() {
+    let mut target = Signature::default();
+    target.create_generator_zero("y");
+    let (g, n) = magma(&mut target, ["x", "f", "m"]);
+
+    let (result, proof) = apply_morphism(target.clone());
+    assert!(result.unwrap());
+    assert!(proof.signature.iter().eq(strip_ids(&target).iter()));
+    assert_eq!(
+        proof.workspace.unwrap().diagram,
+        examples::multiply_thrice(&g, &n).into()
+    );
+}
+
+#[test]
+fn unmatched_generators_are_rejected() {
+    let mut target = Signature::default();
+    magma(&mut target, ["x", "f", "n"]);
+
+    let (result, proof) = apply_morphism(target);
+    assert!(matches!(
+        result,
+        Err(ProofError::MorphismError(MorphismError::Missing(_)))
+    ));
+    assert_eq!(proof.signature.iter().count(), 3);
+}
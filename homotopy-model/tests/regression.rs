@@ -0,0 +1,35 @@
+use std::path::{Path, PathBuf};
+
+use homotopy_model::regression::{check_dir, check_file, FileReport};
+
+fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples")
+}
+
+fn assert_ok(reports: &[FileReport]) {
+    let failures: Vec<_> = reports
+        .iter()
+        .filter(|report| !report.is_ok())
+        .map(|report| format!("{}: {:?}", report.path.display(), report.failures))
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn small_examples() {
+    let reports: Vec<_> = ["associator.hom", "lips.hom", "torus.hom", "MSO2_4.hom"]
+        .into_iter()
+        .map(|name| check_file(&examples_dir().join(name)))
+        .collect();
+    assert_ok(&reports);
+}
+
+// Some of the examples take several minutes to typecheck in debug builds, so the full corpus is
+// opt-in: `cargo test --release -p homotopy-model --test regression -- --ignored`.
+#[test]
+#[ignore]
+fn examples_corpus() {
+    let reports = check_dir(&examples_dir()).expect("The examples directory should be readable.");
+    assert!(!reports.is_empty());
+    assert_ok(&reports);
+}
@@ -0,0 +1,94 @@
+use homotopy_core::{signature::Invertibility, Boundary, Diagram, DiagramN};
+use homotopy_model::proof::{Action, ProofState, Signature, Workspace};
+
+// A wire with the given beads attached from bottom to top.
+fn beads(wire: &DiagramN, beads: &[&DiagramN]) -> Diagram {
+    beads
+        .iter()
+        .fold(wire.clone().identity(), |diagram, bead| {
+            diagram.attach(bead, Boundary::Target, &[0]).unwrap()
+        })
+        .into()
+}
+
+// A proof with a rule rewriting the bead a to the bead c, and the beads a, b, a in the workspace
+// at the given path.
+fn setup(path: &[Boundary]) -> (ProofState, DiagramN, [DiagramN; 3]) {
+    let mut sig = Signature::default();
+    let x = sig.create_generator_zero("x");
+    let f = sig
+        .create_generator(x.into(), x.into(), "f", Invertibility::Directed)
+        .unwrap();
+    let [a, b, c] = ["a", "b", "c"].map(|name| {
+        sig.create_generator(
+            f.clone().into(),
+            f.clone().into(),
+            name,
+            Invertibility::Directed,
+        )
+        .unwrap()
+    });
+    sig.create_generator(
+        a.clone().into(),
+        c.clone().into(),
+        "rule",
+        Invertibility::Directed,
+    )
+    .unwrap();
+
+    let host = match path.first() {
+        Some(Boundary::Source) => beads(&f, &[&c, &b, &c]),
+        _ => beads(&f, &[&a, &b, &a]),
+    };
+    let mut workspace = Workspace::new(path.iter().fold(host, |d, _| d.identity().into()));
+    workspace.path = path.iter().map(|&b| b.into()).collect();
+
+    let proof = ProofState {
+        signature: sig,
+        workspace: Some(workspace),
+        ..Default::default()
+    };
+    (proof, f, [a, b, c])
+}
+
+#[test]
+fn rewrite_all_in_place() {
+    let (mut proof, f, [_, b, c]) = setup(&[]);
+    let rule = proof.signature.iter().last().unwrap().generator;
+
+    let action = Action::RewriteAll(rule);
+    assert!(action.is_valid(&proof));
+    assert!(proof.update(&action).unwrap());
+    assert_eq!(proof.workspace.unwrap().diagram, beads(&f, &[&c, &b, &c]));
+}
+
+#[test]
+fn rewrite_all_on_target() {
+    let (mut proof, f, [a, b, c]) = setup(&[Boundary::Target]);
+    let rule = proof.signature.iter().last().unwrap().generator;
+
+    assert!(proof.update(&Action::RewriteAll(rule)).unwrap());
+    let diagram = DiagramN::try_from(proof.workspace.unwrap().diagram).unwrap();
+    assert_eq!(diagram.source(), beads(&f, &[&a, &b, &a]));
+    assert_eq!(diagram.target(), beads(&f, &[&c, &b, &c]));
+}
+
+#[test]
+fn rewrite_all_on_source() {
+    let (mut proof, f, [a, b, c]) = setup(&[Boundary::Source]);
+    let rule = proof.signature.iter().last().unwrap().generator;
+
+    assert!(proof.update(&Action::RewriteAll(rule)).unwrap());
+    let diagram = DiagramN::try_from(proof.workspace.unwrap().diagram).unwrap();
+    assert_eq!(diagram.source(), beads(&f, &[&a, &b, &a]));
+    assert_eq!(diagram.target(), beads(&f, &[&c, &b, &c]));
+}
+
+#[test]
+fn rewrite_all_with_wrong_dimension() {
+    let (mut proof, _, _) = setup(&[]);
+    let b = proof.signature.iter().nth(3).unwrap().generator;
+
+    assert!(!Action::RewriteAll(b).is_valid(&proof));
+    assert!(!proof.update(&Action::RewriteAll(b)).unwrap());
+}
@@ -0,0 +1,88 @@
+use homotopy_core::{signature::Invertibility, Boundary::Target, Diagram, DiagramN};
+use homotopy_model::proof::{
+    search::{search, SearchError, SearchOptions},
+    ProofState, Signature, Workspace,
+};
+
+// Two parallel wires with beads a and b.
+fn beads() -> (Signature, DiagramN, [DiagramN; 2]) {
+    let mut sig = Signature::default();
+
+    let x = sig.create_generator_zero("x");
+    let f = sig
+        .create_generator(x.into(), x.into(), "f", Invertibility::Directed)
+        .unwrap();
+    let a = sig
+        .create_generator(
+            f.clone().into(),
+            f.clone().into(),
+            "a",
+            Invertibility::Directed,
+        )
+        .unwrap();
+    let b = sig
+        .create_generator(
+            f.clone().into(),
+            f.clone().into(),
+            "b",
+            Invertibility::Directed,
+        )
+        .unwrap();
+    let ff = f.attach(&f, Target, &[]).unwrap().identity();
+
+    (sig, ff, [a, b])
+}
+
+fn replay(signature: Signature, source: Diagram, target: &Diagram) {
+    let actions = search(&source, target, &signature, SearchOptions::default()).unwrap();
+
+    let mut proof = ProofState {
+        signature,
+        workspace: Some(Workspace::new(source)),
+        ..Default::default()
+    };
+    for action in &actions {
+        assert!(proof.update(action).unwrap());
+    }
+    assert_eq!(&proof.workspace.unwrap().diagram, target);
+}
+
+#[test]
+fn interchange() {
+    let (sig, ff, [a, b]) = beads();
+
+    let ab = ff.attach(&a, Target, &[0]).unwrap();
+    let ab = ab.attach(&b, Target, &[1]).unwrap();
+    let ba = ff.attach(&b, Target, &[1]).unwrap();
+    let ba = ba.attach(&a, Target, &[0]).unwrap();
+
+    replay(sig, ab.into(), &ba.into());
+}
+
+#[test]
+fn not_homotopic() {
+    let (sig, ff, [a, b]) = beads();
+
+    let a = ff.attach(&a, Target, &[0]).unwrap();
+    let b = ff.attach(&b, Target, &[0]).unwrap();
+
+    assert!(matches!(
+        search(&a.into(), &b.into(), &sig, SearchOptions::default()),
+        Err(SearchError::NotFound(_))
+    ));
+}
+
+#[test]
+fn different_dimensions() {
+    let (sig, ff, _) = beads();
+
+    assert!(matches!(
+        search(
+            &ff.clone().into(),
+            &ff.identity().into(),
+            &sig,
+            SearchOptions::default()
+        ),
+        Err(SearchError::Dimension)
+    ));
+}
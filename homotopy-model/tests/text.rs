@@ -0,0 +1,133 @@
+use homotopy_core::{signature::Invertibility, Boundary::Target, Diagram, DiagramN};
+use homotopy_model::{
+    proof::Signature,
+    text::{parse, print, print_diagram, Document, ParseErrorKind},
+};
+
+const MONOID: &str = "\
+# A monoid object with a unit.
+generator x
+generator f : x -> x
+generator m : f ; f -> f
+generator u : id(x) -> f invertible
+let unit = u ; f ; m @ 0
+";
+
+// Printing is stable under parsing, up to the numbering of generators.
+fn round_trip(document: &Document) -> Document {
+    let printed = print(document).unwrap();
+    let parsed = parse(&printed).unwrap();
+    assert_eq!(print(&parsed).unwrap(), printed);
+    parsed
+}
+
+#[test]
+fn parse_monoid() {
+    let document = parse(MONOID).unwrap();
+
+    let infos: Vec<_> = document.signature.iter().collect();
+    assert_eq!(infos.len(), 4);
+    assert_eq!(infos[3].name, "u");
+    assert_eq!(infos[3].invertibility, Invertibility::Invertible);
+
+    let Diagram::DiagramN(unit) = document.diagram("unit").unwrap() else {
+        panic!("unit is not an n-diagram");
+    };
+    assert_eq!(unit.size(), 2);
+    assert_eq!(unit.source(), infos[1].diagram);
+    assert_eq!(
+        unit.target(),
+        DiagramN::try_from(infos[2].diagram.clone())
+            .unwrap()
+            .target()
+    );
+}
+
+#[test]
+fn print_monoid() {
+    let document = parse(MONOID).unwrap();
+    assert_eq!(
+        print(&document).unwrap(),
+        "\
+generator x
+generator f : x -> x
+generator m : f ; f -> f
+generator u : id(x) -> f invertible
+let unit = id(f) ; u ; m
+"
+    );
+    round_trip(&document);
+}
+
+#[test]
+fn round_trip_signature() {
+    let mut sig = Signature::default();
+    let x = sig.create_generator_zero("x");
+    let f = sig
+        .create_generator(x.into(), x.into(), "f", Invertibility::Directed)
+        .unwrap();
+    let a = sig
+        .create_generator(
+            f.clone().into(),
+            f.clone().into(),
+            "a",
+            Invertibility::Invertible,
+        )
+        .unwrap();
+    let ff = f.attach(&f, Target, &[]).unwrap().identity();
+    let diagram = ff
+        .attach(&a, Target, &[1])
+        .unwrap()
+        .attach(&a.inverse(), Target, &[0])
+        .unwrap();
+
+    let document = Document {
+        signature: sig,
+        diagrams: vec![("example".to_owned(), diagram.clone().into())],
+    };
+    assert_eq!(round_trip(&document).diagrams, document.diagrams);
+    assert_eq!(
+        print_diagram(&diagram.into(), &document.signature).unwrap(),
+        "id(\"f 1\" ; \"f 1\") ; \"a 2\" @ 1 ; inv(\"a 2\")"
+    );
+}
+
+#[test]
+fn suspend() {
+    let document = parse(
+        "generator x\n\
+         generator e : x -> x\n\
+         let d = e ; e\n\
+         generator s\n\
+         generator t\n\
+         suspend s t",
+    )
+    .unwrap();
+
+    assert_eq!(document.diagram("d").unwrap().dimension(), 2);
+    round_trip(&document);
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse("generator x\ngenerator f : x -> y").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 20));
+    assert!(matches!(error.kind, ParseErrorKind::Undefined(name) if name == "y"));
+
+    let error = parse("generator x\ngenerator x").unwrap_err();
+    assert!(matches!(error.kind, ParseErrorKind::Redefined(_)));
+
+    let error = parse("generator x\ngenerator y\ngenerator f : x -> y\ngenerator g : f -> id(x)")
+        .unwrap_err();
+    assert!(matches!(error.kind, ParseErrorKind::Generator(_)));
+
+    let error = parse("let = x").unwrap_err();
+    assert!(matches!(error.kind, ParseErrorKind::Unexpected { .. }));
+
+    let error = parse("generator \"x").unwrap_err();
+    assert!(matches!(error.kind, ParseErrorKind::UnterminatedString));
+
+    let error = parse("generator x\nlet d = x @ 123456789012345678901234").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 13));
+    assert!(matches!(error.kind, ParseErrorKind::NumberTooLarge));
+}
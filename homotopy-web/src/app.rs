@@ -25,6 +25,7 @@ mod boundary;
 mod debug;
 pub(crate) mod diagram_gl;
 mod diagram_svg;
+mod history;
 mod image_export;
 mod info;
 mod keybindings;
@@ -35,6 +36,7 @@ mod signature;
 mod signature_stylesheet;
 mod stash;
 mod tex;
+mod topology;
 mod workspace;
 
 pub enum Message {
@@ -228,6 +230,8 @@ impl App {
                 dispatch={dispatch.clone()}
                 attachment_highlight={state.attachment_highlight.clone()}
                 slice_highlight={state.slice_highlight}
+                occurrence_highlight={state.occurrence_highlight.clone()}
+                type_error_highlight={state.type_error_highlight.clone()}
             />
         };
 
@@ -256,6 +260,7 @@ impl App {
                     proof={proof.clone()}
                     options={state.options.clone()}
                     remote_project_metadata={state.remote_project_metadata.clone()}
+                    history={state.history.clone()}
                 />
                 <div class="toaster">
                     <ToasterComponent timeout={3000} />
@@ -6,7 +6,7 @@ use std::{
 
 use chrono::{DateTime, Utc};
 use futures::future::join_all;
-use homotopy_model::proof::ProofState;
+use homotopy_model::{history::History, proof::ProofState};
 use js_sys::Uint8Array;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
@@ -94,7 +94,7 @@ pub enum ProjectsView {
 
 #[derive(Debug, Properties, Clone, PartialEq)]
 pub struct Props {
-    pub proof: model::history::Proof,
+    pub history: model::history::History,
     pub remote_project_metadata: Option<RemoteProjectMetadata>,
     pub dispatch: Callback<model::Action>,
 }
@@ -300,10 +300,10 @@ impl Component for AccountView {
             Msg::SaveProject => {
                 if let Some(id) = project_id(ctx) {
                     self.status = OperationStatus::Updating;
-                    let proof = ctx.props().proof.inner().deref().clone();
+                    let history = ctx.props().history.clone();
                     ctx.link().send_future_batch(async move {
                         vec![
-                            Msg::ProjectSaved(save_project(&id, proof).await),
+                            Msg::ProjectSaved(save_project(&id, history).await),
                             Msg::FetchProjects, // TODO: fetch only the new project instead of everything
                         ]
                     });
@@ -312,11 +312,11 @@ impl Component for AccountView {
             }
             Msg::SaveNewProject => {
                 self.status = OperationStatus::Updating;
-                let proof = ctx.props().proof.inner().deref().clone();
+                let history = ctx.props().history.clone();
                 ctx.link().send_future_batch(async {
                     let id = fresh_id().await;
                     vec![
-                        Msg::ProjectSaved(save_project(&id, proof).await),
+                        Msg::ProjectSaved(save_project(&id, history).await),
                         Msg::FetchProjects, // TODO: fetch only the new project instead of everything
                     ]
                 });
@@ -373,9 +373,9 @@ impl Component for AccountView {
             }
             Msg::PublishProjectVersion(tag) => {
                 self.status = OperationStatus::Updating;
-                let proof = ctx.props().proof.inner().deref().clone();
+                let history = ctx.props().history.clone();
                 ctx.link().send_future(async move {
-                    publish_project_version(&tag, proof).await.unwrap();
+                    publish_project_version(&tag, history).await.unwrap();
                     Msg::Resolved(Toast::success(format!("New version published: {tag}")))
                 });
                 false
@@ -810,7 +810,7 @@ struct CustomMetadata {
     r#abstract: String,
 }
 
-async fn save_project(id: &str, proof: ProofState) -> Option<RemoteProjectMetadata> {
+async fn save_project(id: &str, history: History) -> Option<RemoteProjectMetadata> {
     // get destination path
     tracing::debug!("Saving project {id}");
     let uid = user_id().unwrap();
@@ -819,6 +819,7 @@ async fn save_project(id: &str, proof: ProofState) -> Option<RemoteProjectMetada
     let storageref = storage.storage_ref(&path);
     tracing::debug!("got path: {:?}", storageref);
 
+    let proof = history.proof().inner().deref().clone();
     let mut metadata = proof.metadata;
 
     // Author is filled in with user's name if applicable
@@ -830,7 +831,13 @@ async fn save_project(id: &str, proof: ProofState) -> Option<RemoteProjectMetada
             .and_then(User::display_name)
     });
 
-    let blob = model::serialize::serialize(proof.signature, proof.workspace, metadata.clone());
+    let blob = model::serialize::serialize_session(
+        &ProofState {
+            metadata: metadata.clone(),
+            ..proof
+        },
+        Some(&history),
+    );
 
     let upload_metadata = UploadMetadata {
         content_type: "application/msgpack",
@@ -860,13 +867,14 @@ async fn save_project(id: &str, proof: ProofState) -> Option<RemoteProjectMetada
     })
 }
 
-async fn publish_project_version(tag: &str, proof: ProofState) -> Option<()> {
+async fn publish_project_version(tag: &str, history: History) -> Option<()> {
     // get destination path
     let path = format!("published-rs/{tag}/versions/new.hom");
     let storage = firebase().storage();
     let storageref = storage.storage_ref(&path);
     tracing::debug!("got path: {:?}", storageref);
 
+    let proof = history.proof().inner().deref().clone();
     let mut metadata = proof.metadata;
 
     // Author is filled in with user's name if applicable
@@ -878,7 +886,13 @@ async fn publish_project_version(tag: &str, proof: ProofState) -> Option<()> {
             .and_then(User::display_name)
     });
 
-    let blob = model::serialize::serialize(proof.signature, proof.workspace, metadata.clone());
+    let blob = model::serialize::serialize_session(
+        &ProofState {
+            metadata: metadata.clone(),
+            ..proof
+        },
+        Some(&history),
+    );
 
     let upload_metadata = UploadMetadata {
         content_type: "application/msgpack",
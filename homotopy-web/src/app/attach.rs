@@ -1,10 +1,10 @@
-use homotopy_core::{common::Generator, signature::Signature as _};
+use homotopy_core::{common::Generator, signature::Signature as _, Diagram};
 use yew::prelude::*;
 
 use crate::{
-    app::tex::TexSpan,
+    app::{diagram_svg::DiagramSvg, tex::TexSpan},
     model::{
-        proof::{self, AttachOption, Signature},
+        proof::{self, AttachOption, HomotopyOption, Signature},
         Action, Selectables,
     },
 };
@@ -25,6 +25,9 @@ pub fn AttachView(props: &Props) -> Html {
         Selectables::Merge(from, tos) => html! {
             { for tos.iter().map(|to| view_merge_option(props, *from, *to)) }
         },
+        Selectables::Homotopy(options) => html! {
+            { for options.iter().map(|option| view_homotopy_option(props, option)) }
+        },
     }
 }
 
@@ -93,3 +96,38 @@ fn view_merge_option(props: &Props, from: Generator, to: Generator) -> Html {
         </li>
     }
 }
+
+fn view_homotopy_option(props: &Props, option: &HomotopyOption) -> Html {
+    let onclick = props.dispatch.reform({
+        let homotopy = option.homotopy.clone();
+        move |_| Action::Proof(proof::Action::Homotopy(homotopy.clone()))
+    });
+
+    let ws = &option.workspace;
+    let preview = match ws.view.dimension() {
+        0 => view_preview::<0>(ws.visible_diagram(), &props.signature),
+        1 => view_preview::<1>(ws.visible_diagram(), &props.signature),
+        _ => view_preview::<2>(ws.visible_diagram(), &props.signature),
+    };
+
+    html! {
+        <li
+            class="attach__option attach__option-preview"
+            onclick={onclick}
+        >
+            {preview}
+        </li>
+    }
+}
+
+fn view_preview<const N: usize>(diagram: Diagram, signature: &Signature) -> Html {
+    html! {
+        <DiagramSvg<N>
+            id="attach__preview"
+            diagram={diagram}
+            signature={signature.clone()}
+            max_width={Some(200.)}
+            max_height={Some(200.)}
+        />
+    }
+}
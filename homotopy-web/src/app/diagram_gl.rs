@@ -5,7 +5,7 @@ use gloo::render::{request_animation_frame, AnimationFrame};
 use homotopy_core::Diagram;
 use homotopy_gl::GlCtx;
 use js_sys::Uint8Array;
-use ultraviolet::Vec3;
+use ultraviolet::{Vec2, Vec3};
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{Blob, HtmlCanvasElement, OffscreenCanvas, WebGl2RenderingContext};
@@ -25,7 +25,10 @@ use crate::{
     },
     model::{
         generate_download,
-        proof::{Signature, View},
+        proof::{
+            homotopy::{Expand, Homotopy},
+            Signature, View,
+        },
         zip_files,
     },
 };
@@ -73,6 +76,8 @@ pub enum DiagramGlMessage {
     FrameCaptureScrub(f32),
     FrameCaptureDump,
     FrameCaptureFlush,
+    ExpansionStart(Vec2),
+    ExpansionEnd(Vec2),
     Noop,
 }
 
@@ -87,6 +92,8 @@ pub struct DiagramGlProps {
     pub diagram: Diagram,
     pub signature: Signature,
     pub view: View,
+    #[prop_or_default]
+    pub on_homotopy: Callback<Homotopy>,
 }
 
 pub struct DiagramGl {
@@ -95,6 +102,8 @@ pub struct DiagramGl {
     renderer: Rc<RefCell<Option<Renderer>>>,
     global_t: f32,
     t_coord: f32,
+    // the start of a drag expanding the diagram, in client coordinates
+    expansion_start: Option<Vec2>,
 
     // If the render task is dropped, we won't get notified about `requestAnimationFrame()`
     // calls, so store a reference to the task here
@@ -145,6 +154,7 @@ impl Component for DiagramGl {
             renderer: Default::default(),
             global_t: Default::default(),
             t_coord: Default::default(),
+            expansion_start: None,
 
             render_loop: None,
 
@@ -339,6 +349,18 @@ impl Component for DiagramGl {
                     }
                 };
             }
+            DiagramGlMessage::ExpansionStart(point) => {
+                self.expansion_start = Some(point);
+            }
+            DiagramGlMessage::ExpansionEnd(point) => {
+                if let Some(expansion) = self
+                    .expansion_start
+                    .take()
+                    .and_then(|start| self.expansion(start, point))
+                {
+                    ctx.props().on_homotopy.emit(Homotopy::Expand(expansion));
+                }
+            }
             DiagramGlMessage::Noop => {}
         }
 
@@ -358,8 +380,26 @@ impl Component for DiagramGl {
             DiagramGlMessage::Noop
         });
         let on_mouse_move = OrbitCamera::on_mouse_move(interface_callback.clone());
-        let on_mouse_up = OrbitCamera::on_mouse_up(interface_callback.clone());
-        let on_mouse_down = OrbitCamera::on_mouse_down(interface_callback.clone());
+        // Dragging with shift held expands the diagram instead of moving the camera.
+        let on_mouse_up = {
+            let camera = OrbitCamera::on_mouse_up(interface_callback.clone());
+            let link = ctx.link().clone();
+            Callback::from(move |e: MouseEvent| {
+                link.send_message(DiagramGlMessage::ExpansionEnd(client_point(&e)));
+                camera.emit(e);
+            })
+        };
+        let on_mouse_down = {
+            let camera = OrbitCamera::on_mouse_down(interface_callback.clone());
+            let link = ctx.link().clone();
+            Callback::from(move |e: MouseEvent| {
+                if e.shift_key() {
+                    link.send_message(DiagramGlMessage::ExpansionStart(client_point(&e)));
+                } else {
+                    camera.emit(e);
+                }
+            })
+        };
         let on_wheel = OrbitCamera::on_wheel(&self.canvas, interface_callback.clone());
         let on_touch_move = OrbitCamera::on_touch_move(&self.canvas, interface_callback.clone());
         let on_touch_update = OrbitCamera::on_touch_update(&self.canvas, interface_callback);
@@ -419,6 +459,27 @@ impl DiagramGl {
         }));
     }
 
+    /// The expansion described by a drag between two points in client coordinates.
+    fn expansion(&self, from: Vec2, to: Vec2) -> Option<Expand> {
+        let rect = self
+            .canvas
+            .cast::<HtmlCanvasElement>()?
+            .get_bounding_client_rect();
+        let normalize = |point: Vec2| {
+            Vec2::new(
+                2. * (point.x - rect.left() as f32) / rect.width() as f32 - 1.,
+                1. - 2. * (point.y - rect.top() as f32) / rect.height() as f32,
+            )
+        };
+
+        self.renderer.borrow().as_ref()?.expansion(
+            &self.camera,
+            self.t_coord,
+            normalize(from),
+            normalize(to),
+        )
+    }
+
     /// Returns the bounding box of this [`DiagramGl`] in Canvas coordinates (Y-axis goes down).
     fn bounding_box(&self) -> Option<(i32, i32, i32, i32)> {
         let canvas = self
@@ -471,6 +532,10 @@ impl DiagramGl {
     }
 }
 
+fn client_point(e: &MouseEvent) -> Vec2 {
+    Vec2::new(e.client_x() as f32, e.client_y() as f32)
+}
+
 // https://github.com/rust-lang/rust/issues/135889
 fn pop_front_if<T>(vd: &mut VecDeque<T>, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
     let first = vd.front_mut()?;
@@ -1,9 +1,10 @@
+use homotopy_core::{Direction, Height, SliceIndex};
 use homotopy_gl::{
     draw,
     frame::{DepthTest, Frame},
     GlCtx, Result,
 };
-use ultraviolet::{Mat4, Vec4};
+use ultraviolet::{Mat4, Vec2, Vec3, Vec4};
 
 use self::{
     axes::Axes,
@@ -13,7 +14,10 @@ use self::{
     shaders::Shaders,
 };
 use super::{buffers, orbit_camera::OrbitCamera, DiagramGlProps};
-use crate::{app::AppSettings, model::proof::Signature};
+use crate::{
+    app::AppSettings,
+    model::proof::{homotopy::Expand, Signature},
+};
 
 mod axes;
 mod gbuffer;
@@ -116,6 +120,64 @@ impl Renderer {
         Ok(())
     }
 
+    /// The expansion described by a drag between two points in normalised device coordinates.
+    ///
+    /// The drag starts at the node of the mesh closest to its starting point, among those shown at
+    /// time `t` if the view is animated, and moves it along the axis of the projection which is
+    /// most closely aligned with the drag on screen.
+    pub fn expansion(&self, camera: &OrbitCamera, t: f32, from: Vec2, to: Vec2) -> Option<Expand> {
+        let transform = camera.perspective_transform(&self.ctx) * camera.view_transform(&self.ctx);
+        let project = |position: Vec3| {
+            let clip = transform * position.into_homogeneous_point();
+            clip.xy() / clip.w
+        };
+
+        // In animated views, the outermost coordinate of the projection is time.
+        let nodes =
+            self.scene.nodes.iter().filter(|(_, position)| {
+                !self.scene.animated
+                    || self.scene.nodes.iter().all(|(_, other)| {
+                        (position.w - t).abs() <= (other.w - t).abs() + f32::EPSILON
+                    })
+            });
+        let (path, position) = nodes.min_by(|(_, a), (_, b)| {
+            let a = (project(a.xyz()) - from).mag_sq();
+            let b = (project(b.xyz()) - from).mag_sq();
+            a.total_cmp(&b)
+        })?;
+
+        // The layout places the innermost coordinate of the projection first.
+        let n = path.len();
+        let drag = to - from;
+        let (axis, alignment) = (usize::from(self.scene.animated)..n - 1)
+            .map(|axis| {
+                let mut offset = Vec3::zero();
+                offset[n - 1 - axis] = 1.;
+                let screen = project(position.xyz() + offset) - project(position.xyz());
+                (axis, drag.dot(screen.normalized()))
+            })
+            .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))?;
+
+        let point: Vec<Height> = path
+            .iter()
+            .map_while(|index| match index {
+                SliceIndex::Interior(height) => Some(*height),
+                SliceIndex::Boundary(_) => None,
+            })
+            .collect();
+        (point.len() >= axis + 2).then(|| Expand {
+            point,
+            axis,
+            direction: if alignment > 0. {
+                Direction::Forward
+            } else {
+                Direction::Backward
+            },
+            location: vec![],
+            choice: 0,
+        })
+    }
+
     pub fn render(&mut self, camera: &OrbitCamera, t: f32) {
         let n = self.scene.view.dimension();
         let animated = n == 4 || n == 3 && self.animated_3d;
@@ -1,10 +1,10 @@
 use std::{mem, rc::Rc};
 
 use homotopy_common::idx::IdxVec;
-use homotopy_core::{Diagram, Diagram0, Generator};
+use homotopy_core::{Diagram, Diagram0, Generator, SliceIndex};
 use homotopy_gl::{array::VertexArray, vertex_array, GlCtx, Result};
 use homotopy_graphics::{
-    geom::{CubicalGeometry, SimplicialGeometry, VertData},
+    geom::{self, CubicalGeometry, SimplicialGeometry, VertData},
     style::{GeneratorStyle, SignatureStyleData, VertexShape},
 };
 use ultraviolet::{Vec3, Vec4};
@@ -17,6 +17,8 @@ use crate::model::proof::View;
 pub struct Scene {
     pub diagram: Diagram,
     pub view: View,
+    pub animated: bool,
+    pub nodes: Vec<(Vec<SliceIndex>, Vec4)>,
     pub components: Vec<Component<VertexArray>>,
     pub wireframe_components: Vec<VertexArray>,
     pub cylinder_components: Vec<Component<VertexArray>>,
@@ -83,6 +85,8 @@ impl Scene {
         let mut scene = Self {
             diagram,
             view,
+            animated: false,
+            nodes: vec![],
             components: vec![],
             wireframe_components: vec![],
             cylinder_components: vec![],
@@ -162,6 +166,13 @@ impl Scene {
         let n = self.view.dimension();
         let animated = n == 4 || n == 3 && animated_3d;
 
+        self.animated = animated;
+        self.nodes = match self.view.dimension() {
+            3 => node_positions::<3>(&self.diagram, animated),
+            4 => node_positions::<4>(&self.diagram, animated),
+            _ => vec![],
+        };
+
         let mut cubical = match self.view.dimension() {
             0 => CubicalGeometry::new::<0>(&self.diagram, animated).unwrap(),
             1 => CubicalGeometry::new::<1>(&self.diagram, animated).unwrap(),
@@ -317,3 +328,14 @@ impl Scene {
         Ok(())
     }
 }
+
+fn node_positions<const N: usize>(
+    diagram: &Diagram,
+    animated: bool,
+) -> Vec<(Vec<SliceIndex>, Vec4)> {
+    geom::node_positions::<N>(diagram, animated)
+        .unwrap()
+        .into_iter()
+        .map(|(path, position)| (path.to_vec(), position))
+        .collect()
+}
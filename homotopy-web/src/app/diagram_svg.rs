@@ -58,7 +58,7 @@ pub struct DiagramSvgProps<const N: usize> {
     #[prop_or_default]
     pub on_homotopy: Callback<Homotopy>,
     #[prop_or_default]
-    pub highlight: Option<HighlightSvg<N>>,
+    pub highlights: Vec<HighlightSvg<N>>,
     #[prop_or_default]
     pub max_width: Option<f32>,
     #[prop_or_default]
@@ -320,7 +320,7 @@ impl<const N: usize> Component for DiagramSvg<N> {
                     <title>{title}</title>
                 }
                 {self.prepared.graphic.iter().enumerate().map(|(i, e)| self.view_element(ctx, i, e)).collect::<Html>()}
-                {self.view_highlight(ctx)}
+                {ctx.props().highlights.iter().map(|highlight| self.view_highlight(ctx, highlight)).collect::<Html>()}
             </svg>
         }
     }
@@ -439,11 +439,7 @@ impl<const N: usize> DiagramSvg<N> {
         }
     }
 
-    fn view_highlight(&self, ctx: &Context<Self>) -> Html {
-        let Some(highlight) = &ctx.props().highlight else {
-            return Default::default();
-        };
-
+    fn view_highlight(&self, ctx: &Context<Self>, highlight: &HighlightSvg<N>) -> Html {
         let (mut from, mut to) = (
             Point2D::splat(f32::INFINITY),
             Point2D::splat(f32::NEG_INFINITY),
@@ -463,7 +459,7 @@ impl<const N: usize> DiagramSvg<N> {
         }
 
         let padding = match highlight.kind {
-            HighlightKind::Attach => {
+            HighlightKind::Attach | HighlightKind::Occurrence | HighlightKind::TypeError => {
                 let padding = ctx.props().style.scale * 0.25;
                 Vector2D::new(padding, padding)
             }
@@ -488,6 +484,8 @@ impl<const N: usize> DiagramSvg<N> {
         let class = match highlight.kind {
             HighlightKind::Attach => "diagram-svg__attach-highlight",
             HighlightKind::Slice => "diagram-svg__slice-highlight",
+            HighlightKind::Occurrence => "diagram-svg__occurrence-highlight",
+            HighlightKind::TypeError => "diagram-svg__type-error-highlight",
         };
 
         html! {
@@ -680,8 +678,10 @@ fn drag_to_homotopy<const N: usize>(
             if expansion {
                 Some(Homotopy::Expand(Expand {
                     location: prefix.into_iter().collect(),
-                    point: [y, x],
+                    point: vec![y, x],
+                    axis: 0,
                     direction,
+                    choice: 0,
                 }))
             } else {
                 let bias = if default_step != 1 {
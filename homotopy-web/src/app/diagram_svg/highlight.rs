@@ -1,10 +1,14 @@
-use homotopy_core::{common::BoundaryPath, Boundary, DiagramN, Height, SliceIndex};
+use homotopy_core::{
+    common::BoundaryPath, pattern::Occurrence, Boundary, Diagram, DiagramN, Height, SliceIndex,
+};
 use homotopy_model::proof::AttachOption;
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum HighlightKind {
     Attach,
     Slice,
+    Occurrence,
+    TypeError,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
@@ -108,3 +112,78 @@ pub fn highlight_slice<const N: usize>(slice: SliceIndex) -> HighlightSvg<N> {
         kind: HighlightKind::Slice,
     }
 }
+
+/// Highlights an occurrence of a pattern in the visible diagram. Returns `None` if the occurrence
+/// does not lie in a slice of the same dimension as the view.
+pub fn highlight_occurrence<const N: usize>(
+    pattern: &Diagram,
+    occurrence: &Occurrence,
+) -> Option<HighlightSvg<N>> {
+    use Height::Regular;
+
+    if occurrence.path.len() + pattern.dimension() != N {
+        return None;
+    }
+
+    let point = |rest: &[SliceIndex]| {
+        let mut point: Vec<SliceIndex> = occurrence.path.iter().copied().map(Into::into).collect();
+        point.extend(rest);
+        coerce::<N>(&point)
+    };
+    let embedding = &occurrence.embedding;
+
+    let points = match pattern {
+        Diagram::Diagram0(_) => vec![point(&[])],
+        Diagram::DiagramN(pattern) if pattern.dimension() == 1 => {
+            let y = embedding[0];
+            vec![
+                point(&[Regular(y).into()]),
+                point(&[Regular(y + pattern.size()).into()]),
+            ]
+        }
+        Diagram::DiagramN(pattern) => {
+            let (y, x) = (embedding[0], embedding[1]);
+            pattern
+                .regular_slices()
+                .enumerate()
+                .flat_map(|(i, slice)| {
+                    [
+                        point(&[Regular(y + i).into(), Regular(x).into()]),
+                        point(&[
+                            Regular(y + i).into(),
+                            Regular(x + slice.size().unwrap()).into(),
+                        ]),
+                    ]
+                })
+                .collect()
+        }
+    };
+
+    Some(HighlightSvg {
+        points,
+        kind: HighlightKind::Occurrence,
+    })
+}
+
+/// Highlights the point of the workspace diagram at which a homotopy failed to typecheck. Returns
+/// `None` if the point does not lie in the visible diagram.
+pub fn highlight_type_error<const N: usize>(
+    path: &[SliceIndex],
+    visible: &Diagram,
+    point: &[SliceIndex],
+) -> Option<HighlightSvg<N>> {
+    // Points in lower-dimensional slices of the view are projected onto it.
+    let point = point.strip_prefix(path)?.get(..N)?;
+
+    // The point was found in the diagram resulting from the homotopy, so it may lie outside of
+    // the visible diagram.
+    let mut slice = visible.clone();
+    for &index in point {
+        slice = DiagramN::try_from(slice).ok()?.slice(index)?;
+    }
+
+    Some(HighlightSvg {
+        points: vec![coerce(point)],
+        kind: HighlightKind::TypeError,
+    })
+}
@@ -0,0 +1,91 @@
+use homotopy_common::{hash::FastHashSet, tree::Node};
+use homotopy_model::history::{self, Outline};
+use yew::prelude::*;
+use yew_macro::function_component;
+
+use crate::{
+    components::icon::{Icon, IconSize},
+    model::Action,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub outline: Outline,
+    pub dispatch: Callback<Action>,
+}
+
+#[function_component]
+pub fn HistoryView(props: &Props) -> Html {
+    // Snapshots on the way to the current one can not be pruned.
+    let path: FastHashSet<Node> = props
+        .outline
+        .actions
+        .ancestors_of(props.outline.current)
+        .collect();
+
+    html! {
+        <ul class="history">
+            {view_sequence(props, &path, props.outline.actions.root())}
+        </ul>
+    }
+}
+
+/// Lists the snapshots from `node` onwards, as long as there is a single way forward, followed
+/// by an indented list for each branch.
+fn view_sequence(props: &Props, path: &FastHashSet<Node>, mut node: Node) -> Html {
+    let actions = &props.outline.actions;
+    let mut items = vec![view_snapshot(props, path, node)];
+    loop {
+        let children: Vec<_> = actions[node].children().collect();
+        match children[..] {
+            [] => break,
+            [child] => {
+                node = child;
+                items.push(view_snapshot(props, path, node));
+            }
+            _ => {
+                items.extend(children.into_iter().map(|child| {
+                    html! {
+                        <li>
+                            <ul class="history__branch">
+                                {view_sequence(props, path, child)}
+                            </ul>
+                        </li>
+                    }
+                }));
+                break;
+            }
+        }
+    }
+
+    html! { for items }
+}
+
+fn view_snapshot(props: &Props, path: &FastHashSet<Node>, node: Node) -> Html {
+    let name = props.outline.actions[node]
+        .inner()
+        .as_ref()
+        .map_or("Start", |action| action.name());
+    let class = if node == props.outline.current {
+        "history__snapshot history__snapshot-current"
+    } else {
+        "history__snapshot"
+    };
+    let jump = props
+        .dispatch
+        .reform(move |_| Action::History(history::Action::Move(history::Direction::Jump(node))));
+    let prune = props
+        .dispatch
+        .reform(move |_| Action::History(history::Action::Prune(node)));
+
+    html! {
+        <li class={class}>
+            <span class="history__action" onclick={jump}>{name}</span>
+            if !path.contains(&node) {
+                <span class="history__prune" onclick={prune}>
+                    <Icon name="close" size={IconSize::Icon18} />
+                </span>
+            }
+        </li>
+    }
+}
@@ -21,6 +21,10 @@ pub fn get_help_message() -> Html {
                     <td class="help-action">{"Hold  "}<kbd class="kbc-button">{"SHIFT"}</kbd></td>
                     <td class="help-description">{"Cancel inverses"}</td>
                 </tr>
+                <tr>
+                    <td class="help-action">{"Hold  "}<kbd class="kbc-button">{"SHIFT"}</kbd>{" "}<Icon name="swipe" size={IconSize::Icon24}/></td>
+                    <td class="help-description">{"Expand in 3D/4D view"}</td>
+                </tr>
                 <tr>
                     <td class="help-action"><kbd class="kbc-button">{"U"}</kbd></td>
                     <td class="help-description">{"Undo"}</td>
@@ -15,6 +15,7 @@ use crate::{
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Msg {
     ImportProof(File),
+    ApplyMorphism(File),
     EditMetadata(MetadataEdit),
     Noop,
 }
@@ -49,6 +50,14 @@ impl Component for ProjectView {
                 Msg::Noop
             }
         });
+        let apply_morphism = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(filelist) = input.files() {
+                Msg::ApplyMorphism(filelist.get(0).unwrap())
+            } else {
+                Msg::Noop
+            }
+        });
 
         html! {
             <>
@@ -57,6 +66,10 @@ impl Component for ProjectView {
                     {"Import"}
                 </label>
                 <input type="file" accept="application/msgpack,application/octet-stream,application/zip,.hom,.json,.zip" class="visually-hidden" id="import" onchange={import}/>
+                <label for="apply-morphism" class="button" title="Send each generator to the generator of the same name in another proof">
+                    {"Apply morphism"}
+                </label>
+                <input type="file" accept="application/msgpack,application/octet-stream,.hom" class="visually-hidden" id="apply-morphism" onchange={apply_morphism}/>
                 <div class="metadata__details">
                     <TexSpan
                         class="metadata__title"
@@ -147,6 +160,17 @@ impl Component for ProjectView {
                 self.reader = Some(task);
                 false
             }
+            Msg::ApplyMorphism(file) => {
+                let task = gloo::file::callbacks::read_as_bytes(
+                    &file.into(),
+                    closure!(clone dispatch, |res| {
+                        let data = res.expect("failed to read file");
+                        dispatch.emit(model::Action::Proof(model::proof::Action::ApplyMorphism(data.into())));
+                    }),
+                );
+                self.reader = Some(task);
+                false
+            }
             Msg::EditMetadata(edit) => {
                 // In order to avoid generating multiple history events for a single rename, we
                 // don't dispatch renames until the user is done editing.
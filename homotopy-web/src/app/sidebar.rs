@@ -9,7 +9,7 @@ use crate::{
         icon::{Icon, IconSize},
         Visibility,
     },
-    model::{self, proof, Proof},
+    model::{self, history::History, proof, Proof},
 };
 
 mod buttons;
@@ -266,6 +266,7 @@ pub struct SidebarProps {
     pub options: Option<model::Selectables>,
     pub dispatch: Callback<model::Action>,
     pub remote_project_metadata: Option<RemoteProjectMetadata>,
+    pub history: History,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -394,6 +395,7 @@ impl Sidebar {
                     &sidebar_dispatch,
                     &ctx.props().proof,
                     &ctx.props().remote_project_metadata,
+                    &ctx.props().history,
                     self.last_drawer_width,
                     self.drawer_view_size,
                 )
@@ -6,15 +6,18 @@ use crate::app::debug::DebugView;
 use crate::{
     app::{
         account::{AccountView, RemoteProjectMetadata},
+        history::HistoryView,
         image_export::ImageExportView,
         project::ProjectView,
         settings::SettingsView,
         signature::SignatureView,
         stash::StashView,
+        topology::TopologyView,
     },
     components::Visible,
     model::{
         self,
+        history::History,
         proof::{Action, SignatureEdit},
         Proof,
     },
@@ -44,13 +47,14 @@ macro_rules! declare_sidebar_drawers {
         }
 
         impl NavDrawer {
-            #[allow(clippy::let_underscore_untyped)]
+            #[allow(clippy::let_underscore_untyped, clippy::too_many_arguments)]
             pub(super) fn view(
                 self,
                 model_dispatch: &Callback<model::Action>,
                 sidebar_dispatch: &Callback<SidebarMsg>,
                 proof: &Proof,
                 remote_project_metadata: &Option<RemoteProjectMetadata>,
+                history: &History,
                 initial_width: i32,
                 drawer_view_size: DrawerViewSize,
             ) -> Html {
@@ -73,7 +77,7 @@ macro_rules! declare_sidebar_drawers {
                                         action(proof)
                                     })?
                                 >
-                                    {body(model_dispatch, proof, drawer_view_size, remote_project_metadata)}
+                                    {body(model_dispatch, proof, drawer_view_size, remote_project_metadata, history)}
                                 </SidebarDrawer>
                             }
                         }
@@ -116,10 +120,10 @@ declare_sidebar_drawers! {
         "Account",
         "account",
         "account_circle",
-        |dispatch, proof: &Proof, _, remote_project_metadata: &Option<RemoteProjectMetadata>| html! {
+        |dispatch, _, _, remote_project_metadata: &Option<RemoteProjectMetadata>, history: &History| html! {
             <AccountView
                 dispatch={dispatch}
-                proof={proof.clone()}
+                history={history.clone()}
                 remote_project_metadata={remote_project_metadata.clone()}
             />
         },
@@ -130,11 +134,22 @@ declare_sidebar_drawers! {
         "Project",
         "project",
         "info",
-        |dispatch, proof: &Proof, _, _| html! {
-            <ProjectView
-                dispatch={dispatch}
-                metadata={proof.metadata.clone()}
-            />
+        |dispatch, proof: &Proof, _, _, _| html! {
+            <>
+                <ProjectView
+                    dispatch={dispatch}
+                    metadata={proof.metadata.clone()}
+                />
+                {proof.workspace.as_ref().map(|ws| html! {
+                    <div class="topology">
+                        <h3>{"Topology"}</h3>
+                        <TopologyView
+                            diagram={ws.diagram.clone()}
+                            signature={proof.signature.clone()}
+                        />
+                    </div>
+                }).unwrap_or_default()}
+            </>
         },
         min_width: 250,
     }
@@ -143,7 +158,7 @@ declare_sidebar_drawers! {
         "Signature",
         "signature",
         "list",
-        |dispatch, proof: &Proof, drawer_view_size: DrawerViewSize, _| html! {
+        |dispatch, proof: &Proof, drawer_view_size: DrawerViewSize, _, _| html! {
             <SignatureView
                 signature={proof.signature.clone()}
                 dispatch={dispatch}
@@ -159,7 +174,7 @@ declare_sidebar_drawers! {
         "Stash",
         "stash",
         "bookmarks",
-        |dispatch, proof: &Proof, _, _| html! {
+        |dispatch, proof: &Proof, _, _, _| html! {
             <StashView
                 stash={proof.stash.clone()}
                 dispatch={dispatch}
@@ -169,11 +184,24 @@ declare_sidebar_drawers! {
         min_width: 250,
     }
 
+    DRAWER_HISTORY {
+        "History",
+        "history",
+        "history",
+        |dispatch, _, _, _, history: &History| html! {
+            <HistoryView
+                outline={history.outline()}
+                dispatch={dispatch}
+            />
+        },
+        min_width: 250,
+    }
+
     DRAWER_IMAGE_EXPORT {
         "Image export",
         "ImageExport",
         "output",
-        |dispatch, proof: &Proof, _, _| match proof.workspace.as_ref() {
+        |dispatch, proof: &Proof, _, _, _| match proof.workspace.as_ref() {
             None => html! {
                 <p>{"There is nothing to export."}</p>
             },
@@ -192,7 +220,7 @@ declare_sidebar_drawers! {
         "Settings",
         "settings",
         "settings",
-        |_, _, _, _| html! {
+        |_, _, _, _, _| html! {
             <SettingsView />
         },
         min_width: 250,
@@ -203,7 +231,7 @@ declare_sidebar_drawers! {
         "Debug",
         "debug",
         "bug_report",
-        |dispatch, proof: &Proof, _, _| html! {
+        |dispatch, proof: &Proof, _, _, _| html! {
             <DebugView proof={proof.clone()} dispatch={dispatch} />
         },
         min_width: 250,
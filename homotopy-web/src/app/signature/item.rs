@@ -829,12 +829,28 @@ impl ItemView {
                 <button onclick={ctx.props().dispatch.reform(move |_| Action::Merge(generator))}>{"Merge"}</button>
             </div>
         };
+        let rewrite_all_button = html! {
+            <div>
+                <button onclick={ctx.props().dispatch.reform(move |_| proof::Action::RewriteAll(generator).into())}>{"Rewrite all"}</button>
+            </div>
+        };
+        let inline_button = html! {
+            <div>
+                <button onclick={ctx.props().dispatch.reform(move |_| proof::Action::Inline(generator).into())}>{"Inline"}</button>
+            </div>
+        };
+        let occurrences_button = html! {
+            <div>
+                <button onclick={ctx.props().dispatch.reform(move |_| Action::FindOccurrences(generator))}>{"Find occurrences"}</button>
+            </div>
+        };
 
         match generator.dimension {
             0 => html! {
                 <>
                     {suspension_button}
                     {merge_button}
+                    {occurrences_button}
                 </>
             },
             _ => html! {
@@ -863,6 +879,9 @@ impl ItemView {
                         disabled={info.oriented}
                     />
                     {merge_button}
+                    {occurrences_button}
+                    {rewrite_all_button}
+                    {inline_button}
                 </div>
             },
         }
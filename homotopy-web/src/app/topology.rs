@@ -0,0 +1,124 @@
+use homotopy_core::{
+    signature::Signature as _,
+    topology::{stratum_invariants, Invariants, TopologyError},
+    Diagram, Generator,
+};
+use homotopy_model::proof::Signature;
+use yew::prelude::*;
+
+use crate::app::tex::TexSpan;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Msg {
+    Compute,
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct Props {
+    pub diagram: Diagram,
+    pub signature: Signature,
+}
+
+/// Shows the topological invariants of the strata of the workspace diagram.
+///
+/// The invariants are only computed on request, since this can take a while for large diagrams.
+#[derive(Debug, Default)]
+pub struct TopologyView {
+    #[allow(clippy::type_complexity)]
+    invariants: Option<(Diagram, Result<Vec<(Generator, Invariants)>, TopologyError>)>,
+}
+
+impl Component for TopologyView {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Compute => {
+                let diagram = ctx.props().diagram.clone();
+                let invariants = stratum_invariants(&diagram);
+                self.invariants = Some((diagram, invariants));
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let compute = ctx.link().callback(|_| Msg::Compute);
+        let strata = match &self.invariants {
+            Some((diagram, invariants)) if *diagram == ctx.props().diagram => match invariants {
+                Ok(invariants) => invariants
+                    .iter()
+                    .map(|(generator, invariants)| {
+                        view_stratum(&ctx.props().signature, *generator, invariants)
+                    })
+                    .collect(),
+                Err(error) => html! {
+                    <p>{format!("Cannot compute invariants: {error}.")}</p>
+                },
+            },
+            _ => html! {},
+        };
+
+        html! {
+            <>
+                <button onclick={compute}>{"Compute invariants"}</button>
+                <div class="topology__strata">
+                    {strata}
+                </div>
+            </>
+        }
+    }
+}
+
+fn view_stratum(signature: &Signature, generator: Generator, invariants: &Invariants) -> Html {
+    let name = signature
+        .generator_info(generator)
+        .map(|info| info.name.clone())
+        .unwrap_or_default();
+    let orientable = if invariants.orientable { "yes" } else { "no" };
+
+    html! {
+        <div class="topology__stratum">
+            <TexSpan class="topology__name" raw_tex={name} />
+            <table class="topology__invariants">
+                <tr>
+                    <td>{"Dimension"}</td>
+                    <td>{invariants.dimension}</td>
+                </tr>
+                <tr>
+                    <td>{"Euler characteristic"}</td>
+                    <td>{invariants.euler_characteristic}</td>
+                </tr>
+                <tr>
+                    <td>{"Orientable"}</td>
+                    <td>{orientable}</td>
+                </tr>
+                <tr>
+                    <td>{"Boundary components"}</td>
+                    <td>{invariants.boundary_components}</td>
+                </tr>
+                <tr>
+                    <td>{"Betti numbers mod 2"}</td>
+                    <td>{join(&invariants.betti_numbers_mod_2)}</td>
+                </tr>
+                <tr>
+                    <td>{"Homology"}</td>
+                    <td>{join(&invariants.homology)}</td>
+                </tr>
+            </table>
+        </div>
+    }
+}
+
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
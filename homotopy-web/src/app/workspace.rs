@@ -8,7 +8,9 @@ use crate::{
     app::{
         diagram_gl::DiagramGl,
         diagram_svg::{
-            highlight::{highlight_attachment, highlight_slice},
+            highlight::{
+                highlight_attachment, highlight_occurrence, highlight_slice, highlight_type_error,
+            },
             DiagramSvg,
         },
         info::get_onboarding_message,
@@ -18,7 +20,7 @@ use crate::{
     components::panzoom::PanZoomComponent,
     model::{
         proof::{self, AttachOption, Metadata, Signature, Workspace},
-        Action,
+        Action, OccurrenceHighlight,
     },
 };
 
@@ -36,6 +38,8 @@ pub struct Props {
     pub metadata: Metadata,
     pub attachment_highlight: Option<AttachOption>,
     pub slice_highlight: Option<SliceIndex>,
+    pub occurrence_highlight: Option<OccurrenceHighlight>,
+    pub type_error_highlight: Option<Vec<SliceIndex>>,
 }
 
 #[function_component]
@@ -112,6 +116,7 @@ fn view_diagram(props: &Props, diagram_ref: NodeRef) -> Html {
                         diagram={ws.visible_diagram()}
                         signature={props.signature.clone()}
                         view={ws.view}
+                        on_homotopy={props.dispatch.reform(Action::SelectHomotopy)}
                     />
                 }
             }
@@ -125,9 +130,7 @@ fn view_diagram_svg<const N: usize>(props: &Props, diagram_ref: NodeRef) -> Html
     let on_select = props
         .dispatch
         .reform(|p| Action::SelectPoint(p, AppSettings::get_weak_units()));
-    let on_homotopy = props
-        .dispatch
-        .reform(|homotopy| Action::Proof(proof::Action::Homotopy(homotopy)));
+    let on_homotopy = props.dispatch.reform(Action::SelectHomotopy);
 
     if let Some(ref ws) = props.workspace {
         let attachment_highlight = props
@@ -135,7 +138,21 @@ fn view_diagram_svg<const N: usize>(props: &Props, diagram_ref: NodeRef) -> Html
             .as_ref()
             .map(|option| highlight_attachment::<N>(ws.path.len(), option));
         let slice_highlight = props.slice_highlight.map(highlight_slice::<N>);
-        let highlight = attachment_highlight.or(slice_highlight);
+        let occurrence_highlights = props.occurrence_highlight.iter().flat_map(|highlight| {
+            highlight
+                .occurrences
+                .iter()
+                .filter_map(|occurrence| highlight_occurrence::<N>(&highlight.pattern, occurrence))
+        });
+        let highlights = attachment_highlight
+            .or(slice_highlight)
+            .into_iter()
+            .chain(occurrence_highlights)
+            .chain(props.type_error_highlight.as_ref().and_then(|point| {
+                let path: Vec<_> = ws.path.iter().copied().collect();
+                highlight_type_error::<N>(&path, &ws.visible_diagram(), point)
+            }))
+            .collect::<Vec<_>>();
         html! {
             <PanZoomComponent on_scroll={props.dispatch.reform(Action::Proof).reform(proof::Action::SwitchSlice)}>
                 <DiagramSvg<N>
@@ -144,7 +161,7 @@ fn view_diagram_svg<const N: usize>(props: &Props, diagram_ref: NodeRef) -> Html
                     signature={props.signature.clone()}
                     on_select={on_select}
                     on_homotopy={on_homotopy}
-                    highlight={highlight}
+                    highlights={highlights}
                     diagram_ref={diagram_ref}
                 />
             </PanZoomComponent>
@@ -4,11 +4,13 @@ pub use history::Proof;
 use history::{History, UndoState};
 use homotopy_core::{
     common::{BoundaryPath, Generator},
+    pattern::{occurrences, Occurrence},
     signature::Signature,
+    typecheck::TypeError,
     Boundary, Diagram, DiagramN, Height, SliceIndex,
 };
 use homotopy_graphics::{manim, stl, svg, tikz};
-use homotopy_model::proof::AttachOption;
+use homotopy_model::proof::{homotopy::Homotopy, AttachOption, HomotopyOption};
 pub use homotopy_model::{history, migration, proof, serialize};
 use serde::Serialize;
 use thiserror::Error;
@@ -33,8 +35,10 @@ pub enum Action {
     ClearSelections,
     Merge(Generator),
     SelectPoint(Vec<SliceIndex>, bool),
+    SelectHomotopy(Homotopy),
     HighlightAttachment(Option<AttachOption>),
     HighlightSlice(Option<SliceIndex>),
+    FindOccurrences(Generator),
 
     SetRemoteProjectMetadata(Option<account::RemoteProjectMetadata>),
 
@@ -80,7 +84,9 @@ impl Action {
     pub fn is_valid(&self, proof: &Proof) -> bool {
         match self {
             Self::Proof(action) => action.is_valid(proof),
-            Self::History(history::Action::Move(dir)) => proof.can_move(dir),
+            Self::History(history::Action::Move(history::Direction::Linear(dir))) => {
+                proof.can_move(*dir)
+            }
             _ => true,
         }
     }
@@ -111,6 +117,7 @@ impl From<history::Action> for Action {
 pub enum Selectables {
     Merge(Generator, Vec<Generator>),
     Attach(Vec<AttachOption>),
+    Homotopy(Vec<HomotopyOption>),
 }
 
 impl Selectables {
@@ -118,11 +125,19 @@ impl Selectables {
     pub const fn name(&self) -> &'static str {
         match self {
             Self::Attach(_) => "Attach",
+            Self::Homotopy(_) => "Homotopy",
             Self::Merge(_, _) => "Merge",
         }
     }
 }
 
+/// The occurrences of a pattern in the visible diagram.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OccurrenceHighlight {
+    pub pattern: Diagram,
+    pub occurrences: Vec<Occurrence>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct State {
     pub remote_project_metadata: Option<account::RemoteProjectMetadata>,
@@ -130,6 +145,9 @@ pub struct State {
     pub options: Option<Selectables>,
     pub attachment_highlight: Option<AttachOption>,
     pub slice_highlight: Option<SliceIndex>,
+    pub occurrence_highlight: Option<OccurrenceHighlight>,
+    /// The point of the workspace diagram at which the last homotopy failed to typecheck.
+    pub type_error_highlight: Option<Vec<SliceIndex>>,
 }
 
 impl State {
@@ -154,16 +172,48 @@ impl State {
             Action::Proof(action) => {
                 // Only exfiltrate proof actions, otherwise
                 // we risk funny business with circular action imports.
-                crate::panic::push_action(&action);
+                // Navigation is only recorded once it leads to a change to the proof.
+                let recorded = if action.is_navigation() {
+                    vec![]
+                } else {
+                    let mut recorded = self.history.navigation();
+                    recorded.push(action.clone());
+                    recorded
+                };
+                for action in &recorded {
+                    crate::panic::push_action(action);
+                }
 
+                // A proof may be saved with the history which led to it, which is grafted onto
+                // the snapshot of the import. Redoing the import returns to that snapshot.
                 if self.history.try_redo(&action).is_err() {
+                    let mut saved_history = None;
                     let mut proof = self.proof().clone();
-                    let res = proof.update(&action);
+                    let res = match &action {
+                        proof::Action::ImportProof(data) => {
+                            proof.import_proof(data).map(|history| {
+                                saved_history = history;
+                                true
+                            })
+                        }
+                        _ => proof.update(&action),
+                    };
                     if matches!(res, Err(_) | Ok(false)) {
-                        crate::panic::pop_action();
+                        for _ in &recorded {
+                            crate::panic::pop_action();
+                        }
+                        if let Err(error) = &res {
+                            self.type_error_highlight = error
+                                .type_error()
+                                .and_then(TypeError::location)
+                                .map(|location| location.point.clone());
+                        }
                         return Ok(res?);
                     }
                     self.history.add(action, proof);
+                    if let Some(history) = saved_history {
+                        self.history.graft(history);
+                    }
                 }
                 self.clear_selections();
             }
@@ -171,27 +221,19 @@ impl State {
             Action::History(history::Action::Move(dir)) => {
                 use homotopy_core::Direction::{Backward, Forward};
                 match dir {
-                    history::Direction::Linear(Forward) => {
-                        self.history.redo()?;
-                        if let Some(action) = self.history.last_action() {
-                            crate::panic::push_action(&action);
-                        }
-                    }
-                    history::Direction::Linear(Backward) => {
-                        self.history.undo()?;
-                        // The action we just undid is an ImportProof
-                        // So we need to reimport the context before
-                        // that into the panic handler.
-                        if !crate::panic::pop_action() {
-                            for a in self.history.get_last_import_segment() {
-                                crate::panic::push_action(&a);
-                            }
-                        }
-                    }
+                    history::Direction::Linear(Forward) => self.history.redo()?,
+                    history::Direction::Linear(Backward) => self.history.undo()?,
+                    history::Direction::Sibling(dir) => self.history.move_to_sibling(dir)?,
+                    history::Direction::Jump(node) => self.history.jump(node)?,
                 };
+                // Each snapshot records the navigation before the action which led to it, so the
+                // actions leading to the new snapshot are rebuilt for the panic handler.
+                self.reset_crash_dump();
                 self.clear_selections();
             }
 
+            Action::History(history::Action::Prune(node)) => self.history.prune(node)?,
+
             Action::ExportImage(ImageFormat::Svg, option) => {
                 assert_eq!(
                     option,
@@ -306,11 +348,7 @@ impl State {
             }
 
             Action::ExportProof => {
-                let data = serialize::serialize(
-                    self.proof().signature.clone(),
-                    self.proof().workspace.clone(),
-                    self.proof().metadata.clone(),
-                );
+                let data = serialize::serialize_session(self.proof(), Some(&self.history));
                 generate_download("homotopy_io_export", "hom", data.as_slice())
                     .map_err(ModelError::Export)?;
             }
@@ -353,12 +391,21 @@ impl State {
                         *from,
                         *tos.get(index).ok_or(ModelError::IndexOutOfBounds)?,
                     ),
+                    Some(Selectables::Homotopy(options)) => proof::Action::Homotopy(
+                        options
+                            .get(index)
+                            .ok_or(ModelError::IndexOutOfBounds)?
+                            .homotopy
+                            .clone(),
+                    ),
                 };
                 self.update(Action::Proof(action))?;
             }
             Action::SelectPoint(point, weak_units) => self.select_point(&point, weak_units)?,
+            Action::SelectHomotopy(homotopy) => self.select_homotopy(homotopy)?,
             Action::HighlightAttachment(option) => self.highlight_attachment(option),
             Action::HighlightSlice(slice) => self.highlight_slice(slice),
+            Action::FindOccurrences(generator) => self.find_occurrences(generator)?,
             Action::ClearSelections => self.clear_selections(),
             Action::Merge(generator) => self.merge_options(generator),
             Action::SetRemoteProjectMetadata(metadata) => {
@@ -370,6 +417,14 @@ impl State {
         Ok(true)
     }
 
+    /// Replaces the actions recorded for crash dumps by those leading to the current snapshot.
+    fn reset_crash_dump(&self) {
+        crate::panic::clear_actions();
+        for action in self.history.get_last_import_segment() {
+            crate::panic::push_action(&action);
+        }
+    }
+
     /// Handler for [Action::SelectPoint].
     fn select_point(&mut self, point: &[SliceIndex], weak_units: bool) -> Result<(), ModelError> {
         let Some(workspace) = self.proof().workspace.as_ref() else {
@@ -416,7 +471,7 @@ impl State {
             }
             let bubble = |mut diagram: DiagramN| {
                 while diagram.dimension() < haystack.dimension() + 1 {
-                    diagram = diagram.bubble().unwrap();
+                    diagram = diagram.bubble(&self.proof().signature).unwrap();
                 }
                 diagram
             };
@@ -480,6 +535,22 @@ impl State {
         Ok(())
     }
 
+    /// Handler for [Action::SelectHomotopy].
+    fn select_homotopy(&mut self, homotopy: Homotopy) -> Result<(), ModelError> {
+        let mut options = self.proof().homotopy_options(&homotopy);
+        if options.len() > 1 {
+            self.options = Some(Selectables::Homotopy(options));
+            self.attachment_highlight = None;
+            self.slice_highlight = None;
+            return Ok(());
+        }
+
+        // Applying the homotopy itself reports why it fails when there is no outcome.
+        let homotopy = options.pop().map_or(homotopy, |option| option.homotopy);
+        self.update(Action::Proof(proof::Action::Homotopy(homotopy)))?;
+        Ok(())
+    }
+
     /// Handler for [Action::HighlightAttachment].
     fn highlight_attachment(&mut self, option: Option<AttachOption>) {
         self.attachment_highlight = option;
@@ -490,11 +561,39 @@ impl State {
         self.slice_highlight = option;
     }
 
+    /// Handler for [Action::FindOccurrences].
+    fn find_occurrences(&mut self, generator: Generator) -> Result<(), ModelError> {
+        let Some(workspace) = self.proof().workspace.as_ref() else {
+            return Ok(());
+        };
+        let pattern = self
+            .proof()
+            .signature
+            .generator_info(generator)
+            .ok_or(ModelError::Internal)?
+            .diagram
+            .clone();
+
+        let occurrences = occurrences(&pattern, &workspace.visible_diagram());
+        if occurrences.is_empty() {
+            self.occurrence_highlight = None;
+            return Err(ModelError::NoOccurrences);
+        }
+
+        self.occurrence_highlight = Some(OccurrenceHighlight {
+            pattern,
+            occurrences,
+        });
+        Ok(())
+    }
+
     /// Handler for [Action::ClearSelections].
     fn clear_selections(&mut self) {
         self.options = None;
         self.attachment_highlight = None;
         self.slice_highlight = None;
+        self.occurrence_highlight = None;
+        self.type_error_highlight = None;
     }
 
     /// Handler for [Action::MergeOptions].
@@ -535,6 +634,8 @@ pub enum ModelError {
     Internal,
     #[error("no attachment found")]
     NoAttachment,
+    #[error("no occurrences found")]
+    NoOccurrences,
     #[error("index out of bounds")]
     IndexOutOfBounds,
 }
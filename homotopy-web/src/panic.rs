@@ -112,6 +112,10 @@ pub fn pop_action() -> bool {
     CRASH_INFO.lock().unwrap().pop_action()
 }
 
+pub fn clear_actions() {
+    *CRASH_INFO.lock().unwrap() = CrashDump::new();
+}
+
 pub fn needs_zip() -> bool {
     CRASH_INFO.lock().unwrap().needs_zip()
 }